@@ -105,6 +105,14 @@ fn main() -> Result<()> {
                     },
                 },
                 close_proposal_on_execution_failure: false,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                commit_reveal: None,
+                delegation_registry: None,
+                partial_execution: false,
+                power_transform: None,
+                review_period: None,
+                voting_period_choice: None,
             })?,
             admin: Some(Admin::CoreModule {}),
             label: "DAO DAO Proposal Module".to_string(),
@@ -72,6 +72,14 @@ pub fn create_dao(
                 allow_revoting: false,
                 only_members_execute: true,
                 close_proposal_on_execution_failure: false,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                commit_reveal: None,
+                delegation_registry: None,
+                partial_execution: false,
+                power_transform: None,
+                review_period: None,
+                voting_period_choice: None,
                 pre_propose_info: PreProposeInfo::ModuleMayPropose {
                     info: ModuleInstantiateInfo {
                         code_id: chain.orc.contract_map.code_id("dao_pre_propose_single")?,
@@ -118,15 +126,16 @@ pub fn create_dao(
         .unwrap();
 
     let ProposalCreationPolicy::Module { addr: pre_propose } = chain
-	.orc
-	.query(
-	    "dao_proposal_single",
-	    &dao_proposal_single::msg::QueryMsg::ProposalCreationPolicy {}
-	).unwrap()
-	.data()
-	.unwrap()
+        .orc
+        .query(
+            "dao_proposal_single",
+            &dao_proposal_single::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap()
+        .data()
+        .unwrap()
     else {
-	panic!("expected pre-propose module")
+        panic!("expected pre-propose module")
     };
     chain
         .orc
@@ -240,6 +249,10 @@ pub fn create_proposal(
                     title: "title".to_string(),
                     description: "desc".to_string(),
                     msgs,
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             key,
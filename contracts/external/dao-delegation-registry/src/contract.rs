@@ -0,0 +1,248 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    Storage,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use dao_voting::voting::get_voting_power;
+
+use crate::error::ContractError;
+use crate::msg::{DelegationResponse, DelegatorsResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{DAO, DELEGATIONS, DELEGATORS, DELEGATOR_COUNTS, GLOBAL_SCOPE, MAX_DELEGATORS};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-delegation-registry";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("dao", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Delegate {
+            delegate,
+            proposal_module,
+        } => execute_delegate(deps, env, info, delegate, proposal_module),
+        ExecuteMsg::Undelegate { proposal_module } => {
+            execute_undelegate(deps, env, info, proposal_module)
+        }
+    }
+}
+
+/// Validates PROPOSAL_MODULE and returns the scope of delegations
+/// to it.
+fn scope(api: &dyn Api, proposal_module: Option<String>) -> StdResult<String> {
+    proposal_module
+        .map(|module| api.addr_validate(&module).map(Addr::into_string))
+        .unwrap_or_else(|| Ok(GLOBAL_SCOPE.to_string()))
+}
+
+fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+    proposal_module: Option<String>,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+    // Delegators are listed when their delegate votes, so require
+    // voting power to prevent spamming a delegate with empty
+    // delegations.
+    let dao = DAO.load(deps.storage)?;
+    if get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+    let scope = scope(deps.api, proposal_module)?;
+
+    let previous = DELEGATIONS.may_load(deps.storage, (&info.sender, &scope))?;
+    if previous.as_ref() != Some(&delegate) {
+        if let Some(previous) = previous {
+            remove_delegator(deps.storage, &previous, &info.sender)?;
+        }
+        add_delegator(deps.storage, &delegate, &info.sender)?;
+    }
+    DELEGATIONS.save(
+        deps.storage,
+        (&info.sender, &scope),
+        &delegate,
+        env.block.height,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("proposal_module", display_scope(&scope)))
+}
+
+fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_module: Option<String>,
+) -> Result<Response, ContractError> {
+    let scope = scope(deps.api, proposal_module)?;
+    let delegate = DELEGATIONS
+        .may_load(deps.storage, (&info.sender, &scope))?
+        .ok_or(ContractError::NoDelegation {})?;
+    remove_delegator(deps.storage, &delegate, &info.sender)?;
+    DELEGATIONS.remove(deps.storage, (&info.sender, &scope), env.block.height)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("proposal_module", display_scope(&scope)))
+}
+
+/// Records that one more of DELEGATOR's scopes is delegated to
+/// DELEGATE, failing if this would give DELEGATE more than
+/// `MAX_DELEGATORS` delegators.
+fn add_delegator(
+    storage: &mut dyn Storage,
+    delegate: &Addr,
+    delegator: &Addr,
+) -> Result<(), ContractError> {
+    let count = DELEGATORS
+        .may_load(storage, (delegate, delegator))?
+        .unwrap_or_default();
+    if count == 0 {
+        let delegators = DELEGATOR_COUNTS
+            .may_load(storage, delegate)?
+            .unwrap_or_default();
+        if delegators as usize >= MAX_DELEGATORS {
+            return Err(ContractError::TooManyDelegators {
+                max: MAX_DELEGATORS,
+            });
+        }
+        DELEGATOR_COUNTS.save(storage, delegate, &(delegators + 1))?;
+    }
+    DELEGATORS.save(storage, (delegate, delegator), &(count + 1))?;
+    Ok(())
+}
+
+/// Records that one fewer of DELEGATOR's scopes is delegated to
+/// DELEGATE. The pair is kept even if none remain, as DELEGATOR is
+/// still DELEGATE's delegator as of earlier heights.
+fn remove_delegator(storage: &mut dyn Storage, delegate: &Addr, delegator: &Addr) -> StdResult<()> {
+    let count = DELEGATORS.load(storage, (delegate, delegator))?;
+    if count == 1 {
+        let delegators = DELEGATOR_COUNTS.load(storage, delegate)?;
+        DELEGATOR_COUNTS.save(storage, delegate, &(delegators - 1))?;
+    }
+    DELEGATORS.save(storage, (delegate, delegator), &(count - 1))
+}
+
+fn display_scope(scope: &str) -> &str {
+    if scope == GLOBAL_SCOPE {
+        "_all"
+    } else {
+        scope
+    }
+}
+
+/// Returns who DELEGATOR's votes in SCOPE are delegated to as of the
+/// start of block HEIGHT. Delegations to a proposal module take
+/// precedence over global ones.
+fn load_delegation(
+    storage: &dyn Storage,
+    delegator: &Addr,
+    scope: &str,
+    height: Option<u64>,
+) -> StdResult<Option<Addr>> {
+    let load = |scope: &str| match height {
+        Some(height) => DELEGATIONS.may_load_at_height(storage, (delegator, scope), height),
+        None => DELEGATIONS.may_load(storage, (delegator, scope)),
+    };
+    match load(scope)? {
+        None if scope != GLOBAL_SCOPE => load(GLOBAL_SCOPE),
+        delegate => Ok(delegate),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Delegation {
+            delegator,
+            proposal_module,
+            height,
+        } => {
+            let delegator = deps.api.addr_validate(&delegator)?;
+            let scope = scope(deps.api, proposal_module)?;
+            to_binary(&DelegationResponse {
+                delegate: load_delegation(deps.storage, &delegator, &scope, height)?,
+            })
+        }
+        QueryMsg::Delegators {
+            delegate,
+            proposal_module,
+            height,
+            start_after,
+            limit,
+        } => query_delegators(deps, delegate, proposal_module, height, start_after, limit),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+fn query_delegators(
+    deps: Deps,
+    delegate: String,
+    proposal_module: Option<String>,
+    height: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let scope = scope(deps.api, proposal_module)?;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    let delegators = DELEGATORS
+        .prefix(&delegate)
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .filter_map(|delegator| {
+            delegator
+                .and_then(|delegator| {
+                    let current = load_delegation(deps.storage, &delegator, &scope, height)?;
+                    Ok((current.as_ref() == Some(&delegate)).then_some(delegator))
+                })
+                .transpose()
+        })
+        .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&DelegatorsResponse { delegators })
+}
@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("can not delegate voting power to yourself")]
+    SelfDelegation {},
+
+    #[error("non-zero voting power required to delegate")]
+    ZeroVotingPower {},
+
+    #[error("no delegation to remove")]
+    NoDelegation {},
+
+    #[error("delegates may have at most ({max}) delegators")]
+    TooManyDelegators { max: usize },
+}
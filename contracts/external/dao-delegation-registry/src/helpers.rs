@@ -0,0 +1,32 @@
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+
+use crate::msg::{DelegatorsResponse, QueryMsg};
+
+/// Returns every address whose votes on proposals in PROPOSAL_MODULE
+/// were delegated to DELEGATE in REGISTRY as of the start of block
+/// HEIGHT. At most `MAX_DELEGATORS` addresses are returned.
+pub fn query_delegators(
+    querier: &QuerierWrapper,
+    registry: &Addr,
+    delegate: &Addr,
+    proposal_module: &Addr,
+    height: u64,
+) -> StdResult<Vec<Addr>> {
+    let mut delegators: Vec<Addr> = vec![];
+    loop {
+        let page: DelegatorsResponse = querier.query_wasm_smart(
+            registry,
+            &QueryMsg::Delegators {
+                delegate: delegate.to_string(),
+                proposal_module: Some(proposal_module.to_string()),
+                height: Some(height),
+                start_after: delegators.last().map(Addr::to_string),
+                limit: None,
+            },
+        )?;
+        if page.delegators.is_empty() {
+            return Ok(delegators);
+        }
+        delegators.extend(page.delegators);
+    }
+}
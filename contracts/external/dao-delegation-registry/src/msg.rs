@@ -0,0 +1,64 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Delegates the sender's votes to DELEGATE. If PROPOSAL_MODULE
+    /// is set the delegation only applies to proposals in that
+    /// module, and takes precedence over the sender's delegation
+    /// without a proposal module. Replaces any existing delegation
+    /// for the same proposal module.
+    Delegate {
+        delegate: String,
+        proposal_module: Option<String>,
+    },
+    /// Removes the sender's delegation for PROPOSAL_MODULE, or their
+    /// delegation for all proposal modules if not set.
+    Undelegate { proposal_module: Option<String> },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns who DELEGATOR's votes on proposals in PROPOSAL_MODULE
+    /// are delegated to as of the start of block HEIGHT. If no
+    /// proposal module is set only delegations for all proposal
+    /// modules are considered. If no height is set the current
+    /// delegation is returned.
+    #[returns(DelegationResponse)]
+    Delegation {
+        delegator: String,
+        proposal_module: Option<String>,
+        height: Option<u64>,
+    },
+    /// Lists the addresses whose votes on proposals in
+    /// PROPOSAL_MODULE are delegated to DELEGATE as of the start of
+    /// block HEIGHT, in ascending order of address.
+    #[returns(DelegatorsResponse)]
+    Delegators {
+        delegate: String,
+        proposal_module: Option<String>,
+        height: Option<u64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the address of the DAO this registry belongs to.
+    #[returns(cosmwasm_std::Addr)]
+    Dao {},
+    /// Returns contract version info.
+    #[returns(dao_interface::voting::InfoResponse)]
+    Info {},
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegate: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct DelegatorsResponse {
+    pub delegators: Vec<Addr>,
+}
@@ -0,0 +1,34 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+
+/// The scope of delegations that apply to every proposal module.
+pub(crate) const GLOBAL_SCOPE: &str = "";
+
+/// The DAO this registry belongs to.
+pub(crate) const DAO: Item<Addr> = Item::new("dao");
+
+/// Maps (delegator, scope) to the address the delegator's votes are
+/// delegated to. The scope is the address of the proposal module the
+/// delegation applies to, or `GLOBAL_SCOPE`.
+pub(crate) const DELEGATIONS: SnapshotMap<(&Addr, &str), Addr> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// The maximum number of delegators a delegate may have at once.
+/// Proposal modules load each of a delegate's delegators as of a
+/// proposal's start height when the delegate votes, so this bounds
+/// the cost of their vote.
+pub const MAX_DELEGATORS: usize = 50;
+
+/// Maps (delegate, delegator) to the number of the delegator's
+/// scopes that are currently delegated to the delegate. Pairs are
+/// kept once none are so that delegators may be listed as of past
+/// heights by checking each candidate against `DELEGATIONS`.
+pub(crate) const DELEGATORS: Map<(&Addr, &Addr), u32> = Map::new("delegators");
+
+/// Maps each delegate to the number of delegators in `DELEGATORS`
+/// that currently delegate at least one scope to them.
+pub(crate) const DELEGATOR_COUNTS: Map<&Addr, u32> = Map::new("delegator_counts");
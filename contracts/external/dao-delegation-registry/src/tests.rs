@@ -0,0 +1,327 @@
+use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
+use cw20::Cw20Coin;
+use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
+use dao_testing::helpers::instantiate_with_cw4_groups_governance;
+
+use crate::{
+    msg::{DelegationResponse, DelegatorsResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::MAX_DELEGATORS,
+    ContractError,
+};
+
+const MODULE: &str = "module";
+
+fn registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn sudo_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_sudo::contract::execute,
+        dao_proposal_sudo::contract::instantiate,
+        dao_proposal_sudo::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn setup_test() -> (App, Addr) {
+    setup_test_with_members(vec![
+        Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        },
+        Cw20Coin {
+            address: "blue".to_string(),
+            amount: Uint128::new(5),
+        },
+    ])
+}
+
+fn setup_test_with_members(members: Vec<Cw20Coin>) -> (App, Addr) {
+    let mut app = App::default();
+    let sudo_id = app.store_code(sudo_contract());
+    let registry_id = app.store_code(registry_contract());
+
+    let core = instantiate_with_cw4_groups_governance(
+        &mut app,
+        sudo_id,
+        to_binary(&dao_proposal_sudo::msg::InstantiateMsg {
+            root: "root".to_string(),
+        })
+        .unwrap(),
+        Some(members),
+    );
+    let registry = app
+        .instantiate_contract(registry_id, core, &InstantiateMsg {}, &[], "registry", None)
+        .unwrap();
+    (app, registry)
+}
+
+fn delegate(
+    app: &mut App,
+    registry: &Addr,
+    sender: &str,
+    delegate: &str,
+    proposal_module: Option<&str>,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        registry.clone(),
+        &ExecuteMsg::Delegate {
+            delegate: delegate.to_string(),
+            proposal_module: proposal_module.map(str::to_string),
+        },
+        &[],
+    )
+    .map_err(|e| e.downcast().unwrap())
+    .map(|_| ())
+}
+
+fn undelegate(
+    app: &mut App,
+    registry: &Addr,
+    sender: &str,
+    proposal_module: Option<&str>,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        registry.clone(),
+        &ExecuteMsg::Undelegate {
+            proposal_module: proposal_module.map(str::to_string),
+        },
+        &[],
+    )
+    .map_err(|e| e.downcast().unwrap())
+    .map(|_| ())
+}
+
+fn query_delegation(
+    app: &App,
+    registry: &Addr,
+    delegator: &str,
+    proposal_module: Option<&str>,
+    height: Option<u64>,
+) -> Option<Addr> {
+    let res: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry,
+            &QueryMsg::Delegation {
+                delegator: delegator.to_string(),
+                proposal_module: proposal_module.map(str::to_string),
+                height,
+            },
+        )
+        .unwrap();
+    res.delegate
+}
+
+fn query_delegators(
+    app: &App,
+    registry: &Addr,
+    delegate: &str,
+    proposal_module: Option<&str>,
+    height: Option<u64>,
+) -> Vec<Addr> {
+    let res: DelegatorsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            registry,
+            &QueryMsg::Delegators {
+                delegate: delegate.to_string(),
+                proposal_module: proposal_module.map(str::to_string),
+                height,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    res.delegators
+}
+
+#[test]
+fn test_module_delegation_takes_precedence() {
+    let (mut app, registry) = setup_test();
+
+    delegate(&mut app, &registry, "ekez", "blue", None).unwrap();
+    delegate(&mut app, &registry, "ekez", "zeke", Some(MODULE)).unwrap();
+
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", None, None),
+        Some(Addr::unchecked("blue"))
+    );
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", Some("other"), None),
+        Some(Addr::unchecked("blue"))
+    );
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", Some(MODULE), None),
+        Some(Addr::unchecked("zeke"))
+    );
+
+    assert_eq!(
+        query_delegators(&app, &registry, "blue", Some("other"), None),
+        vec![Addr::unchecked("ekez")]
+    );
+    assert!(query_delegators(&app, &registry, "blue", Some(MODULE), None).is_empty());
+    assert_eq!(
+        query_delegators(&app, &registry, "zeke", Some(MODULE), None),
+        vec![Addr::unchecked("ekez")]
+    );
+
+    // Removing the module delegation falls back to the global one.
+    undelegate(&mut app, &registry, "ekez", Some(MODULE)).unwrap();
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", Some(MODULE), None),
+        Some(Addr::unchecked("blue"))
+    );
+    assert!(query_delegators(&app, &registry, "zeke", Some(MODULE), None).is_empty());
+}
+
+#[test]
+fn test_delegations_at_height() {
+    let (mut app, registry) = setup_test();
+
+    let start = app.block_info().height;
+    delegate(&mut app, &registry, "ekez", "blue", None).unwrap();
+    app.update_block(next_block);
+    delegate(&mut app, &registry, "ekez", "zeke", None).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_delegators(&app, &registry, "zeke", None, Some(start + 2)),
+        vec![Addr::unchecked("ekez")]
+    );
+    assert!(query_delegators(&app, &registry, "zeke", None, Some(start + 1)).is_empty());
+    // Delegators who have since stopped delegating to a delegate are
+    // still listed as theirs at earlier heights.
+    assert_eq!(
+        query_delegators(&app, &registry, "blue", Some(MODULE), Some(start + 1)),
+        vec![Addr::unchecked("ekez")]
+    );
+    assert!(query_delegators(&app, &registry, "blue", Some(MODULE), None).is_empty());
+
+    undelegate(&mut app, &registry, "ekez", None).unwrap();
+    app.update_block(next_block);
+
+    // Delegations take effect at the start of the following block.
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", None, Some(start)),
+        None
+    );
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", None, Some(start + 1)),
+        Some(Addr::unchecked("blue"))
+    );
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", Some(MODULE), Some(start + 2)),
+        Some(Addr::unchecked("zeke"))
+    );
+    assert_eq!(
+        query_delegation(&app, &registry, "ekez", None, Some(start + 3)),
+        None
+    );
+    assert_eq!(query_delegation(&app, &registry, "ekez", None, None), None);
+
+    assert_eq!(
+        query_delegators(&app, &registry, "zeke", None, Some(start + 2)),
+        vec![Addr::unchecked("ekez")]
+    );
+    assert!(query_delegators(&app, &registry, "zeke", None, Some(start + 3)).is_empty());
+    assert!(query_delegators(&app, &registry, "zeke", None, None).is_empty());
+}
+
+#[test]
+fn test_delegation_errors() {
+    let (mut app, registry) = setup_test();
+
+    let err = delegate(&mut app, &registry, "ekez", "ekez", None).unwrap_err();
+    assert!(matches!(err, ContractError::SelfDelegation {}));
+
+    let err = delegate(&mut app, &registry, "zeke", "ekez", None).unwrap_err();
+    assert!(matches!(err, ContractError::ZeroVotingPower {}));
+
+    let err = undelegate(&mut app, &registry, "ekez", None).unwrap_err();
+    assert!(matches!(err, ContractError::NoDelegation {}));
+
+    // A global delegation is not removed by removing a module one.
+    delegate(&mut app, &registry, "ekez", "blue", None).unwrap();
+    let err = undelegate(&mut app, &registry, "ekez", Some(MODULE)).unwrap_err();
+    assert!(matches!(err, ContractError::NoDelegation {}));
+}
+
+#[test]
+fn test_delegators_kept_after_last_delegation() {
+    let (mut app, registry) = setup_test();
+
+    delegate(&mut app, &registry, "ekez", "blue", None).unwrap();
+    delegate(&mut app, &registry, "ekez", "blue", Some(MODULE)).unwrap();
+    app.update_block(next_block);
+    let height = app.block_info().height;
+
+    // The global delegation remains, so ekez is still listed.
+    undelegate(&mut app, &registry, "ekez", Some(MODULE)).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        query_delegators(&app, &registry, "blue", Some(MODULE), None),
+        vec![Addr::unchecked("ekez")]
+    );
+
+    // Once no delegations remain, ekez is only listed at heights
+    // where they delegated.
+    undelegate(&mut app, &registry, "ekez", None).unwrap();
+    app.update_block(next_block);
+    assert!(query_delegators(&app, &registry, "blue", Some(MODULE), None).is_empty());
+    assert_eq!(
+        query_delegators(&app, &registry, "blue", Some(MODULE), Some(height)),
+        vec![Addr::unchecked("ekez")]
+    );
+
+    // Delegating again lists ekez again.
+    delegate(&mut app, &registry, "ekez", "blue", None).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        query_delegators(&app, &registry, "blue", None, None),
+        vec![Addr::unchecked("ekez")]
+    );
+}
+
+#[test]
+fn test_delegators_capped() {
+    let members = (0..=MAX_DELEGATORS)
+        .map(|i| Cw20Coin {
+            address: format!("member{i}"),
+            amount: Uint128::new(1),
+        })
+        .collect();
+    let (mut app, registry) = setup_test_with_members(members);
+
+    for i in 0..MAX_DELEGATORS {
+        delegate(&mut app, &registry, &format!("member{i}"), "zeke", None).unwrap();
+    }
+    // Delegating in another scope does not add a delegator.
+    delegate(&mut app, &registry, "member0", "zeke", Some(MODULE)).unwrap();
+
+    let last = format!("member{MAX_DELEGATORS}");
+    let err = delegate(&mut app, &registry, &last, "zeke", None).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::TooManyDelegators {
+            max: MAX_DELEGATORS
+        }
+    ));
+
+    // Moving a delegation elsewhere frees its place, as does removing
+    // one.
+    delegate(&mut app, &registry, "member1", "blue", None).unwrap();
+    delegate(&mut app, &registry, &last, "zeke", None).unwrap();
+    undelegate(&mut app, &registry, "member2", None).unwrap();
+    delegate(&mut app, &registry, "member1", "zeke", None).unwrap();
+    let err = delegate(&mut app, &registry, "member2", "zeke", None).unwrap_err();
+    assert!(matches!(err, ContractError::TooManyDelegators { .. }));
+}
@@ -54,6 +54,18 @@ pub fn query_proposal_v1(
         status: v1_status_to_v2(proposal.status),
         votes: v1_votes_to_v2(proposal.votes),
         allow_revoting: proposal.allow_revoting,
+        anti_sniping: None,
+        extensions: 0,
+        msg_results: vec![],
+        power_transform: None,
+        review_expiration: None,
+        version: 0,
+        commit_expiration: None,
+        prerequisites: vec![],
+        execute_at: None,
+        recurrence: None,
+        voting_period: None,
+        turnout_recorded: false,
     };
 
     (proposal_count, proposal)
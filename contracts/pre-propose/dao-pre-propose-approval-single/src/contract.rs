@@ -91,11 +91,19 @@ pub fn execute_propose(
             title,
             description,
             msgs,
+            prerequisites,
+            execute_at,
+            recurrence,
+            voting_period,
         } => ProposeMsg {
             title,
             description,
             msgs,
             proposer: Some(info.sender.to_string()),
+            prerequisites,
+            execute_at,
+            recurrence,
+            voting_period,
         },
     };
 
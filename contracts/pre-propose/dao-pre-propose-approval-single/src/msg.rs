@@ -1,9 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::{Duration, Expiration};
 use dao_pre_propose_base::msg::{
     ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
 };
+use dao_voting::prerequisite::Prerequisite;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::recurrence::Recurrence;
 
 #[cw_serde]
 pub enum ApproverProposeMessage {
@@ -20,6 +23,22 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
+        /// Proposals that must be executed before this proposal may
+        /// be executed.
+        #[serde(default)]
+        prerequisites: Vec<Prerequisite>,
+        /// If set, the proposal may not be executed before this time
+        /// once passed.
+        #[serde(default)]
+        execute_at: Option<Expiration>,
+        /// If set, the proposal's messages are executed repeatedly on
+        /// this schedule once it is executed.
+        #[serde(default)]
+        recurrence: Option<Recurrence>,
+        /// The voting period of the proposal, if the proposal module
+        /// allows proposers to choose one.
+        #[serde(default)]
+        voting_period: Option<Duration>,
     },
 }
 
@@ -75,6 +75,14 @@ fn get_default_proposal_module_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     }
 }
 
@@ -182,6 +190,10 @@ fn make_pre_proposal(app: &mut App, pre_propose: Addr, proposer: &str, funds: &[
                 title: "title".to_string(),
                 description: "description".to_string(),
                 msgs: vec![],
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             },
         },
         funds,
@@ -998,6 +1010,10 @@ fn test_permissions() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -1146,6 +1162,10 @@ fn test_no_deposit_required_members_submission() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -1204,6 +1224,14 @@ fn test_instantiate_with_zero_native_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     };
 
@@ -1267,6 +1295,14 @@ fn test_instantiate_with_zero_cw20_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     };
 
@@ -1548,3 +1584,67 @@ fn test_withdraw() {
     let balance = get_balance_native(&app, core_addr.as_str(), "ujuno");
     assert_eq!(balance, Uint128::new(30));
 }
+
+#[test]
+fn test_approved_proposal_may_be_amended() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single,
+        pre_propose,
+    } = setup_default_test(&mut app, None, true);
+
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_single.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Time(86400),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: Some(Duration::Time(100)),
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pre_propose_id = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let id = approve_proposal(&mut app, pre_propose, "approver", pre_propose_id);
+
+    // The proposer recorded on the approved proposal may amend it
+    // during the review period.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &dao_proposal_single::msg::ExecuteMsg::Amend {
+            proposal_id: id,
+            title: "title".to_string(),
+            description: "amended".to_string(),
+            msgs: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+    let proposal: dao_proposal_single::query::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: id },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("ekez"));
+    assert_eq!(proposal.proposal.description, "amended");
+    assert_eq!(proposal.proposal.version, 1);
+}
@@ -99,6 +99,14 @@ fn get_proposal_module_approval_single_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     }
 }
 
@@ -130,6 +138,14 @@ fn get_proposal_module_approver_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     }
 }
 
@@ -301,6 +317,10 @@ fn make_pre_proposal(app: &mut App, pre_propose: Addr, proposer: &str, funds: &[
                 title: "title".to_string(),
                 description: "description".to_string(),
                 msgs: vec![],
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             },
         },
         funds,
@@ -1143,6 +1163,10 @@ fn test_permissions() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -0,0 +1,454 @@
+use cosmwasm_std::{coins, from_slice, to_binary, Addr, Coin, Decimal, Empty, Uint128};
+use cw2::ContractVersion;
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor};
+use cw_utils::Duration;
+use dao_core::state::ProposalModule;
+use dao_interface::{Admin, ModuleInstantiateInfo};
+use dao_pre_propose_base::{error::PreProposeError, msg::DepositInfoResponse};
+use dao_proposal_condorcet as cpc;
+use dao_testing::{
+    contracts::proposal_condorcet_contract, helpers::instantiate_with_cw4_groups_governance,
+};
+use dao_voting::{
+    deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::{PreProposeInfo, ProposalCreationPolicy},
+    status::Status,
+    threshold::PercentageThreshold,
+};
+
+use crate::contract::*;
+
+fn pre_propose_condorcet_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+struct DefaultTestSetup {
+    core_addr: Addr,
+    proposal_module: Addr,
+    pre_propose: Addr,
+}
+
+fn setup_default_test(
+    app: &mut App,
+    deposit_info: Option<UncheckedDepositInfo>,
+    open_proposal_submission: bool,
+) -> DefaultTestSetup {
+    let condorcet_id = app.store_code(proposal_condorcet_contract());
+    let pre_propose_id = app.store_code(pre_propose_condorcet_contract());
+
+    let proposal_module_instantiate = cpc::msg::InstantiateMsg {
+        config: cpc::config::UncheckedConfig {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            voting_period: Duration::Time(86400),
+            min_voting_period: None,
+            close_proposals_on_execution_failure: true,
+            completion: None,
+            allow_revoting: false,
+            voting_period_choice: None,
+        },
+        pre_propose_info: PreProposeInfo::ModuleMayPropose {
+            info: ModuleInstantiateInfo {
+                code_id: pre_propose_id,
+                msg: to_binary(&InstantiateMsg {
+                    deposit_info,
+                    open_proposal_submission,
+                    extension: Empty::default(),
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "condorcet pre-propose module".to_string(),
+            },
+        },
+    };
+
+    let core_addr = instantiate_with_cw4_groups_governance(
+        app,
+        condorcet_id,
+        to_binary(&proposal_module_instantiate).unwrap(),
+        Some(vec![
+            cw20::Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(9),
+            },
+            cw20::Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(8),
+            },
+        ]),
+    );
+    let proposal_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &dao_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_module = proposal_modules.into_iter().next().unwrap().address;
+    let proposal_creation_policy = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &cpc::msg::QueryMsg::ProposalCreationPolicy {},
+        )
+        .unwrap();
+
+    let pre_propose = match proposal_creation_policy {
+        ProposalCreationPolicy::Module { addr } => addr,
+        _ => panic!("expected a module for the proposal creation policy"),
+    };
+
+    // Make sure things were set up correctly.
+    let module: Addr = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::ProposalModule {})
+        .unwrap();
+    assert_eq!(module, proposal_module);
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(pre_propose.clone(), &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, core_addr);
+
+    DefaultTestSetup {
+        core_addr,
+        proposal_module,
+        pre_propose,
+    }
+}
+
+fn propose_message(title: &str) -> ExecuteMsg {
+    ExecuteMsg::Propose {
+        msg: ProposeMessage::Propose {
+            title: title.to_string(),
+            description: "description".to_string(),
+            choices: vec![
+                cpc::msg::Choice {
+                    title: "choice 0".to_string(),
+                    description: "the first choice".to_string(),
+                    msgs: vec![],
+                },
+                cpc::msg::Choice {
+                    title: "choice 1".to_string(),
+                    description: "the second choice".to_string(),
+                    msgs: vec![],
+                },
+            ],
+            voting_period: None,
+        },
+    }
+}
+
+fn make_proposal(
+    app: &mut App,
+    pre_propose: Addr,
+    proposal_module: Addr,
+    proposer: &str,
+    funds: &[Coin],
+) -> u32 {
+    app.execute_contract(
+        Addr::unchecked(proposer),
+        pre_propose,
+        &propose_message("title"),
+        funds,
+    )
+    .unwrap();
+
+    let id: u32 = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &cpc::msg::QueryMsg::NextProposalId {})
+        .unwrap();
+    let id = id - 1;
+
+    let proposal: cpc::proposal::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &cpc::msg::QueryMsg::Proposal { id })
+        .unwrap();
+
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked(proposer));
+    assert_eq!(proposal.proposal.title, "title".to_string());
+    // The proposal module adds a "None of the above" choice.
+    assert_eq!(proposal.proposal.choices.len(), 3);
+
+    id
+}
+
+fn mint_natives(app: &mut App, receiver: &str, coins: Vec<Coin>) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(BankSudo::Mint {
+        to_address: receiver.to_string(),
+        amount: coins,
+    }))
+    .unwrap();
+}
+
+fn get_balance_native(app: &App, who: &str, denom: &str) -> Uint128 {
+    let res = app.wrap().query_balance(who, denom).unwrap();
+    res.amount
+}
+
+fn get_deposit_info(app: &App, module: Addr, id: u32) -> DepositInfoResponse {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::DepositInfo {
+                proposal_id: id as u64,
+            },
+        )
+        .unwrap()
+}
+
+fn vote(app: &mut App, module: Addr, sender: &str, proposal_id: u32, vote: Vec<u32>) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &cpc::msg::ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn execute_proposal(app: &mut App, module: Addr, sender: &str, proposal_id: u32) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &cpc::msg::ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+}
+
+fn close_proposal(app: &mut App, module: Addr, sender: &str, proposal_id: u32) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &cpc::msg::ExecuteMsg::Close { proposal_id },
+        &[],
+    )
+    .unwrap();
+}
+
+enum EndStatus {
+    Passed,
+    Failed,
+}
+
+fn test_native_permutation(
+    end_status: EndStatus,
+    refund_policy: DepositRefundPolicy,
+    proposer_refunded: bool,
+) {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_module,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("ujuno".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: refund_policy.clone(),
+        }),
+        false,
+    );
+
+    mint_natives(&mut app, "ekez", coins(10, "ujuno"));
+    let id = make_proposal(
+        &mut app,
+        pre_propose.clone(),
+        proposal_module.clone(),
+        "ekez",
+        &coins(10, "ujuno"),
+    );
+
+    let balance = get_balance_native(&app, "ekez", "ujuno");
+    assert_eq!(balance, Uint128::zero());
+    let info = get_deposit_info(&app, pre_propose, id);
+    assert_eq!(
+        info,
+        DepositInfoResponse {
+            deposit_info: Some(CheckedDepositInfo {
+                denom: cw_denom::CheckedDenom::Native("ujuno".to_string()),
+                amount: Uint128::new(10),
+                refund_policy,
+            }),
+            proposer: Addr::unchecked("ekez"),
+        }
+    );
+
+    match end_status {
+        EndStatus::Passed => {
+            // ekez holds a majority of the voting power so their
+            // favorite choice can not be beaten.
+            vote(&mut app, proposal_module.clone(), "ekez", id, vec![0, 1, 2]);
+            execute_proposal(&mut app, proposal_module, "ekez", id);
+        }
+        EndStatus::Failed => {
+            // Nobody votes, so the proposal is rejected for lack of
+            // quorum once it expires.
+            app.update_block(|b| b.time = b.time.plus_seconds(86400));
+            close_proposal(&mut app, proposal_module, "ekez", id);
+        }
+    }
+
+    let (dao_expected, proposer_expected) = if proposer_refunded { (0, 10) } else { (10, 0) };
+    let proposer_balance = get_balance_native(&app, "ekez", "ujuno");
+    let dao_balance = get_balance_native(&app, core_addr.as_str(), "ujuno");
+    assert_eq!(proposer_expected, proposer_balance.u128());
+    assert_eq!(dao_expected, dao_balance.u128());
+}
+
+#[test]
+fn test_native_passed_always_refund() {
+    test_native_permutation(EndStatus::Passed, DepositRefundPolicy::Always, true)
+}
+
+#[test]
+fn test_native_failed_always_refund() {
+    test_native_permutation(EndStatus::Failed, DepositRefundPolicy::Always, true)
+}
+
+#[test]
+fn test_native_passed_never_refund() {
+    test_native_permutation(EndStatus::Passed, DepositRefundPolicy::Never, false)
+}
+
+#[test]
+fn test_native_failed_never_refund() {
+    test_native_permutation(EndStatus::Failed, DepositRefundPolicy::Never, false)
+}
+
+#[test]
+fn test_native_passed_only_passed_refund() {
+    test_native_permutation(EndStatus::Passed, DepositRefundPolicy::OnlyPassed, true)
+}
+
+#[test]
+fn test_native_failed_only_passed_refund() {
+    test_native_permutation(EndStatus::Failed, DepositRefundPolicy::OnlyPassed, false)
+}
+
+#[test]
+fn test_set_version() {
+    let mut app = App::default();
+
+    let DefaultTestSetup { pre_propose, .. } = setup_default_test(&mut app, None, false);
+
+    let info: ContractVersion = from_slice(
+        &app.wrap()
+            .query_wasm_raw(pre_propose, "contract_info".as_bytes())
+            .unwrap()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string()
+        },
+        info
+    )
+}
+
+#[test]
+fn test_permissions() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        core_addr,
+        proposal_module,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+
+    let err: PreProposeError = app
+        .execute_contract(
+            core_addr,
+            pre_propose.clone(),
+            &ExecuteMsg::ProposalCompletedHook {
+                proposal_id: 1,
+                new_status: Status::Closed,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotModule {});
+
+    // Non-members may not propose when open_propose_submission is
+    // disabled.
+    let err: PreProposeError = app
+        .execute_contract(
+            Addr::unchecked("nonmember"),
+            pre_propose,
+            &propose_message("I would like to join the DAO"),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, PreProposeError::NotMember {});
+
+    // Members may not bypass the pre-propose module.
+    let err: cpc::ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module,
+            &cpc::msg::ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: vec![],
+                proposer: None,
+                voting_period: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, cpc::ContractError::NotPermitted {}));
+}
+
+#[test]
+fn test_open_proposal_submission() {
+    let mut app = App::default();
+
+    let DefaultTestSetup {
+        proposal_module,
+        pre_propose,
+        ..
+    } = setup_default_test(&mut app, None, true);
+
+    // A non-member with no voting power may propose, but not vote.
+    let id = make_proposal(
+        &mut app,
+        pre_propose,
+        proposal_module.clone(),
+        "nonmember",
+        &[],
+    );
+    vote(&mut app, proposal_module.clone(), "ekez", id, vec![1, 0, 2]);
+    execute_proposal(&mut app, proposal_module.clone(), "keze", id);
+
+    let proposal: cpc::proposal::ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &cpc::msg::QueryMsg::Proposal { id })
+        .unwrap();
+    assert_eq!(
+        proposal.proposal.last_status(),
+        cpc::proposal::Status::Executed
+    );
+}
@@ -3,13 +3,14 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
 use cw2::set_contract_version;
+use cw_utils::{Duration, Expiration};
 
 use dao_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
     state::PreProposeContract,
 };
-use dao_voting::multiple_choice::MultipleChoiceOptions;
+use dao_voting::multiple_choice::{MultipleChoiceOptions, TopN};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-multiple";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -20,6 +21,17 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         choices: MultipleChoiceOptions,
+        /// If set, the proposal may not be executed before this time
+        /// once passed.
+        #[serde(default)]
+        execute_at: Option<Expiration>,
+        /// If set, up to this many options may win the proposal.
+        #[serde(default)]
+        top_n: Option<TopN>,
+        /// The voting period of the proposal, if the proposal module
+        /// allows proposers to choose one.
+        #[serde(default)]
+        voting_period: Option<Duration>,
     },
 }
 
@@ -37,6 +49,9 @@ enum ProposeMessageInternal {
         description: String,
         choices: MultipleChoiceOptions,
         proposer: Option<String>,
+        execute_at: Option<Expiration>,
+        top_n: Option<TopN>,
+        voting_period: Option<Duration>,
     },
 }
 
@@ -73,6 +88,9 @@ pub fn execute(
                     title,
                     description,
                     choices,
+                    execute_at,
+                    top_n,
+                    voting_period,
                 },
         } => ExecuteInternal::Propose {
             msg: ProposeMessageInternal::Propose {
@@ -80,6 +98,9 @@ pub fn execute(
                 title,
                 description,
                 choices,
+                execute_at,
+                top_n,
+                voting_period,
             },
         },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
@@ -76,6 +76,10 @@ fn get_default_proposal_module_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        voting_period_choice: None,
     }
 }
 
@@ -201,6 +205,9 @@ fn make_proposal(
                         },
                     ],
                 },
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
         },
         funds,
@@ -216,7 +223,7 @@ fn make_proposal(
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module,
+            &proposal_module,
             &cpm::msg::QueryMsg::Proposal { proposal_id: id },
         )
         .unwrap();
@@ -224,8 +231,19 @@ fn make_proposal(
     assert_eq!(proposal.proposal.proposer, Addr::unchecked(proposer));
     assert_eq!(proposal.proposal.title, "title".to_string());
     assert_eq!(proposal.proposal.description, "description".to_string());
+    let options: cpm::query::OptionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &cpm::msg::QueryMsg::ListOptions {
+                proposal_id: id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
     assert_eq!(
-        proposal.proposal.choices,
+        options.options,
         vec![
             CheckedMultipleChoiceOption {
                 description: "multiple choice option 1".to_string(),
@@ -489,12 +507,12 @@ fn test_native_permutation(
         fn(&mut App, Addr, &str, u64) -> (),
     ) = match end_status {
         EndStatus::Passed => (
-            MultipleChoiceVote { option_id: 0 },
+            MultipleChoiceVote::single(0),
             Status::Passed,
             execute_proposal,
         ),
         EndStatus::Failed => (
-            MultipleChoiceVote { option_id: 2 },
+            MultipleChoiceVote::single(2),
             Status::Rejected,
             close_proposal,
         ),
@@ -567,12 +585,12 @@ fn test_cw20_permutation(
         fn(&mut App, Addr, &str, u64) -> (),
     ) = match end_status {
         EndStatus::Passed => (
-            MultipleChoiceVote { option_id: 0 },
+            MultipleChoiceVote::single(0),
             Status::Passed,
             execute_proposal,
         ),
         EndStatus::Failed => (
-            MultipleChoiceVote { option_id: 2 },
+            MultipleChoiceVote::single(2),
             Status::Rejected,
             close_proposal,
         ),
@@ -744,7 +762,7 @@ fn test_multiple_open_proposals() {
         proposal_single.clone(),
         "ekez",
         first_id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     assert_eq!(Status::Passed, new_status);
 
@@ -764,7 +782,7 @@ fn test_multiple_open_proposals() {
         proposal_single.clone(),
         "ekez",
         second_id,
-        MultipleChoiceVote { option_id: 2 },
+        MultipleChoiceVote::single(2),
     );
     assert_eq!(Status::Rejected, new_status);
 
@@ -867,6 +885,9 @@ fn test_permissions() {
                             title: "title".to_string(),
                         }],
                     },
+                    execute_at: None,
+                    top_n: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -911,7 +932,7 @@ fn test_propose_open_proposal_submission() {
         proposal_single,
         "ekez",
         id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     assert_eq!(Status::Passed, new_status)
 }
@@ -941,7 +962,7 @@ fn test_no_deposit_required_open_submission() {
         proposal_single,
         "ekez",
         id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     assert_eq!(Status::Passed, new_status)
 }
@@ -973,6 +994,9 @@ fn test_no_deposit_required_members_submission() {
                             title: "title".to_string(),
                         }],
                     },
+                    execute_at: None,
+                    top_n: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -988,7 +1012,7 @@ fn test_no_deposit_required_members_submission() {
         proposal_single,
         "ekez",
         id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     assert_eq!(Status::Passed, new_status)
 }
@@ -1063,6 +1087,10 @@ fn test_instantiate_with_zero_native_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            voting_period_choice: None,
         }
     };
 
@@ -1124,6 +1152,10 @@ fn test_instantiate_with_zero_cw20_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            voting_period_choice: None,
         }
     };
 
@@ -1236,14 +1268,14 @@ fn test_update_config() {
         proposal_single.clone(),
         "ekez",
         id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     vote(
         &mut app,
         proposal_single.clone(),
         "ekez",
         new_id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     execute_proposal(&mut app, proposal_single.clone(), "ekez", id);
     execute_proposal(&mut app, proposal_single.clone(), "ekez", new_id);
@@ -1376,7 +1408,7 @@ fn test_withdraw() {
         proposal_single.clone(),
         "ekez",
         cw20_id,
-        MultipleChoiceVote { option_id: 0 },
+        MultipleChoiceVote::single(0),
     );
     execute_proposal(&mut app, proposal_single.clone(), "ekez", cw20_id);
 
@@ -1398,7 +1430,7 @@ fn test_withdraw() {
         proposal_single.clone(),
         "ekez",
         native_id,
-        MultipleChoiceVote { option_id: 2 },
+        MultipleChoiceVote::single(2),
     );
     close_proposal(&mut app, proposal_single.clone(), "ekez", native_id);
     withdraw(
@@ -5,13 +5,16 @@ use cosmwasm_std::{
     Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
+use cw_utils::{Duration, Expiration};
 
 use dao_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
     state::PreProposeContract,
 };
+use dao_voting::prerequisite::Prerequisite;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::recurrence::Recurrence;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -26,6 +29,22 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
+        /// Proposals that must be executed before this proposal may
+        /// be executed.
+        #[serde(default)]
+        prerequisites: Vec<Prerequisite>,
+        /// If set, the proposal may not be executed before this time
+        /// once passed.
+        #[serde(default)]
+        execute_at: Option<Expiration>,
+        /// If set, the proposal's messages are executed repeatedly on
+        /// this schedule once it is executed.
+        #[serde(default)]
+        recurrence: Option<Recurrence>,
+        /// The voting period of the proposal, if the proposal module
+        /// allows proposers to choose one.
+        #[serde(default)]
+        voting_period: Option<Duration>,
     },
 }
 
@@ -74,6 +93,10 @@ pub fn execute(
                     title,
                     description,
                     msgs,
+                    prerequisites,
+                    execute_at,
+                    recurrence,
+                    voting_period,
                 },
         } => ExecuteInternal::Propose {
             msg: ProposeMessageInternal::Propose(ProposeMsg {
@@ -82,6 +105,10 @@ pub fn execute(
                 title,
                 description,
                 msgs,
+                prerequisites,
+                execute_at,
+                recurrence,
+                voting_period,
             }),
         },
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
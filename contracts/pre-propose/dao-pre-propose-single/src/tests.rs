@@ -74,6 +74,14 @@ fn get_default_proposal_module_instantiate(
             },
         },
         close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     }
 }
 
@@ -186,6 +194,10 @@ fn make_proposal(
                 title: "title".to_string(),
                 description: "description".to_string(),
                 msgs: vec![],
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             },
         },
         funds,
@@ -827,6 +839,10 @@ fn test_permissions() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -915,6 +931,10 @@ fn test_no_deposit_required_members_submission() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -999,6 +1019,14 @@ fn test_instantiate_with_zero_native_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     };
 
@@ -1060,6 +1088,14 @@ fn test_instantiate_with_zero_cw20_deposit() {
                 },
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     };
 
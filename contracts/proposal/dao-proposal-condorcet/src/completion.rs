@@ -0,0 +1,194 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+use crate::{cell::Cell, m::M};
+
+/// A method for choosing a winner from the pairwise margins of an
+/// election that has no Condorcet winner, for example because the
+/// voters' preferences form a cycle. Both methods choose the
+/// Condorcet winner when there is one.
+///
+/// The cost of each method depends only on the number of candidates
+/// and not on the votes cast. As `Tally::new` computes the winner, a
+/// proposal whose completion can not fit inside gas limits fails to
+/// be created.
+#[cw_serde]
+#[derive(Copy)]
+pub enum Completion {
+    /// The Schulze method. Candidate x beats candidate y if the
+    /// strongest path of victories from x to y is stronger than the
+    /// strongest path from y to x, where a path is as strong as its
+    /// smallest margin. The candidate that beats every other
+    /// candidate wins.
+    ///
+    /// Costs O(candidates^3).
+    Schulze {},
+    /// Tideman's Ranked Pairs. Victories are locked in from the
+    /// largest margin to the smallest, skipping any that would create
+    /// a cycle with those already locked. The candidate that has a
+    /// path of locked victories over every other candidate wins.
+    /// Victories with equal margins are locked in the order of their
+    /// candidates' indexes.
+    ///
+    /// Costs O(candidates^4).
+    RankedPairs {},
+}
+
+impl Completion {
+    /// Computes the winner of the election described by M. Returns
+    /// None if there is no unique winner.
+    pub(crate) fn winner(self, m: &M) -> Option<u32> {
+        match self {
+            Completion::Schulze {} => schulze(m),
+            Completion::RankedPairs {} => ranked_pairs(m),
+        }
+    }
+}
+
+/// The margin by which x beats y, or zero if x does not beat y.
+fn victory(m: &M, (x, y): (usize, usize)) -> Uint128 {
+    if x == y {
+        return Uint128::zero();
+    }
+    match m.get((x as u32, y as u32)) {
+        Cell::Positive(p) => p,
+        Cell::Zero | Cell::Negative(_) => Uint128::zero(),
+    }
+}
+
+/// Finds the candidate x for which `beats(x, y)` holds for every
+/// other candidate y. There is at most one such candidate if `beats`
+/// is asymmetric.
+fn unique_winner(n: usize, beats: impl Fn(usize, usize) -> bool) -> Option<u32> {
+    (0..n)
+        .find(|x| (0..n).all(|y| y == *x || beats(*x, y)))
+        .map(|x| x as u32)
+}
+
+fn schulze(m: &M) -> Option<u32> {
+    let n = m.n as usize;
+    // p[x][y] is the strength of the strongest path from x to y. this
+    // is the widest path variant of floyd-warshall.
+    let mut p: Vec<Vec<Uint128>> = (0..n)
+        .map(|x| (0..n).map(|y| victory(m, (x, y))).collect())
+        .collect();
+    for k in 0..n {
+        for x in 0..n {
+            for y in 0..n {
+                if x != k && y != k && x != y {
+                    let through = p[x][k].min(p[k][y]);
+                    if through > p[x][y] {
+                        p[x][y] = through;
+                    }
+                }
+            }
+        }
+    }
+    unique_winner(n, |x, y| p[x][y] > p[y][x])
+}
+
+fn ranked_pairs(m: &M) -> Option<u32> {
+    let n = m.n as usize;
+    // every pair of candidates, ordered so that the first candidate
+    // is the one with the larger (or equal) margin.
+    let mut pairs = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+    for x in 0..n {
+        for y in (x + 1)..n {
+            pairs.push(match m.get((x as u32, y as u32)) {
+                Cell::Negative(margin) => (margin, y, x),
+                Cell::Positive(margin) => (margin, x, y),
+                Cell::Zero => (Uint128::zero(), x, y),
+            })
+        }
+    }
+    pairs.sort_by(|(a, ax, ay), (b, bx, by)| b.cmp(a).then((ax, ay).cmp(&(bx, by))));
+
+    // reach[a][b] is true if there is a path of locked victories
+    // from a to b.
+    let mut reach = vec![vec![false; n]; n];
+    for (margin, x, y) in pairs {
+        // locking x over y creates a cycle iff y already reaches x.
+        let lock = !margin.is_zero() && !reach[y][x];
+        // the closure is walked even if nothing is locked so that
+        // the cost of this does not depend on the votes cast.
+        for a in 0..n {
+            for b in 0..n {
+                if lock && (a == x || reach[a][x]) && (b == y || reach[y][b]) {
+                    reach[a][b] = true;
+                }
+            }
+        }
+    }
+    unique_winner(n, |x, y| reach[x][y])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m_with_victories(n: u32, victories: &[((u32, u32), u128)]) -> M {
+        let mut m = M::new(n);
+        for (pair, margin) in victories {
+            m.increment(*pair, Uint128::new(*margin));
+        }
+        m
+    }
+
+    fn winners(m: &M) -> (Option<u32>, Option<u32>) {
+        (
+            Completion::Schulze {}.winner(m),
+            Completion::RankedPairs {}.winner(m),
+        )
+    }
+
+    #[test]
+    fn test_condorcet_winner() {
+        let m = m_with_victories(3, &[((1, 0), 1), ((1, 2), 3), ((2, 0), 5)]);
+        assert_eq!(winners(&m), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_cycle() {
+        // 0 beats 1 by 6, 1 beats 2 by 4, and 2 beats 0 by 2. the
+        // weakest victory is the one that is overruled.
+        let m = m_with_victories(3, &[((0, 1), 6), ((1, 2), 4), ((2, 0), 2)]);
+        assert_eq!(winners(&m), (Some(0), Some(0)));
+    }
+
+    #[test]
+    fn test_nested_cycles() {
+        // 0 > 1 (5), 1 > 2 (7), 2 > 0 (3), 3 > 0 (9), 1 > 3 (6),
+        // 2 > 3 (4).
+        //
+        // ranked pairs locks 3 > 0, 1 > 2, and 1 > 3, then skips
+        // 0 > 1, so 1 wins. under schulze 1 beats 0 as the path
+        // 1 > 3 > 0 has strength 6, more than 0's victory of 5.
+        let m = m_with_victories(
+            4,
+            &[
+                ((0, 1), 5),
+                ((1, 2), 7),
+                ((2, 0), 3),
+                ((3, 0), 9),
+                ((1, 3), 6),
+                ((2, 3), 4),
+            ],
+        );
+        assert_eq!(winners(&m), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_no_unique_winner() {
+        // no votes.
+        assert_eq!(winners(&M::new(4)), (None, None));
+
+        // a perfect cycle. ranked pairs locks 0 > 1 and 1 > 2 first
+        // as they have the smallest indexes.
+        let m = m_with_victories(3, &[((0, 1), 2), ((1, 2), 2), ((2, 0), 2)]);
+        assert_eq!(winners(&m), (None, Some(0)));
+
+        // two candidates that tie and beat everyone else.
+        let m = m_with_victories(3, &[((0, 2), 2), ((1, 2), 2)]);
+        assert_eq!(winners(&m), (None, None));
+    }
+}
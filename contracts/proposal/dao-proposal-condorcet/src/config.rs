@@ -3,9 +3,10 @@ use cw_utils::Duration;
 use dao_voting::{
     threshold::{validate_quorum, PercentageThreshold},
     voting::validate_voting_period,
+    voting_period::VotingPeriodChoice,
 };
 
-use crate::ContractError;
+use crate::{completion::Completion, ContractError};
 
 #[cw_serde]
 pub struct UncheckedConfig {
@@ -13,6 +14,20 @@ pub struct UncheckedConfig {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
+    /// How a winner is chosen for proposals that have no Condorcet
+    /// winner. If None, those proposals are rejected.
+    #[serde(default)]
+    pub completion: Option<Completion>,
+    /// If voters may change their vote before a proposal expires. If
+    /// true, proposals can not pass or be rejected before they
+    /// expire.
+    #[serde(default)]
+    pub allow_revoting: bool,
+    /// If set, proposers may choose a voting period between its
+    /// minimum and `voting_period`, and proposals with short voting
+    /// periods may require a higher quorum.
+    #[serde(default)]
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 #[cw_serde]
@@ -21,6 +36,12 @@ pub(crate) struct Config {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
+    #[serde(default)]
+    pub completion: Option<Completion>,
+    #[serde(default)]
+    pub allow_revoting: bool,
+    #[serde(default)]
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 impl UncheckedConfig {
@@ -28,9 +49,15 @@ impl UncheckedConfig {
         validate_quorum(&self.quorum)?;
         let (min_voting_period, voting_period) =
             validate_voting_period(self.min_voting_period, self.voting_period)?;
+        if let Some(choice) = &self.voting_period_choice {
+            choice.validate(min_voting_period.as_ref(), &voting_period)?;
+        }
         Ok(Config {
             quorum: self.quorum,
             close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+            completion: self.completion,
+            allow_revoting: self.allow_revoting,
+            voting_period_choice: self.voting_period_choice,
             voting_period,
             min_voting_period,
         })
@@ -1,24 +1,41 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, SubMsg, WasmMsg,
 };
 
 use cw2::set_contract_version;
-use dao_voting::reply::TaggedReplyId;
+use cw_hooks::Hooks;
+use cw_storage_plus::Bound;
+use cw_utils::{parse_reply_instantiate_data, Duration};
+use dao_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use dao_vote_hooks::new_vote_hooks;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::reply::{failed_pre_propose_module_hook_id, TaggedReplyId};
+use dao_voting::status::Status as CompletedStatus;
 use dao_voting::voting::{get_total_power, get_voting_power};
+use dao_voting::voting_period::proposal_voting_period;
 
 use crate::config::UncheckedConfig;
 use crate::error::ContractError;
 use crate::msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::proposal::{Proposal, ProposalResponse, Status};
-use crate::state::{next_proposal_id, CONFIG, DAO, PROPOSAL, TALLY, VOTE};
+use crate::proposal::{Proposal, ProposalResponse, Status, TallyResponse};
+use crate::state::{
+    next_proposal_id, CONFIG, CREATION_POLICY, DAO, PROPOSAL, PROPOSAL_HOOKS, TALLY, VOTE,
+    VOTE_HOOKS,
+};
 use crate::tally::Tally;
-use crate::vote::Vote;
+use crate::vote::{Ballot, Vote, VoteInfo, VoteListResponse, VoteResponse};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-condorcet";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Message type used for firing hooks to this module's pre-propose
+/// module, if one is installed.
+type PreProposeHookMsg = dao_pre_propose_base::msg::ExecuteMsg<Empty, Empty>;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -29,9 +46,15 @@ pub fn instantiate(
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     DAO.save(deps.storage, &info.sender)?;
-    CONFIG.save(deps.storage, &msg.into_checked()?)?;
+    CONFIG.save(deps.storage, &msg.config.into_checked()?)?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(info.sender.clone())?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
 
     Ok(Response::default()
+        .add_submessages(pre_propose_messages)
         .add_attribute("method", "instantiate")
         .add_attribute("creator", info.sender))
 }
@@ -48,9 +71,9 @@ pub fn instantiate(
 //
 // in terms of storage costs:
 //
-// propose: proposal_load + proposal_store + tally_load + tally_store + config_load
-// execute: proposal_load + proposal_store + tally_load
-// vote:                                     tally_load + tally_store               + vote_load + vote_store
+// propose: proposal_load + proposal_store + tally_load + tally_store + config_load                          + hooks_load
+// execute: proposal_load + proposal_store + tally_load                                                     + hooks_load
+// vote:                                     tally_load + tally_store               + vote_load + vote_store + hooks_load
 //
 // so we are good so long as:
 //
@@ -61,7 +84,11 @@ pub fn instantiate(
 // Vec<Vec<CosmosMsg>>`, `choices.len() = vote.len()`, vote is a
 // `Vec<u32>`, even an empty vec must contain it's length which is a
 // usize, so `sizeof(Vec<u32>) <= sizeof(Vec<usize>) <=
-// sizeof(Vec<Vec<CosmosMsg>) => sizeof(vote) <= sizeof(proposal)`.
+// sizeof(Vec<Vec<CosmosMsg>) => sizeof(vote) <= sizeof(proposal)`. a
+// ballot adds the voter's power to its vote, which is no larger than
+// the proposal's total power, and an optional rationale. the
+// rationale is chosen by the voter, so a voter may always make their
+// ballot small enough by leaving it out.
 //
 // in terms of other costs:
 //
@@ -69,7 +96,9 @@ pub fn instantiate(
 // execute: query_voting_power
 // vote:    query_voting_power + compute_winner
 //
-// so we're good there as well.
+// so we're good there as well. a revote only touches the cells of M
+// whose order changed between the old and new vote, so it costs no
+// more than a first vote [3].
 //
 // [1] we need to be gas efficent in this way because the size of the
 //     Tally type grows with candidates^2 and thus can be too large to
@@ -79,6 +108,7 @@ pub fn instantiate(
 //     be voted on or executed.
 // [2] Tally::new computes the winner over the new matrix so that this
 //     is the case.
+// [3] see Tally::revote.
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -88,28 +118,96 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Propose { choices } => execute_propose(deps, env, info, choices),
-        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Propose {
+            title,
+            description,
+            choices,
+            proposer,
+            voting_period,
+        } => execute_propose(
+            deps,
+            env,
+            info,
+            title,
+            description,
+            choices,
+            proposer,
+            voting_period,
+        ),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale,
+        } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
 
         ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_pre_propose_info(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address } => {
+            execute_add_hook(deps, info, PROPOSAL_HOOKS, address, "add_proposal_hook")
+        }
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_hook(deps, info, PROPOSAL_HOOKS, address, "remove_proposal_hook")
+        }
+        ExecuteMsg::AddVoteHook { address } => {
+            execute_add_hook(deps, info, VOTE_HOOKS, address, "add_vote_hook")
+        }
+        ExecuteMsg::RemoveVoteHook { address } => {
+            execute_remove_hook(deps, info, VOTE_HOOKS, address, "remove_vote_hook")
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_propose(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    title: String,
+    description: String,
     choices: Vec<Choice>,
+    proposer: Option<String>,
+    voting_period: Option<Duration>,
 ) -> Result<Response, ContractError> {
     let dao = DAO.load(deps.storage)?;
-    let sender_voting_power = get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
-    if sender_voting_power.is_zero() {
-        return Err(ContractError::ZeroVotingPower {});
-    }
+    let proposer = match (proposer, CREATION_POLICY.load(deps.storage)?) {
+        (None, ProposalCreationPolicy::Anyone {}) => {
+            let sender_voting_power =
+                get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
+            if sender_voting_power.is_zero() {
+                return Err(ContractError::ZeroVotingPower {});
+            }
+            info.sender
+        }
+        // the pre-propose module decides who may propose, so the
+        // proposer need not have voting power.
+        (Some(proposer), ProposalCreationPolicy::Module { addr }) => {
+            if info.sender != addr {
+                return Err(ContractError::NotPermitted {});
+            }
+            deps.api.addr_validate(&proposer)?
+        }
+        (None, ProposalCreationPolicy::Module { .. }) => {
+            return Err(ContractError::NotPermitted {})
+        }
+        (Some(_), ProposalCreationPolicy::Anyone {}) => {
+            return Err(ContractError::InvalidProposer {})
+        }
+    };
 
     let config = CONFIG.load(deps.storage)?;
+    let period = proposal_voting_period(
+        config.voting_period_choice.as_ref(),
+        voting_period,
+        config.voting_period,
+    )?;
 
     let id = next_proposal_id(deps.storage)?;
     let total_power = get_total_power(deps.as_ref(), &dao, None)?;
@@ -118,7 +216,11 @@ fn execute_propose(
         return Err(ContractError::ZeroChoices {});
     }
 
-    let none_of_the_above = Choice { msgs: vec![] };
+    let none_of_the_above = Choice {
+        title: "None of the above".to_string(),
+        description: "None of the above".to_string(),
+        msgs: vec![],
+    };
     let mut choices = choices;
     choices.push(none_of_the_above);
 
@@ -126,15 +228,35 @@ fn execute_propose(
         choices.len() as u32,
         total_power,
         env.block.height,
-        config.voting_period.after(&env.block),
+        period.after(&env.block),
+        config.completion,
+        config.allow_revoting,
     );
     TALLY.save(deps.storage, id, &tally)?;
 
-    let mut proposal = Proposal::new(&env.block, &config, info.sender, id, choices, total_power);
+    let mut proposal = Proposal::new(
+        &env.block,
+        &config,
+        proposer,
+        id,
+        title,
+        description,
+        choices,
+        total_power,
+        voting_period,
+    );
     proposal.update_status(&env.block, &tally);
     PROPOSAL.save(deps.storage, id, &proposal)?;
 
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id as u64,
+        proposal.proposer.as_str(),
+    )?;
+
     Ok(Response::default()
+        .add_submessages(hooks)
         .add_attribute("method", "propose")
         .add_attribute("proposal_id", proposal.id.to_string())
         .add_attribute("proposer", proposal.proposer))
@@ -146,8 +268,9 @@ fn execute_vote(
     info: MessageInfo,
     proposal_id: u32,
     vote: Vec<u32>,
+    rationale: Option<String>,
 ) -> Result<Response, ContractError> {
-    let tally = TALLY.load(deps.storage, proposal_id)?;
+    let mut tally = TALLY.load(deps.storage, proposal_id)?;
     let sender_power = get_voting_power(
         deps.as_ref(),
         info.sender.clone(),
@@ -155,25 +278,77 @@ fn execute_vote(
         Some(tally.start_height),
     )?;
     if sender_power.is_zero() {
-        Err(ContractError::ZeroVotingPower {})
-    } else if VOTE.has(deps.storage, (proposal_id, info.sender.clone())) {
-        Err(ContractError::Voted {})
-    } else if tally.expired(&env.block) {
-        Err(ContractError::Expired {})
-    } else {
-        let vote = Vote::new(vote, tally.candidates())?;
-        VOTE.save(deps.storage, (proposal_id, info.sender.clone()), &vote)?;
-
-        let mut tally = tally;
-        tally.add_vote(vote, sender_power);
-        TALLY.save(deps.storage, proposal_id, &tally)?;
+        return Err(ContractError::ZeroVotingPower {});
+    }
+    if tally.expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    let vote = Vote::new(vote, tally.candidates())?;
 
-        Ok(Response::default()
-            .add_attribute("method", "vote")
-            .add_attribute("proposal_id", proposal_id.to_string())
-            .add_attribute("voter", info.sender)
-            .add_attribute("power", sender_power))
+    // voting power is queried at the tally's start height, so a
+    // revote is cast with the same power as the vote it replaces.
+    match VOTE.may_load(deps.storage, (proposal_id, info.sender.clone()))? {
+        Some(_) if !tally.allow_revoting => return Err(ContractError::Voted {}),
+        Some(ballot) if ballot.vote == vote => return Err(ContractError::AlreadyCast {}),
+        Some(ballot) => tally.revote(ballot.vote, vote.clone(), sender_power),
+        None => tally.add_vote(vote.clone(), sender_power),
     }
+    TALLY.save(deps.storage, proposal_id, &tally)?;
+    VOTE.save(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        &Ballot {
+            vote: vote.clone(),
+            power: sender_power,
+            rationale: rationale.clone(),
+        },
+    )?;
+
+    let hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id as u64,
+        info.sender.to_string(),
+        vote.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("method", "vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("power", sender_power)
+        .add_attribute("rationale", rationale.as_deref().unwrap_or("_none")))
+}
+
+fn execute_update_rationale(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u32,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    VOTE.update(
+        deps.storage,
+        // the sender can't be forged, so keying on it is all the
+        // access control needed.
+        (proposal_id, info.sender.clone()),
+        |ballot| match ballot {
+            Some(ballot) => Ok(Ballot {
+                rationale: rationale.clone(),
+                ..ballot
+            }),
+            None => Err(ContractError::NoSuchVote {
+                id: proposal_id,
+                voter: info.sender.to_string(),
+            }),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_rationale")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("rationale", rationale.as_deref().unwrap_or("_none")))
 }
 
 fn execute_execute(
@@ -195,11 +370,24 @@ fn execute_execute(
     }
 
     let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let old_status = proposal.last_status();
     if let Status::Passed { winner } = proposal.update_status(&env.block, &tally) {
         let msgs = proposal.set_executed(dao, winner)?;
         PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
 
+        let hooks = proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id as u64,
+            old_status.to_string(),
+            proposal.last_status().to_string(),
+        )?;
+        let pre_propose_hook =
+            pre_propose_completed_hook(deps.storage, proposal_id, CompletedStatus::Executed)?;
+
         Ok(Response::default()
+            .add_submessages(hooks)
+            .add_submessages(pre_propose_hook)
             .add_attribute("method", "execute")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("executor", info.sender)
@@ -217,11 +405,24 @@ fn execute_close(
 ) -> Result<Response, ContractError> {
     let tally = TALLY.load(deps.storage, proposal_id)?;
     let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let old_status = proposal.last_status();
     if let Status::Rejected = proposal.update_status(&env.block, &tally) {
         proposal.set_closed();
         PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
 
+        let hooks = proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id as u64,
+            old_status.to_string(),
+            proposal.last_status().to_string(),
+        )?;
+        let pre_propose_hook =
+            pre_propose_completed_hook(deps.storage, proposal_id, CompletedStatus::Closed)?;
+
         Ok(Response::default()
+            .add_submessages(hooks)
+            .add_submessages(pre_propose_hook)
             .add_attribute("method", "close")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("closer", info.sender))
@@ -245,16 +446,177 @@ fn execute_set_config(
     }
 }
 
+fn execute_update_pre_propose_info(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(ContractError::NotDao {});
+    }
+
+    let (initial_policy, messages) = new_info.into_initial_policy_and_messages(dao)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("method", "update_pre_propose_info")
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+/// Tells the pre-propose module, if there is one, that a proposal
+/// has been executed or closed so that it may handle its deposit.
+fn pre_propose_completed_hook(
+    storage: &dyn Storage,
+    proposal_id: u32,
+    new_status: CompletedStatus,
+) -> StdResult<Option<SubMsg>> {
+    Ok(match CREATION_POLICY.load(storage)? {
+        ProposalCreationPolicy::Anyone {} => None,
+        ProposalCreationPolicy::Module { addr } => Some(SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: addr.into_string(),
+                msg: to_binary(&PreProposeHookMsg::ProposalCompletedHook {
+                    proposal_id: proposal_id as u64,
+                    new_status,
+                })?,
+                funds: vec![],
+            },
+            failed_pre_propose_module_hook_id(),
+        )),
+    })
+}
+
+fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    address: String,
+    method: &str,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    hooks.add_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", method)
+        .add_attribute("address", address))
+}
+
+fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    address: String,
+    method: &str,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    hooks.remove_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", method)
+        .add_attribute("address", address))
+}
+
+fn proposal_response(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    mut proposal: Proposal,
+) -> StdResult<ProposalResponse> {
+    let tally = TALLY.load(storage, proposal.id)?;
+    proposal.update_status(block, &tally);
+    Ok(ProposalResponse { proposal, tally })
+}
+
+fn list_proposals(
+    deps: Deps,
+    env: Env,
+    min: Option<Bound<u32>>,
+    max: Option<Bound<u32>>,
+    order: Order,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let proposals = PROPOSAL
+        .range(deps.storage, min, max, order)
+        .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+        .map(|item| {
+            item.and_then(|(_, proposal)| proposal_response(deps.storage, &env.block, proposal))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&proposals)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Proposal { id } => {
-            let mut proposal = PROPOSAL.load(deps.storage, id)?;
+            let proposal = PROPOSAL.load(deps.storage, id)?;
+            to_binary(&proposal_response(deps.storage, &env.block, proposal)?)
+        }
+        QueryMsg::ListProposals { start_after, limit } => list_proposals(
+            deps,
+            env,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+            limit,
+        ),
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => list_proposals(
+            deps,
+            env,
+            None,
+            start_before.map(Bound::exclusive),
+            Order::Descending,
+            limit,
+        ),
+        QueryMsg::GetVote { proposal_id, voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            let vote = VOTE
+                .may_load(deps.storage, (proposal_id, voter.clone()))?
+                .map(|ballot| VoteInfo::new(voter, ballot));
+            to_binary(&VoteResponse { vote })
+        }
+        QueryMsg::ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => {
+            let start_after = start_after
+                .map(|voter| deps.api.addr_validate(&voter))
+                .transpose()?;
+            let votes = VOTE
+                .prefix(proposal_id)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::<Addr>::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+                .map(|item| item.map(|(voter, ballot)| VoteInfo::new(voter, ballot)))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_binary(&VoteListResponse { votes })
+        }
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::Tally { id } => {
             let tally = TALLY.load(deps.storage, id)?;
-            proposal.update_status(&env.block, &tally);
-            to_binary(&ProposalResponse { proposal, tally })
+            to_binary(&TallyResponse {
+                margins: tally.margins(),
+                tally,
+            })
         }
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
         QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
         QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
         QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
@@ -274,6 +636,47 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             Ok(Response::default()
                 .add_attribute("proposal_execution_failed", proposal_id.to_string()))
         }
-        _ => unimplemented!("pre-propose and hooks not yet supported"),
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::default().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::FailedVoteHook(idx) => {
+            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::default().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
+        }
+        TaggedReplyId::PreProposeModuleInstantiation => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let module = deps.api.addr_validate(&res.contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module { addr: module },
+            )?;
+
+            let response = Response::default()
+                .add_attribute("update_pre_propose_module", res.contract_address);
+            // forward the pre-propose module's instantiation data to
+            // the DAO.
+            Ok(match res.data {
+                Some(data) => response.set_data(data),
+                None => response,
+            })
+        }
+        TaggedReplyId::FailedPreProposeModuleHook => {
+            // the pre-propose module errored while handling a
+            // completed proposal. remove it so that proposals may
+            // still be executed and closed.
+            match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    Err(ContractError::InvalidReplyId { id: msg.id })
+                }
+                ProposalCreationPolicy::Module { addr } => {
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    Ok(Response::default().add_attribute("failed_prepropose_hook", addr))
+                }
+            }
+        }
+        TaggedReplyId::FailedProposalMessage(..) | TaggedReplyId::FailedInstalment(..) => {
+            Err(ContractError::InvalidReplyId { id: msg.id })
+        }
     }
 }
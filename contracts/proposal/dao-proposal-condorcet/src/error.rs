@@ -1,4 +1,6 @@
 use cosmwasm_std::StdError;
+use cw_hooks::HookError;
+use cw_utils::ParseReplyError;
 use dao_voting::{error::VotingError, reply::error::TagError, threshold::ThresholdError};
 use thiserror::Error;
 
@@ -16,6 +18,10 @@ pub enum ContractError {
     Voting(#[from] VotingError),
     #[error(transparent)]
     Tag(#[from] TagError),
+    #[error(transparent)]
+    Hook(#[from] HookError),
+    #[error(transparent)]
+    ParseReply(#[from] ParseReplyError),
 
     #[error("non-zero voting power required to perform this action")]
     ZeroVotingPower {},
@@ -32,9 +38,26 @@ pub enum ContractError {
     #[error("already voted")]
     Voted {},
 
+    #[error("already cast a vote with that ranking. change your vote to revote")]
+    AlreadyCast {},
+
+    #[error("no vote exists for proposal ({id}) and voter ({voter})")]
+    NoSuchVote { id: u32, voter: String },
+
     #[error("only non-expired proposals may be voted on")]
     Expired {},
 
     #[error("must specify at least one choice for proposal")]
     ZeroChoices {},
+
+    #[error("only the proposal creation policy's module may create proposals")]
+    NotPermitted {},
+
+    #[error(
+        "a proposer may be specified if, and only if, a pre-propose module creates the proposal"
+    )]
+    InvalidProposer {},
+
+    #[error("received unexpected reply ({id})")]
+    InvalidReplyId { id: u64 },
 }
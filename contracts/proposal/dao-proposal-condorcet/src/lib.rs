@@ -1,4 +1,5 @@
-mod cell;
+pub mod cell;
+pub mod completion;
 pub mod config;
 pub mod contract;
 mod error;
@@ -1,24 +1,87 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::Duration;
 
 use dao_macros::proposal_module_query;
+use dao_voting::pre_propose::PreProposeInfo;
 
 use crate::config::UncheckedConfig;
 
-pub type InstantiateMsg = UncheckedConfig;
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub config: UncheckedConfig,
+    /// Who may create proposals, and the pre-propose module to
+    /// instantiate if proposals are to be created through one.
+    pub pre_propose_info: PreProposeInfo,
+}
 
 #[cw_serde]
 pub struct Choice {
+    pub title: String,
+    pub description: String,
     pub msgs: Vec<CosmosMsg<Empty>>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    Propose { choices: Vec<Choice> },
-    Vote { proposal_id: u32, vote: Vec<u32> },
-    Execute { proposal_id: u32 },
-    Close { proposal_id: u32 },
+    Propose {
+        title: String,
+        description: String,
+        choices: Vec<Choice>,
+        /// The address creating the proposal. Must be set if, and
+        /// only if, the proposal is created by a pre-propose module.
+        proposer: Option<String>,
+        /// The voting period of the proposal, if the module allows
+        /// proposers to choose one. If None, the module's voting
+        /// period is used.
+        #[serde(default)]
+        voting_period: Option<Duration>,
+    },
+    Vote {
+        proposal_id: u32,
+        vote: Vec<u32>,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the voter.
+        rationale: Option<String>,
+    },
+    /// Updates the sender's rationale for their vote on the specified
+    /// proposal. Errors if no vote has been cast.
+    UpdateRationale {
+        proposal_id: u32,
+        rationale: Option<String>,
+    },
+    Execute {
+        proposal_id: u32,
+    },
+    Close {
+        proposal_id: u32,
+    },
     SetConfig(UncheckedConfig),
+    /// Updates who may create proposals. Only the DAO may call this
+    /// method.
+    UpdatePreProposeInfo {
+        info: PreProposeInfo,
+    },
+    /// Adds an address as a consumer of proposal hooks. Only the DAO
+    /// may call this method.
+    AddProposalHook {
+        address: String,
+    },
+    /// Removes a consumer of proposal hooks. Only the DAO may call
+    /// this method.
+    RemoveProposalHook {
+        address: String,
+    },
+    /// Adds an address as a consumer of vote hooks. Only the DAO may
+    /// call this method.
+    AddVoteHook {
+        address: String,
+    },
+    /// Removes a consumer of vote hooks. Only the DAO may call this
+    /// method.
+    RemoveVoteHook {
+        address: String,
+    },
 }
 
 #[proposal_module_query]
@@ -27,6 +90,39 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(crate::proposal::ProposalResponse)]
     Proposal { id: u32 },
+    /// Lists proposals in ascending order of id.
+    #[returns(Vec<crate::proposal::ProposalResponse>)]
+    ListProposals {
+        start_after: Option<u32>,
+        limit: Option<u64>,
+    },
+    /// Lists proposals in descending order of id.
+    #[returns(Vec<crate::proposal::ProposalResponse>)]
+    ReverseProposals {
+        start_before: Option<u32>,
+        limit: Option<u64>,
+    },
+    /// Gets the tally of a proposal along with its full pairwise
+    /// margin matrix.
+    #[returns(crate::proposal::TallyResponse)]
+    Tally { id: u32 },
+    /// Gets VOTER's vote on a proposal, if they have voted.
+    #[returns(crate::vote::VoteResponse)]
+    GetVote { proposal_id: u32, voter: String },
+    /// Lists the votes cast on a proposal in ascending order of
+    /// voter address.
+    #[returns(crate::vote::VoteListResponse)]
+    ListVotes {
+        proposal_id: u32,
+        start_after: Option<String>,
+        limit: Option<u64>,
+    },
     #[returns(crate::config::Config)]
     Config {},
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    #[returns(cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    #[returns(cw_hooks::HooksResponse)]
+    VoteHooks {},
 }
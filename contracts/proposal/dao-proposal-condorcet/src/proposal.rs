@@ -1,12 +1,14 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{to_binary, Addr, BlockInfo, StdResult, SubMsg, Uint128, WasmMsg};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use dao_voting::{
-    reply::mask_proposal_execution_proposal_id, threshold::PercentageThreshold,
+    reply::mask_proposal_execution_proposal_id,
+    threshold::{stricter_quorum, PercentageThreshold},
     voting::does_vote_count_pass,
 };
 
 use crate::{
+    cell::Cell,
     config::Config,
     msg::Choice,
     tally::{Tally, Winner},
@@ -17,6 +19,8 @@ pub struct Proposal {
     last_status: Status,
 
     pub proposer: Addr,
+    pub title: String,
+    pub description: String,
 
     pub quorum: PercentageThreshold,
     pub min_voting_period: Option<Expiration>,
@@ -26,6 +30,10 @@ pub struct Proposal {
 
     pub id: u32,
     pub choices: Vec<Choice>,
+
+    /// The voting period chosen by the proposer, if any.
+    #[serde(default)]
+    pub voting_period: Option<Duration>,
 }
 
 #[cw_serde]
@@ -46,12 +54,33 @@ pub enum Status {
     ExecutionFailed,
 }
 
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Rejected => write!(f, "rejected"),
+            Status::Passed { .. } => write!(f, "passed"),
+            Status::Executed => write!(f, "executed"),
+            Status::Closed => write!(f, "closed"),
+            Status::ExecutionFailed => write!(f, "execution_failed"),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct ProposalResponse {
     pub proposal: Proposal,
     pub tally: Tally,
 }
 
+#[cw_serde]
+pub struct TallyResponse {
+    /// `margins[x][y]` is the voting power that ranked candidate x
+    /// above candidate y minus that which ranked y above x.
+    pub margins: Vec<Vec<Cell>>,
+    pub tally: Tally,
+}
+
 fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
     match proposal.last_status {
         Status::Rejected
@@ -87,7 +116,7 @@ fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
                             Status::Open
                         }
                     }
-                    Winner::Some(winner) => {
+                    Winner::Some(winner) | Winner::Completed(winner) => {
                         if expired && quorum {
                             Status::Passed { winner }
                         } else {
@@ -108,25 +137,42 @@ fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
 }
 
 impl Proposal {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         block: &BlockInfo,
         config: &Config,
         proposer: Addr,
         id: u32,
+        title: String,
+        description: String,
         choices: Vec<Choice>,
         total_power: Uint128,
+        voting_period: Option<Duration>,
     ) -> Self {
+        // proposals with short voting periods may require a higher
+        // quorum than the module's, but never a lower one.
+        let quorum = match config
+            .voting_period_choice
+            .as_ref()
+            .and_then(|choice| choice.quorum(&voting_period.unwrap_or(config.voting_period)))
+        {
+            Some(quorum) => stricter_quorum(quorum, config.quorum),
+            None => config.quorum,
+        };
         Self {
             last_status: Status::Open,
 
             min_voting_period: config.min_voting_period.map(|m| m.after(block)),
-            quorum: config.quorum,
+            quorum,
             close_on_execution_failure: config.close_proposals_on_execution_failure,
 
             id,
             proposer,
+            title,
+            description,
             choices,
             total_power,
+            voting_period,
         }
     }
 
@@ -139,8 +185,8 @@ impl Proposal {
         status(block, self, tally)
     }
 
-    // To test that status is updated before responding to queries.
-    #[cfg(test)]
+    /// The proposal's status as of when it was last updated. Queries
+    /// update the status before responding.
     pub fn last_status(&self) -> Status {
         self.last_status
     }
@@ -1,14 +1,20 @@
 use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
+use dao_voting::pre_propose::ProposalCreationPolicy;
 
-use crate::{config::Config, proposal::Proposal, tally::Tally, vote::Vote};
+use crate::{config::Config, proposal::Proposal, tally::Tally, vote::Ballot};
 
 pub(crate) const DAO: Item<Addr> = Item::new("dao");
 pub(crate) const CONFIG: Item<Config> = Item::new("config");
+pub(crate) const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
 
 pub(crate) const TALLY: Map<u32, Tally> = Map::new("tallys");
 pub(crate) const PROPOSAL: Map<u32, Proposal> = Map::new("proposals");
-pub(crate) const VOTE: Map<(u32, Addr), Vote> = Map::new("votes");
+pub(crate) const VOTE: Map<(u32, Addr), Ballot> = Map::new("votes");
+
+pub(crate) const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+pub(crate) const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 
 pub(crate) fn next_proposal_id(storage: &dyn Storage) -> StdResult<u32> {
     PROPOSAL
@@ -3,6 +3,8 @@ use cosmwasm_std::{BlockInfo, Uint128};
 use cw_utils::Expiration;
 
 use crate::{
+    cell::Cell,
+    completion::Completion,
     m::{Stats, M},
     vote::Vote,
 };
@@ -17,7 +19,7 @@ use crate::{
 /// if a column holds all positive, non-zero values then the
 /// corresponding candidate is the Condorcet winner. A Condorcet
 /// winner is undisputed if it's smallest margin of victory is larger
-/// than the outstanding voting power.
+/// than the outstanding voting power and votes may not be changed.
 #[cw_serde]
 pub struct Tally {
     m: M,
@@ -30,6 +32,14 @@ pub struct Tally {
     pub power_outstanding: Uint128,
     /// The current winner. Always up to date and updated on vote.
     pub winner: Winner,
+    /// How a winner is chosen if there is no Condorcet winner.
+    #[serde(default)]
+    pub completion: Option<Completion>,
+    /// If votes may be changed. If so, the winner is never
+    /// undisputed and the tally is never without a possible winner
+    /// until it expires.
+    #[serde(default)]
+    pub allow_revoting: bool,
 }
 
 #[cw_serde]
@@ -39,6 +49,9 @@ pub enum Winner {
     None,
     Some(u32),
     Undisputed(u32),
+    /// There is no Condorcet winner, and this candidate was chosen by
+    /// the tally's completion method.
+    Completed(u32),
 }
 
 impl Tally {
@@ -47,6 +60,8 @@ impl Tally {
         total_power: Uint128,
         start_height: u64,
         expiration: Expiration,
+        completion: Option<Completion>,
+        allow_revoting: bool,
     ) -> Self {
         let mut tally = Self {
             m: M::new(candidates),
@@ -54,6 +69,8 @@ impl Tally {
             winner: Winner::None,
             start_height,
             expiration,
+            completion,
+            allow_revoting,
         };
         // compute even though this will always be Winner::None so
         // that creating a tally has the same compute cost of adding a
@@ -67,6 +84,24 @@ impl Tally {
         self.m.n
     }
 
+    /// The tally's pairwise margins. `margins[x][y]` is the voting
+    /// power that ranked x above y minus that which ranked y above x.
+    pub fn margins(&self) -> Vec<Vec<Cell>> {
+        (0..self.m.n)
+            .map(|x| {
+                (0..self.m.n)
+                    .map(|y| {
+                        if x == y {
+                            Cell::Zero
+                        } else {
+                            self.m.get((x, y))
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn expired(&self, block: &BlockInfo) -> bool {
         self.expiration.is_expired(block)
     }
@@ -94,10 +129,38 @@ impl Tally {
         self.winner = self.winner();
     }
 
+    /// Replaces a vote recorded with `add_vote` with a new vote of
+    /// the same power. Only the pairs of candidates whose order
+    /// differs between the two votes are updated, so this costs no
+    /// more than `add_vote`.
+    ///
+    /// Invariants:
+    ///
+    /// - `old` was recorded in this tally with `power`.
+    /// - Tally is not expired.
+    pub fn revote(&mut self, old: Vote, new: Vote, power: Uint128) {
+        // old_rank[c] is the position of candidate c in the old vote.
+        let mut old_rank = vec![0; self.m.n as usize];
+        for (index, candidate) in old.iter().enumerate() {
+            old_rank[*candidate as usize] = index
+        }
+        for (index, preference) in new.iter().enumerate() {
+            for defeat in 0..index {
+                let winner = new[defeat];
+                // if the old vote ranked preference above winner,
+                // undo that victory and record this defeat.
+                if old_rank[*preference as usize] < old_rank[winner as usize] {
+                    self.m.decrement((*preference, winner), power + power)
+                }
+            }
+        }
+        self.winner = self.winner();
+    }
+
     fn winner(&self) -> Winner {
         match self.m.stats(self.power_outstanding) {
             Stats::PositiveColumn { col, min_margin } => {
-                if min_margin > self.power_outstanding {
+                if min_margin > self.power_outstanding && !self.allow_revoting {
                     Winner::Undisputed(col)
                 } else {
                     Winner::Some(col)
@@ -106,7 +169,14 @@ impl Tally {
             Stats::NoPositiveColumn {
                 no_winnable_columns,
             } => {
-                if no_winnable_columns {
+                if let Some(completion) = self.completion {
+                    // votes may still change the completion's choice,
+                    // so the election is not over even if no column
+                    // is winnable.
+                    completion
+                        .winner(&self.m)
+                        .map_or(Winner::None, Winner::Completed)
+                } else if no_winnable_columns && !self.allow_revoting {
                     Winner::Never
                 } else {
                     Winner::None
@@ -13,7 +13,7 @@ fn test_instantiation() {
     let suite = SuiteBuilder::default().build();
     let config = suite.query_config();
 
-    assert_eq!(config, default_config.into_checked().unwrap())
+    assert_eq!(config, default_config.config.into_checked().unwrap())
 }
 
 #[test]
@@ -24,6 +24,9 @@ fn test_instantiate_conflicting_proposal_durations() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Height(11)),
         close_proposals_on_execution_failure: true,
+        completion: None,
+        allow_revoting: false,
+        voting_period_choice: None,
     })
     .build();
 }
@@ -38,6 +41,9 @@ fn test_instantiate_conflicting_duration_types() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Time(9)),
         close_proposals_on_execution_failure: true,
+        completion: None,
+        allow_revoting: false,
+        voting_period_choice: None,
     })
     .build();
 }
@@ -49,6 +55,9 @@ fn test_instantiate_open_til_expiry() {
         voting_period: Duration::Height(10),
         min_voting_period: Some(Duration::Height(10)),
         close_proposals_on_execution_failure: true,
+        completion: None,
+        allow_revoting: false,
+        voting_period_choice: None,
     })
     .build();
     SuiteBuilder::with_config(UncheckedConfig {
@@ -56,6 +65,9 @@ fn test_instantiate_open_til_expiry() {
         voting_period: Duration::Time(10),
         min_voting_period: Some(Duration::Time(10)),
         close_proposals_on_execution_failure: true,
+        completion: None,
+        allow_revoting: false,
+        voting_period_choice: None,
     })
     .build();
 }
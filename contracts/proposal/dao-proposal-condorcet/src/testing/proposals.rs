@@ -1,9 +1,16 @@
-use cosmwasm_std::{to_binary, WasmMsg};
+use cosmwasm_std::{to_binary, Decimal, Uint128, WasmMsg};
 use cw_utils::Duration;
+use dao_voting::{
+    error::VotingError,
+    threshold::PercentageThreshold,
+    voting_period::{QuorumStep, VotingPeriodChoice},
+};
 
 use crate::{
+    cell::Cell,
+    completion::Completion,
     config::UncheckedConfig,
-    msg::ExecuteMsg,
+    msg::{ExecuteMsg, QueryMsg},
     proposal::{ProposalResponse, Status},
     tally::Winner,
     testing::suite::unimportant_message,
@@ -55,9 +62,14 @@ fn test_make_proposal() {
     let ProposalResponse { proposal, tally } = suite.query_proposal(id);
 
     assert_eq!(proposal.id, id);
+    assert_eq!(proposal.title, "proposal 1");
+    assert_eq!(proposal.description, "a proposal");
     assert_eq!(proposal.choices.len(), 2);
+    assert_eq!(proposal.choices[0].title, "choice 0");
     assert_eq!(proposal.choices[0].msgs[0], unimportant_message());
-    assert_eq!(proposal.choices[1].msgs, vec![]); // none-of-the-above added to the end.
+    // none-of-the-above added to the end.
+    assert_eq!(proposal.choices[1].title, "None of the above");
+    assert_eq!(proposal.choices[1].msgs, vec![]);
 
     assert_eq!(tally.candidates(), 2);
     assert_eq!(tally.winner, Winner::None);
@@ -134,6 +146,141 @@ fn test_proposal_passes_after_expiry() {
     assert_eq!(status, Status::Passed { winner: 0 });
 }
 
+#[test]
+fn test_voting_period_choice() {
+    let mut builder = SuiteBuilder::default().with_voters(&[("pleb", 20), ("belp", 80)]);
+    builder.instantiate.config.voting_period_choice = Some(VotingPeriodChoice {
+        min: Duration::Time(60 * 60 * 24),
+        quorum_schedule: vec![QuorumStep {
+            max_voting_period: Duration::Time(60 * 60 * 24 * 2),
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+        }],
+    });
+    let mut suite = builder.build();
+
+    let urgent = suite
+        .propose_with_voting_period(
+            "pleb",
+            vec![vec![unimportant_message()]],
+            Some(Duration::Time(60 * 60 * 24)),
+        )
+        .unwrap();
+    let routine = suite
+        .propose("pleb", vec![vec![unimportant_message()]])
+        .unwrap();
+
+    let proposal = suite.query_proposal(urgent).proposal;
+    assert_eq!(proposal.voting_period, Some(Duration::Time(60 * 60 * 24)));
+    assert_eq!(
+        proposal.quorum,
+        PercentageThreshold::Percent(Decimal::percent(50))
+    );
+    let proposal = suite.query_proposal(routine).proposal;
+    assert_eq!(proposal.voting_period, None);
+    assert_eq!(
+        proposal.quorum,
+        PercentageThreshold::Percent(Decimal::percent(15))
+    );
+
+    // 20% of the voting power is enough for routine proposals, but
+    // not for urgent ones.
+    suite.vote("pleb", urgent, vec![0, 1]).unwrap();
+    suite.vote("pleb", routine, vec![0, 1]).unwrap();
+    suite.a_day_passes();
+    let (_, status) = suite.query_winner_and_status(urgent);
+    assert_eq!(status, Status::Rejected);
+    let (_, status) = suite.query_winner_and_status(routine);
+    assert_eq!(status, Status::Open);
+    suite.a_week_passes();
+    let (_, status) = suite.query_winner_and_status(routine);
+    assert_eq!(status, Status::Passed { winner: 0 });
+
+    let err = suite.propose_with_voting_period(
+        "pleb",
+        vec![vec![unimportant_message()]],
+        Some(Duration::Time(60)),
+    );
+    is_error!(
+        err,
+        &ContractError::Voting(VotingError::InvalidVotingPeriod {}).to_string()
+    );
+}
+
+#[test]
+fn test_quorum_schedule_does_not_lower_quorum() {
+    let mut builder = SuiteBuilder::default().with_voters(&[("pleb", 20), ("belp", 80)]);
+    builder.instantiate.config.voting_period_choice = Some(VotingPeriodChoice {
+        min: Duration::Time(60 * 60 * 24),
+        quorum_schedule: vec![QuorumStep {
+            max_voting_period: Duration::Time(60 * 60 * 24 * 2),
+            quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+        }],
+    });
+    let mut suite = builder.build();
+
+    let urgent = suite
+        .propose_with_voting_period(
+            "pleb",
+            vec![vec![unimportant_message()]],
+            Some(Duration::Time(60 * 60 * 24)),
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_proposal(urgent).proposal.quorum,
+        PercentageThreshold::Percent(Decimal::percent(15))
+    );
+}
+
+// the votes form a cycle, 0 > 1 > 2 > 0, which is broken by
+// overruling the weakest victory, 2 > 0.
+#[test]
+fn test_completion_breaks_cycle() {
+    let mut builder = SuiteBuilder::default();
+    builder.instantiate.config.completion = Some(Completion::RankedPairs {});
+    let mut suite = builder
+        .with_voters(&[("ekez", 4), ("blue", 3), ("zeke", 2)])
+        .with_proposal(2)
+        .build();
+
+    suite.vote("ekez", 1, vec![0, 1, 2]).unwrap();
+    suite.vote("blue", 1, vec![1, 2, 0]).unwrap();
+    suite.vote("zeke", 1, vec![2, 0, 1]).unwrap();
+
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Completed(0));
+    assert_eq!(status, Status::Open);
+
+    let tally = suite.query_tally(1);
+    assert_eq!(
+        tally.margins,
+        vec![
+            vec![
+                Cell::Zero,
+                Cell::Positive(3u128.into()),
+                Cell::Negative(1u128.into())
+            ],
+            vec![
+                Cell::Negative(3u128.into()),
+                Cell::Zero,
+                Cell::Positive(5u128.into())
+            ],
+            vec![
+                Cell::Positive(1u128.into()),
+                Cell::Negative(5u128.into()),
+                Cell::Zero
+            ],
+        ]
+    );
+    assert_eq!(tally.tally.completion, Some(Completion::RankedPairs {}));
+
+    suite.a_week_passes();
+
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Completed(0));
+    assert_eq!(status, Status::Passed { winner: 0 });
+    suite.execute("ekez", 1).unwrap();
+}
+
 #[test]
 fn test_no_vote_after_expiry() {
     let mut suite = SuiteBuilder::default().with_proposal(1).build();
@@ -176,6 +323,9 @@ fn test_proposal_set_config() {
                     voting_period: config.voting_period,
                     min_voting_period: None,
                     close_proposals_on_execution_failure: false,
+                    completion: None,
+                    allow_revoting: false,
+                    voting_period_choice: None,
                 }))
                 .unwrap(),
                 funds: vec![],
@@ -199,6 +349,9 @@ fn test_proposal_set_config() {
                     voting_period: config.voting_period,
                     min_voting_period: Some(Duration::Height(10)),
                     close_proposals_on_execution_failure: false,
+                    completion: None,
+                    allow_revoting: false,
+                    voting_period_choice: None,
                 }))
                 .unwrap(),
                 funds: vec![],
@@ -228,7 +381,10 @@ fn test_proposal_set_config() {
 #[test]
 fn test_execution_fail_handling() {
     let mut suite = SuiteBuilder::default().with_proposal(1);
-    suite.instantiate.close_proposals_on_execution_failure = false;
+    suite
+        .instantiate
+        .config
+        .close_proposals_on_execution_failure = false;
     let mut suite = suite.build();
 
     suite.vote(suite.sender(), 1, vec![0, 1]).unwrap();
@@ -236,3 +392,209 @@ fn test_execution_fail_handling() {
     // no state changes get committed.
     suite.execute(suite.sender(), 1).unwrap_err();
 }
+
+#[test]
+fn test_list_proposals() {
+    let mut suite = SuiteBuilder::default().build();
+    for _ in 0..3 {
+        suite
+            .propose(suite.sender(), vec![vec![unimportant_message()]])
+            .unwrap();
+    }
+
+    assert_eq!(suite.query_list_proposals(None, false), vec![1, 2, 3]);
+    assert_eq!(suite.query_list_proposals(Some(1), false), vec![2, 3]);
+    assert_eq!(suite.query_list_proposals(None, true), vec![3, 2, 1]);
+    assert_eq!(suite.query_list_proposals(Some(3), true), vec![2, 1]);
+}
+
+#[test]
+fn test_vote_queries() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 4), ("blue", 3)])
+        .with_proposal(2)
+        .build();
+
+    assert_eq!(suite.query_vote(1, "ekez"), None);
+
+    suite.vote("ekez", 1, vec![0, 1, 2]).unwrap();
+    suite.vote("blue", 1, vec![1, 2, 0]).unwrap();
+
+    let vote = suite.query_vote(1, "ekez").unwrap();
+    assert_eq!(vote.voter, "ekez");
+    assert_eq!(vote.vote.to_string(), "0,1,2");
+    assert_eq!(vote.power, Uint128::new(4));
+
+    let votes = suite.query_list_votes(1, None);
+    assert_eq!(
+        votes
+            .iter()
+            .map(|v| (v.voter.as_str(), v.power.u128()))
+            .collect::<Vec<_>>(),
+        vec![("blue", 3), ("ekez", 4)]
+    );
+    let votes = suite.query_list_votes(1, Some("blue"));
+    assert_eq!(votes.len(), 1);
+    assert_eq!(votes[0].voter, "ekez");
+}
+
+#[test]
+fn test_revoting() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 4), ("blue", 3)])
+        .with_proposal(2)
+        .build();
+
+    suite.vote("ekez", 1, vec![0, 1, 2]).unwrap();
+    let err = suite.vote("ekez", 1, vec![1, 0, 2]);
+    is_error!(err, &ContractError::Voted {}.to_string());
+
+    let mut builder = SuiteBuilder::default()
+        .with_voters(&[("ekez", 4), ("blue", 3)])
+        .with_proposal(2);
+    builder.instantiate.config.allow_revoting = true;
+    builder.instantiate.config.min_voting_period = None;
+    let mut suite = builder.build();
+
+    // a majority of the voting power is cast, but as it may change
+    // the proposal stays open until it expires.
+    suite.vote("ekez", 1, vec![0, 1, 2]).unwrap();
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Some(0));
+    assert_eq!(status, Status::Open);
+
+    let err = suite.vote("ekez", 1, vec![0, 1, 2]);
+    is_error!(err, &ContractError::AlreadyCast {}.to_string());
+
+    suite.vote("ekez", 1, vec![1, 0, 2]).unwrap();
+    suite.vote("blue", 1, vec![0, 1, 2]).unwrap();
+    let vote = suite.query_vote(1, "ekez").unwrap();
+    assert_eq!(vote.vote.to_string(), "1,0,2");
+    assert_eq!(vote.power, Uint128::new(4));
+
+    suite.a_week_passes();
+    let err = suite.vote("ekez", 1, vec![0, 1, 2]);
+    is_error!(err, &ContractError::Expired {}.to_string());
+
+    let (winner, status) = suite.query_winner_and_status(1);
+    assert_eq!(winner, Winner::Some(1));
+    assert_eq!(status, Status::Passed { winner: 1 });
+}
+
+#[test]
+fn test_rationale() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 4), ("blue", 3)])
+        .with_proposal(2)
+        .build();
+
+    let err = suite.update_rationale("ekez", 1, Some("I forgot to vote"));
+    is_error!(
+        err,
+        &ContractError::NoSuchVote {
+            id: 1,
+            voter: "ekez".to_string()
+        }
+        .to_string()
+    );
+
+    suite
+        .vote_with_rationale("ekez", 1, vec![0, 1, 2], Some("zero is the best"))
+        .unwrap();
+    suite.vote("blue", 1, vec![1, 0, 2]).unwrap();
+    assert_eq!(
+        suite.query_vote(1, "ekez").unwrap().rationale.as_deref(),
+        Some("zero is the best")
+    );
+    assert_eq!(suite.query_vote(1, "blue").unwrap().rationale, None);
+
+    suite
+        .update_rationale("ekez", 1, Some("zero is the best, and one is fine"))
+        .unwrap();
+    suite
+        .update_rationale("blue", 1, Some("one all the way"))
+        .unwrap();
+    let rationales: Vec<_> = suite
+        .query_list_votes(1, None)
+        .into_iter()
+        .map(|v| v.rationale.unwrap())
+        .collect();
+    assert_eq!(
+        rationales,
+        vec!["one all the way", "zero is the best, and one is fine"]
+    );
+
+    // updating a rationale does not change the vote.
+    assert_eq!(
+        suite.query_vote(1, "ekez").unwrap().vote.to_string(),
+        "0,1,2"
+    );
+}
+
+#[test]
+fn test_hooks() {
+    let mut suite = SuiteBuilder::default().with_proposal(1).build();
+    let dao = suite.core.clone();
+
+    let err = suite.update_hooks(
+        suite.sender(),
+        ExecuteMsg::AddProposalHook {
+            address: "hook".to_string(),
+        },
+    );
+    is_error!(err, &ContractError::NotDao {}.to_string());
+
+    suite
+        .update_hooks(
+            &dao,
+            ExecuteMsg::AddProposalHook {
+                address: "hook".to_string(),
+            },
+        )
+        .unwrap();
+    suite
+        .update_hooks(
+            &dao,
+            ExecuteMsg::AddVoteHook {
+                address: "hook".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.query_hooks(QueryMsg::ProposalHooks {}), vec!["hook"]);
+    assert_eq!(suite.query_hooks(QueryMsg::VoteHooks {}), vec!["hook"]);
+
+    // "hook" is not a contract, so the hooks fail and are removed.
+    suite.vote(suite.sender(), 1, vec![0, 1]).unwrap();
+    assert_eq!(
+        suite.query_hooks(QueryMsg::VoteHooks {}),
+        Vec::<String>::new()
+    );
+    suite
+        .propose(suite.sender(), vec![vec![unimportant_message()]])
+        .unwrap();
+    assert_eq!(
+        suite.query_hooks(QueryMsg::ProposalHooks {}),
+        Vec::<String>::new()
+    );
+
+    suite
+        .update_hooks(
+            &dao,
+            ExecuteMsg::AddVoteHook {
+                address: "hook".to_string(),
+            },
+        )
+        .unwrap();
+    suite
+        .update_hooks(
+            &dao,
+            ExecuteMsg::RemoveVoteHook {
+                address: "hook".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_hooks(QueryMsg::VoteHooks {}),
+        Vec::<String>::new()
+    );
+}
@@ -5,14 +5,15 @@ use dao_interface::{voting::InfoResponse, Admin, ModuleInstantiateInfo};
 use dao_testing::contracts::{
     cw4_group_contract, dao_core_contract, dao_voting_cw4_contract, proposal_condorcet_contract,
 };
-use dao_voting::threshold::PercentageThreshold;
+use dao_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold};
 
 use crate::{
     config::{Config, UncheckedConfig},
     contract::{CONTRACT_NAME, CONTRACT_VERSION},
     msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::{ProposalResponse, Status},
+    proposal::{ProposalResponse, Status, TallyResponse},
     tally::Winner,
+    vote::{VoteInfo, VoteListResponse, VoteResponse},
 };
 
 pub(crate) struct Suite {
@@ -31,11 +32,17 @@ pub(crate) struct SuiteBuilder {
 impl Default for SuiteBuilder {
     fn default() -> Self {
         Self {
-            instantiate: UncheckedConfig {
-                quorum: PercentageThreshold::Percent(Decimal::percent(15)),
-                voting_period: Duration::Time(60 * 60 * 24 * 7),
-                min_voting_period: Some(Duration::Time(60 * 60 * 24)),
-                close_proposals_on_execution_failure: true,
+            instantiate: InstantiateMsg {
+                config: UncheckedConfig {
+                    quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+                    voting_period: Duration::Time(60 * 60 * 24 * 7),
+                    min_voting_period: Some(Duration::Time(60 * 60 * 24)),
+                    close_proposals_on_execution_failure: true,
+                    completion: None,
+                    allow_revoting: false,
+                    voting_period_choice: None,
+                },
+                pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
             },
             with_proposal: None,
             with_voters: vec![("sender".to_string(), 10)],
@@ -45,9 +52,9 @@ impl Default for SuiteBuilder {
 
 impl SuiteBuilder {
     #[allow(clippy::field_reassign_with_default)]
-    pub fn with_config(instantiate: UncheckedConfig) -> Self {
+    pub fn with_config(config: UncheckedConfig) -> Self {
         let mut b = Self::default();
-        b.instantiate = instantiate;
+        b.instantiate.config = config;
         b
     }
 
@@ -199,6 +206,73 @@ impl Suite {
             .unwrap()
     }
 
+    pub fn query_list_proposals(&self, start_after: Option<u32>, reverse: bool) -> Vec<u32> {
+        let query = if reverse {
+            QueryMsg::ReverseProposals {
+                start_before: start_after,
+                limit: None,
+            }
+        } else {
+            QueryMsg::ListProposals {
+                start_after,
+                limit: None,
+            }
+        };
+        let proposals: Vec<ProposalResponse> = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.condorcet, &query)
+            .unwrap();
+        proposals.into_iter().map(|p| p.proposal.id).collect()
+    }
+
+    pub fn query_vote(&self, proposal_id: u32, voter: &str) -> Option<VoteInfo> {
+        let vote: VoteResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.condorcet,
+                &QueryMsg::GetVote {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap();
+        vote.vote
+    }
+
+    pub fn query_list_votes(&self, proposal_id: u32, start_after: Option<&str>) -> Vec<VoteInfo> {
+        let votes: VoteListResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.condorcet,
+                &QueryMsg::ListVotes {
+                    proposal_id,
+                    start_after: start_after.map(|s| s.to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        votes.votes
+    }
+
+    pub fn query_hooks(&self, query: QueryMsg) -> Vec<String> {
+        let hooks: cw_hooks::HooksResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.condorcet, &query)
+            .unwrap();
+        hooks.hooks
+    }
+
+    pub fn query_tally(&self, id: u32) -> TallyResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.condorcet, &QueryMsg::Tally { id })
+            .unwrap()
+    }
+
     pub fn query_winner_and_status(&self, id: u32) -> (Winner, Status) {
         let q = self.query_proposal(id);
         (q.tally.winner, q.proposal.last_status())
@@ -232,13 +306,34 @@ impl Suite {
         &mut self,
         sender: S,
         choices: Vec<Vec<CosmosMsg>>,
+    ) -> anyhow::Result<u32> {
+        self.propose_with_voting_period(sender, choices, None)
+    }
+
+    pub fn propose_with_voting_period<S: Into<String>>(
+        &mut self,
+        sender: S,
+        choices: Vec<Vec<CosmosMsg>>,
+        voting_period: Option<Duration>,
     ) -> anyhow::Result<u32> {
         let id = self.query_next_proposal_id();
         self.app.execute_contract(
             Addr::unchecked(sender),
             self.condorcet.clone(),
             &ExecuteMsg::Propose {
-                choices: choices.into_iter().map(|msgs| Choice { msgs }).collect(),
+                title: format!("proposal {id}"),
+                description: "a proposal".to_string(),
+                choices: choices
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, msgs)| Choice {
+                        title: format!("choice {i}"),
+                        description: "a choice".to_string(),
+                        msgs,
+                    })
+                    .collect(),
+                proposer: None,
+                voting_period,
             },
             &[],
         )?;
@@ -250,12 +345,45 @@ impl Suite {
         sender: S,
         proposal_id: u32,
         vote: Vec<u32>,
+    ) -> anyhow::Result<()> {
+        self.vote_with_rationale(sender, proposal_id, vote, None)
+    }
+
+    pub fn vote_with_rationale<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u32,
+        vote: Vec<u32>,
+        rationale: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.condorcet.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote,
+                    rationale: rationale.map(|r| r.to_string()),
+                },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn update_rationale<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u32,
+        rationale: Option<&str>,
     ) -> anyhow::Result<()> {
         self.app
             .execute_contract(
                 Addr::unchecked(sender),
                 self.condorcet.clone(),
-                &ExecuteMsg::Vote { proposal_id, vote },
+                &ExecuteMsg::UpdateRationale {
+                    proposal_id,
+                    rationale: rationale.map(|r| r.to_string()),
+                },
                 &[],
             )
             .map(|_| ())
@@ -272,6 +400,16 @@ impl Suite {
             .map(|_| ())
     }
 
+    pub fn update_hooks<S: Into<String>>(
+        &mut self,
+        sender: S,
+        msg: ExecuteMsg,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.condorcet.clone(), &msg, &[])
+            .map(|_| ())
+    }
+
     pub fn close<S: Into<String>>(&mut self, sender: S, proposal_id: u32) -> anyhow::Result<()> {
         self.app
             .execute_contract(
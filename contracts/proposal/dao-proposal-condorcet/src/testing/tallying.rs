@@ -2,6 +2,7 @@ use cosmwasm_std::Uint128;
 use cw_utils::Expiration;
 
 use crate::{
+    completion::Completion,
     tally::{Tally, Winner},
     vote::Vote,
 };
@@ -9,7 +10,14 @@ use crate::{
 #[test]
 fn test_pair_election() {
     let candidates = 2;
-    let mut tally = Tally::new(candidates, Uint128::new(3), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(3),
+        0,
+        Expiration::Never {},
+        None,
+        false,
+    );
 
     tally.add_vote(Vote::new(vec![0, 1], candidates).unwrap(), Uint128::one());
     tally.add_vote(Vote::new(vec![1, 0], candidates).unwrap(), Uint128::one());
@@ -21,7 +29,14 @@ fn test_pair_election() {
 #[test]
 fn test_triplet_election() {
     let candidates = 3;
-    let mut tally = Tally::new(candidates, Uint128::new(3), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(3),
+        0,
+        Expiration::Never {},
+        None,
+        false,
+    );
 
     tally.add_vote(
         Vote::new(vec![0, 1, 2], candidates).unwrap(),
@@ -45,7 +60,14 @@ fn test_triplet_election() {
 #[test]
 fn test_condorcet_paradox() {
     let candidates = 3;
-    let mut tally = Tally::new(candidates, Uint128::new(6), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(6),
+        0,
+        Expiration::Never {},
+        None,
+        false,
+    );
 
     tally.add_vote(
         Vote::new(vec![0, 2, 1], candidates).unwrap(),
@@ -96,7 +118,14 @@ fn test_condorcet_paradox() {
 #[test]
 fn test_tally_overflow() {
     let candidates = 6;
-    let mut tally = Tally::new(candidates, Uint128::MAX, 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::MAX,
+        0,
+        Expiration::Never {},
+        None,
+        false,
+    );
 
     tally.add_vote(
         Vote::new(vec![1, 2, 3, 4, 5, 0], candidates).unwrap(),
@@ -117,7 +146,14 @@ fn test_tally_overflow() {
 #[test]
 fn test_winner_none() {
     let candidates = 6;
-    let mut tally = Tally::new(candidates, Uint128::new(9), 0, Expiration::Never {});
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(9),
+        0,
+        Expiration::Never {},
+        None,
+        false,
+    );
 
     tally.add_vote(
         Vote::new(vec![1, 2, 3, 4, 5, 0], candidates).unwrap(),
@@ -151,3 +187,70 @@ fn test_winner_none() {
     // -2 -2  2  2  4  \
     assert_eq!(tally.winner, Winner::None)
 }
+
+#[test]
+fn test_condorcet_paradox_completed() {
+    let candidates = 3;
+    let mut tally = Tally::new(
+        candidates,
+        Uint128::new(6),
+        0,
+        Expiration::Never {},
+        Some(Completion::Schulze {}),
+        false,
+    );
+
+    for vote in [
+        vec![0, 2, 1],
+        vec![1, 0, 2],
+        vec![2, 1, 0],
+        vec![1, 0, 2],
+        vec![0, 2, 1],
+        vec![2, 0, 1],
+    ] {
+        tally.add_vote(Vote::new(vote, candidates).unwrap(), Uint128::one());
+    }
+
+    // the same ballots as `test_condorcet_paradox`. 0 ties 1 head to
+    // head, but beats it through 2 with a strength of 2.
+    assert_eq!(tally.winner, Winner::Completed(0))
+}
+
+#[test]
+fn test_revote() {
+    let candidates = 4;
+    let new_tally = || {
+        Tally::new(
+            candidates,
+            Uint128::new(7),
+            0,
+            Expiration::Never {},
+            None,
+            true,
+        )
+    };
+    let vote = |v: &[u32]| Vote::new(v.to_vec(), candidates).unwrap();
+
+    let mut tally = new_tally();
+    tally.add_vote(vote(&[0, 1, 2, 3]), Uint128::new(4));
+    tally.add_vote(vote(&[3, 2, 1, 0]), Uint128::new(2));
+    // 0 beats every other candidate by more than the outstanding
+    // power, but as votes may change it is not undisputed.
+    assert_eq!(tally.winner, Winner::Some(0));
+
+    tally.revote(vote(&[0, 1, 2, 3]), vote(&[2, 3, 0, 1]), Uint128::new(4));
+    assert_eq!(tally.winner, Winner::Some(2));
+
+    // revoting is the same as having voted that way to begin with.
+    let mut expected = new_tally();
+    expected.add_vote(vote(&[2, 3, 0, 1]), Uint128::new(4));
+    expected.add_vote(vote(&[3, 2, 1, 0]), Uint128::new(2));
+    assert_eq!(tally, expected);
+
+    // undoing a revote restores the tally.
+    tally.revote(vote(&[2, 3, 0, 1]), vote(&[0, 1, 2, 3]), Uint128::new(4));
+    let mut expected = new_tally();
+    expected.add_vote(vote(&[0, 1, 2, 3]), Uint128::new(4));
+    expected.add_vote(vote(&[3, 2, 1, 0]), Uint128::new(2));
+    assert_eq!(tally, expected);
+}
@@ -1,11 +1,50 @@
-use std::ops::Index;
+use std::{fmt, ops::Index};
 
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
 use thiserror::Error;
 
 #[cw_serde]
 pub struct Vote(Vec<u32>);
 
+/// A vote and the voting power it was cast with.
+#[cw_serde]
+pub struct Ballot {
+    pub vote: Vote,
+    pub power: Uint128,
+    #[serde(default)]
+    pub rationale: Option<String>,
+}
+
+#[cw_serde]
+pub struct VoteInfo {
+    pub voter: Addr,
+    pub vote: Vote,
+    pub power: Uint128,
+    pub rationale: Option<String>,
+}
+
+impl VoteInfo {
+    pub(crate) fn new(voter: Addr, ballot: Ballot) -> Self {
+        Self {
+            voter,
+            vote: ballot.vote,
+            power: ballot.power,
+            rationale: ballot.rationale,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct VoteResponse {
+    pub vote: Option<VoteInfo>,
+}
+
+#[cw_serde]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteInfo>,
+}
+
 impl Vote {
     pub(crate) fn new(vote: Vec<u32>, candidates: u32) -> Result<Self, VoteError> {
         if vote.len() != candidates as usize {
@@ -32,6 +71,15 @@ impl Vote {
     }
 }
 
+/// Formats the vote as its candidates separated by commas, from most
+/// to least favored. This is the vote sent to vote hooks.
+impl fmt::Display for Vote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let candidates: Vec<String> = self.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", candidates.join(","))
+    }
+}
+
 impl Index<usize> for Vote {
     type Output = u32;
 
@@ -78,6 +126,7 @@ mod tests {
     #[test]
     fn test_vote_construction() {
         let vote = Vote::new(vec![0, 1, 2], 3).unwrap();
-        assert_eq!(vote.0, vec![0, 1, 2])
+        assert_eq!(vote.0, vec![0, 1, 2]);
+        assert_eq!(vote.to_string(), "0,1,2")
     }
 }
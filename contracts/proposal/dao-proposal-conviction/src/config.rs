@@ -0,0 +1,61 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Deps};
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_utils::Duration;
+
+use crate::ContractError;
+
+#[cw_serde]
+pub struct UncheckedConfig {
+    /// The denomination of funds that proposals may request from
+    /// the DAO's treasury.
+    pub denom: UncheckedDenom,
+    /// The share of a proposal's conviction that remains after each
+    /// conviction period. Must be greater than zero and less than
+    /// one.
+    pub decay: Decimal,
+    /// The maximum share of the DAO's treasury that a proposal may
+    /// request. Must be greater than zero and at most one.
+    pub max_ratio: Decimal,
+    /// Scales the conviction proposals need to pass. Must be
+    /// non-zero.
+    pub weight: Decimal,
+    /// The amount of time between conviction updates.
+    pub conviction_period: Duration,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub denom: CheckedDenom,
+    pub decay: Decimal,
+    pub max_ratio: Decimal,
+    pub weight: Decimal,
+    pub conviction_period: Duration,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+impl UncheckedConfig {
+    pub(crate) fn into_checked(self, deps: Deps) -> Result<Config, ContractError> {
+        if self.decay.is_zero() || self.decay >= Decimal::one() {
+            return Err(ContractError::InvalidDecay {});
+        }
+        if self.max_ratio.is_zero() || self.max_ratio > Decimal::one() {
+            return Err(ContractError::InvalidMaxRatio {});
+        }
+        if self.weight.is_zero() {
+            return Err(ContractError::ZeroWeight {});
+        }
+        if let Duration::Height(0) | Duration::Time(0) = self.conviction_period {
+            return Err(ContractError::ZeroConvictionPeriod {});
+        }
+        Ok(Config {
+            denom: self.denom.into_checked(deps)?,
+            decay: self.decay,
+            max_ratio: self.max_ratio,
+            weight: self.weight,
+            conviction_period: self.conviction_period,
+            close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+        })
+    }
+}
@@ -0,0 +1,471 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult,
+    Uint128,
+};
+
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use dao_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::reply::TaggedReplyId;
+use dao_voting::status::Status;
+use dao_voting::voting::{get_total_power, get_voting_power};
+
+use crate::config::UncheckedConfig;
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::proposal::{Proposal, ProposalResponse};
+use crate::state::{
+    next_proposal_id, CONFIG, DAO, MAX_STAKES, PROPOSAL, PROPOSAL_HOOKS, STAKE, STAKED,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = msg.into_checked(deps.as_ref())?;
+    DAO.save(deps.storage, &info.sender)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            beneficiary,
+            amount,
+        } => execute_propose(deps, env, info, title, description, beneficiary, amount),
+        ExecuteMsg::Stake {
+            proposal_id,
+            amount,
+        } => execute_stake(deps, env, info, proposal_id, amount),
+        ExecuteMsg::Unstake {
+            proposal_id,
+            amount,
+        } => execute_unstake(deps, env, info, proposal_id, amount),
+        ExecuteMsg::Sync {
+            voter,
+            start_after,
+            limit,
+        } => execute_sync(deps, env, info, voter, start_after, limit),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Cancel { proposal_id } => execute_cancel(deps, env, info, proposal_id),
+
+        ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+        ExecuteMsg::AddProposalHook { address } => execute_add_proposal_hook(deps, info, address),
+        ExecuteMsg::RemoveProposalHook { address } => {
+            execute_remove_proposal_hook(deps, info, address)
+        }
+    }
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    beneficiary: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    let sender_voting_power = get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
+    if sender_voting_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+
+    let id = next_proposal_id(deps.storage)?;
+    let proposal = Proposal::new(
+        &env.block,
+        &config,
+        id,
+        title,
+        description,
+        info.sender,
+        beneficiary,
+        amount,
+    );
+    PROPOSAL.save(deps.storage, id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposal.proposer.as_str())?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("method", "propose")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", proposal.proposer)
+        .add_attribute("beneficiary", proposal.beneficiary)
+        .add_attribute("amount", proposal.amount))
+}
+
+fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroStake {});
+    }
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if proposal.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+
+    let power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &DAO.load(deps.storage)?,
+        None,
+    )?;
+    let staked = STAKED
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if staked + amount > power {
+        return Err(ContractError::InsufficientPower {
+            available: power.saturating_sub(staked),
+        });
+    }
+
+    let stake = STAKE.may_load(deps.storage, (&info.sender, proposal_id))?;
+    if stake.is_none()
+        && STAKE
+            .prefix(&info.sender)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(MAX_STAKES)
+            .count()
+            == MAX_STAKES
+    {
+        return Err(ContractError::TooManyStakes { max: MAX_STAKES });
+    }
+
+    STAKED.save(deps.storage, &info.sender, &(staked + amount))?;
+    STAKE.save(
+        deps.storage,
+        (&info.sender, proposal_id),
+        &(stake.unwrap_or_default() + amount),
+    )?;
+    proposal.stake(&env.block, amount);
+    PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "stake")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Removes AMOUNT of VOTER's stake from PROPOSAL_ID.
+fn unstake(
+    deps: DepsMut,
+    env: &Env,
+    voter: &Addr,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let stake = STAKE
+        .may_load(deps.storage, (voter, proposal_id))?
+        .unwrap_or_default();
+    if amount > stake {
+        return Err(ContractError::InsufficientStake { staked: stake });
+    }
+    if amount == stake {
+        STAKE.remove(deps.storage, (voter, proposal_id));
+    } else {
+        STAKE.save(deps.storage, (voter, proposal_id), &(stake - amount))?;
+    }
+    STAKED.update(deps.storage, voter, |staked| -> StdResult<_> {
+        Ok(staked.unwrap_or_default() - amount)
+    })?;
+
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    proposal.unstake(&env.block, amount);
+    PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+    Ok(())
+}
+
+fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroStake {});
+    }
+    unstake(deps, &env, &info.sender, proposal_id, amount)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "unstake")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_sync(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let power = get_voting_power(deps.as_ref(), voter.clone(), &DAO.load(deps.storage)?, None)?;
+    let staked = STAKED.may_load(deps.storage, &voter)?.unwrap_or_default();
+    if staked <= power {
+        return Err(ContractError::Synced {});
+    }
+
+    let stakes = STAKE
+        .prefix(&voter)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut unstaked = Uint128::zero();
+    for (proposal_id, amount) in stakes {
+        unstake(deps.branch(), &env, &voter, proposal_id, amount)?;
+        unstaked += amount;
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "sync")
+        .add_attribute("voter", voter)
+        .add_attribute("unstaked", unstaked)
+        .add_attribute("syncer", info.sender))
+}
+
+fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if proposal.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+    let dao = DAO.load(deps.storage)?;
+
+    proposal.update_conviction(&env.block);
+    let funds = proposal.denom.query_balance(&deps.querier, &dao)?;
+    let total_power = get_total_power(deps.as_ref(), &dao, None)?;
+    match proposal.threshold(funds, total_power) {
+        Some(threshold) if proposal.conviction >= threshold => {
+            let old_status = proposal.status;
+            let msg = proposal.set_executed(dao)?;
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+            let hooks = proposal_status_changed_hooks(
+                PROPOSAL_HOOKS,
+                deps.storage,
+                proposal_id,
+                old_status.to_string(),
+                proposal.status.to_string(),
+            )?;
+
+            Ok(Response::default()
+                .add_submessage(msg)
+                .add_submessages(hooks)
+                .add_attribute("method", "execute")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("executor", info.sender))
+        }
+        _ => Err(ContractError::InsufficientConviction {}),
+    }
+}
+
+fn execute_cancel(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if info.sender != proposal.proposer && info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotProposer {});
+    }
+    if proposal.status != Status::Open {
+        return Err(ContractError::NotOpen {});
+    }
+
+    let old_status = proposal.status;
+    proposal.set_closed();
+    PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        proposal.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("method", "cancel")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("canceller", info.sender))
+}
+
+fn execute_set_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: UncheckedConfig,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        Err(ContractError::NotDao {})
+    } else {
+        let config = config.into_checked(deps.as_ref())?;
+        CONFIG.save(deps.storage, &config)?;
+        Ok(Response::default()
+            .add_attribute("method", "update_config")
+            .add_attribute("updater", info.sender))
+    }
+}
+
+fn execute_add_proposal_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    PROPOSAL_HOOKS.add_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", "add_proposal_hook")
+        .add_attribute("address", address))
+}
+
+fn execute_remove_proposal_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)? {
+        return Err(ContractError::NotDao {});
+    }
+    let address = deps.api.addr_validate(&address)?;
+    PROPOSAL_HOOKS.remove_hook(deps.storage, address.clone())?;
+
+    Ok(Response::default()
+        .add_attribute("method", "remove_proposal_hook")
+        .add_attribute("address", address))
+}
+
+fn proposal_response(deps: Deps, env: &Env, proposal: Proposal) -> StdResult<ProposalResponse> {
+    let conviction = proposal.conviction(&env.block);
+    let threshold = if proposal.status == Status::Open {
+        let dao = DAO.load(deps.storage)?;
+        let funds = proposal.denom.query_balance(&deps.querier, &dao)?;
+        let total_power = get_total_power(deps, &dao, None)?;
+        proposal.threshold(funds, total_power)
+    } else {
+        None
+    };
+    Ok(ProposalResponse {
+        proposal,
+        conviction,
+        threshold,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proposal { id } => {
+            let proposal = PROPOSAL.load(deps.storage, id)?;
+            to_binary(&proposal_response(deps, &env, proposal)?)
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let proposals = PROPOSAL
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+                .map(|item| item.and_then(|(_, proposal)| proposal_response(deps, &env, proposal)))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_binary(&proposals)
+        }
+        QueryMsg::Stake { proposal_id, voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_binary(
+                &STAKE
+                    .may_load(deps.storage, (&voter, proposal_id))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::Staked { voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_binary(&STAKED.may_load(deps.storage, &voter)?.unwrap_or_default())
+        }
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+            proposal.set_execution_failed();
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+            Ok(Response::default()
+                .add_attribute("proposal_execution_failed", proposal_id.to_string()))
+        }
+        TaggedReplyId::FailedProposalHook(idx) => {
+            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::default().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+        }
+        // This module has no pre-propose or vote hooks, so no other
+        // submessage it sends can reply.
+        _ => Err(ContractError::InvalidReplyId { id: msg.id }),
+    }
+}
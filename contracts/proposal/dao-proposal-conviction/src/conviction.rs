@@ -0,0 +1,167 @@
+//! Conviction accumulation and passing thresholds.
+//!
+//! Conviction follows the discrete decay curve
+//!
+//! ```text
+//! y(t + 1) = a * y(t) + x
+//! ```
+//!
+//! where `a` is the decay rate and `x` is the amount of voting power
+//! staked on a proposal during the period. Held constant for `t`
+//! periods this has the closed form
+//!
+//! ```text
+//! y(t) = y(0) * a^t + x * (1 - a^t) / (1 - a)
+//! ```
+//!
+//! which approaches `x / (1 - a)` as `t` grows. A proposal requesting
+//! `r` tokens from a treasury holding `R` tokens passes once its
+//! conviction exceeds
+//!
+//! ```text
+//! threshold = (p * S) / ((1 - a) * (b - r / R)^2)
+//! ```
+//!
+//! where `S` is the DAO's total voting power, `b` is the maximum share
+//! of the treasury that may be requested, and `p` is a weight
+//! controlling how much support is needed. Requests for `b` or more
+//! of the treasury can never pass.
+
+use cosmwasm_std::{Decimal, Decimal256, Uint128, Uint256};
+
+fn to_decimal256(amount: Uint128) -> Decimal256 {
+    Decimal256::from_ratio(Uint256::from(amount), Uint256::one())
+}
+
+/// Returns the conviction of a proposal with CONVICTION conviction
+/// and STAKED voting power staked on it after PERIODS periods have
+/// elapsed.
+pub fn decay(conviction: Decimal256, staked: Uint128, decay: Decimal, periods: u64) -> Decimal256 {
+    if periods == 0 {
+        return conviction;
+    }
+    let decay = Decimal256::from(decay);
+    // Decay is less than one so a^t tends to zero. Saturating the
+    // exponent only removes precision that has already been lost.
+    let decayed = decay
+        .checked_pow(u32::try_from(periods).unwrap_or(u32::MAX))
+        .unwrap_or_default();
+    conviction * decayed
+        + to_decimal256(staked) * (Decimal256::one() - decayed) / (Decimal256::one() - decay)
+}
+
+/// Returns the conviction a proposal requesting REQUESTED of FUNDS
+/// must reach to pass, or None if the proposal can not pass.
+pub fn threshold(
+    requested: Uint128,
+    funds: Uint128,
+    total_power: Uint128,
+    decay: Decimal,
+    max_ratio: Decimal,
+    weight: Decimal,
+) -> Option<Decimal256> {
+    if funds.is_zero() || requested >= funds {
+        return None;
+    }
+    let ratio = Decimal256::from_ratio(requested, funds);
+    let max_ratio = Decimal256::from(max_ratio);
+    if ratio >= max_ratio {
+        return None;
+    }
+    let distance = max_ratio - ratio;
+    let denominator = (Decimal256::one() - Decimal256::from(decay)) * distance * distance;
+    Some(Decimal256::from(weight) * to_decimal256(total_power) / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay() {
+        let a = Decimal::percent(50);
+        let zero = Decimal256::zero();
+
+        assert_eq!(decay(zero, Uint128::new(10), a, 0), zero);
+        // y(1) = 0.5 * 0 + 10
+        assert_eq!(
+            decay(zero, Uint128::new(10), a, 1),
+            Decimal256::from_ratio(10u128, 1u128)
+        );
+        // y(2) = 0.5 * 10 + 10
+        assert_eq!(
+            decay(zero, Uint128::new(10), a, 2),
+            Decimal256::from_ratio(15u128, 1u128)
+        );
+        // stepping is the same as jumping.
+        let one = decay(zero, Uint128::new(10), a, 1);
+        assert_eq!(
+            decay(one, Uint128::new(10), a, 1),
+            decay(zero, Uint128::new(10), a, 2)
+        );
+        // conviction approaches x / (1 - a) = 20.
+        assert_eq!(
+            decay(zero, Uint128::new(10), a, u64::MAX),
+            Decimal256::from_ratio(20u128, 1u128)
+        );
+        // without stake conviction decays to zero.
+        assert_eq!(
+            decay(Decimal256::from_ratio(20u128, 1u128), Uint128::zero(), a, 1),
+            Decimal256::from_ratio(10u128, 1u128)
+        );
+    }
+
+    #[test]
+    fn test_threshold() {
+        let a = Decimal::percent(50);
+        let b = Decimal::percent(20);
+        let p = Decimal::percent(1);
+
+        // (0.01 * 100) / (0.5 * (0.2 - 0.1)^2) = 200
+        assert_eq!(
+            threshold(
+                Uint128::new(10),
+                Uint128::new(100),
+                Uint128::new(100),
+                a,
+                b,
+                p
+            ),
+            Some(Decimal256::from_ratio(200u128, 1u128))
+        );
+        // requesting more of the treasury requires more conviction.
+        assert!(
+            threshold(
+                Uint128::new(15),
+                Uint128::new(100),
+                Uint128::new(100),
+                a,
+                b,
+                p
+            ) > threshold(
+                Uint128::new(10),
+                Uint128::new(100),
+                Uint128::new(100),
+                a,
+                b,
+                p
+            )
+        );
+        // requests for the max ratio or more can not pass.
+        assert_eq!(
+            threshold(
+                Uint128::new(20),
+                Uint128::new(100),
+                Uint128::new(100),
+                a,
+                b,
+                p
+            ),
+            None
+        );
+        assert_eq!(
+            threshold(Uint128::new(1), Uint128::zero(), Uint128::new(100), a, b, p),
+            None
+        );
+    }
+}
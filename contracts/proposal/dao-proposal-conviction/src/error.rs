@@ -0,0 +1,65 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_denom::DenomError;
+use cw_hooks::HookError;
+use dao_voting::reply::error::TagError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+    #[error(transparent)]
+    Denom(#[from] DenomError),
+    #[error(transparent)]
+    Hook(#[from] HookError),
+
+    #[error("non-zero voting power required to perform this action")]
+    ZeroVotingPower {},
+
+    #[error("proposals must request a non-zero amount")]
+    ZeroAmount {},
+
+    #[error("stake amount must be non-zero")]
+    ZeroStake {},
+
+    #[error("only the DAO may perform this action")]
+    NotDao {},
+
+    #[error("only the proposer or the DAO may cancel a proposal")]
+    NotProposer {},
+
+    #[error("only open proposals may be staked on, executed, or cancelled")]
+    NotOpen {},
+
+    #[error("insufficient voting power, ({available}) available to stake")]
+    InsufficientPower { available: Uint128 },
+
+    #[error("can not unstake more than is staked, ({staked}) staked")]
+    InsufficientStake { staked: Uint128 },
+
+    #[error("proposal conviction has not reached its threshold")]
+    InsufficientConviction {},
+
+    #[error("voters may have stake on at most ({max}) proposals at once")]
+    TooManyStakes { max: usize },
+
+    #[error("voter's voting power covers their stake")]
+    Synced {},
+
+    #[error("decay must be greater than zero and less than one")]
+    InvalidDecay {},
+
+    #[error("max ratio must be greater than zero and at most one")]
+    InvalidMaxRatio {},
+
+    #[error("weight must be non-zero")]
+    ZeroWeight {},
+
+    #[error("conviction period must be non-zero")]
+    ZeroConvictionPeriod {},
+
+    #[error("received unexpected reply ({id})")]
+    InvalidReplyId { id: u64 },
+}
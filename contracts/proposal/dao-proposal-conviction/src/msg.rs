@@ -0,0 +1,71 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use dao_macros::proposal_module_query;
+
+use crate::config::UncheckedConfig;
+
+pub type InstantiateMsg = UncheckedConfig;
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a proposal requesting AMOUNT of the module's denom be
+    /// sent from the DAO's treasury to BENEFICIARY.
+    Propose {
+        title: String,
+        description: String,
+        beneficiary: String,
+        amount: Uint128,
+    },
+    /// Stakes AMOUNT of the sender's voting power on an open
+    /// proposal.
+    Stake { proposal_id: u64, amount: Uint128 },
+    /// Removes AMOUNT of the sender's stake from a proposal.
+    Unstake { proposal_id: u64, amount: Uint128 },
+    /// Removes VOTER's stake from up to LIMIT of the proposals they
+    /// have staked on, starting after START_AFTER, if their voting
+    /// power has fallen below their staked total.
+    Sync {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Executes a proposal whose conviction has reached its
+    /// threshold.
+    Execute { proposal_id: u64 },
+    /// Closes an open proposal. Only the proposer or the DAO may
+    /// call this method.
+    Cancel { proposal_id: u64 },
+    /// Updates the module's config. Proposals keep the config they
+    /// were created with. Only the DAO may call this method.
+    SetConfig(UncheckedConfig),
+    /// Adds an address as a consumer of proposal hooks. Only the DAO
+    /// may call this method.
+    AddProposalHook { address: String },
+    /// Removes a consumer of proposal hooks. Only the DAO may call
+    /// this method.
+    RemoveProposalHook { address: String },
+}
+
+#[proposal_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::proposal::ProposalResponse)]
+    Proposal { id: u64 },
+    #[returns(Vec<crate::proposal::ProposalResponse>)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Returns the amount of voting power VOTER has staked on a
+    /// proposal.
+    #[returns(cosmwasm_std::Uint128)]
+    Stake { proposal_id: u64, voter: String },
+    /// Returns the total amount of voting power VOTER has staked.
+    #[returns(cosmwasm_std::Uint128)]
+    Staked { voter: String },
+    #[returns(crate::config::Config)]
+    Config {},
+    #[returns(cw_hooks::HooksResponse)]
+    ProposalHooks {},
+}
@@ -0,0 +1,175 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, BlockInfo, Decimal, Decimal256, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_denom::CheckedDenom;
+use cw_utils::Duration;
+use dao_voting::{reply::mask_proposal_execution_proposal_id, status::Status};
+
+use crate::{config::Config, conviction};
+
+#[cw_serde]
+pub struct Proposal {
+    pub id: u64,
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+    /// The address that will receive `amount` if the proposal is
+    /// executed.
+    pub beneficiary: Addr,
+    pub amount: Uint128,
+    pub status: Status,
+
+    pub denom: CheckedDenom,
+    pub decay: Decimal,
+    pub max_ratio: Decimal,
+    pub weight: Decimal,
+    pub conviction_period: Duration,
+    pub close_on_execution_failure: bool,
+
+    /// The total amount of voting power staked on the proposal.
+    pub staked: Uint128,
+    /// The proposal's conviction as of `last_period`.
+    pub conviction: Decimal256,
+    /// The conviction period during which `conviction` was last
+    /// updated.
+    pub last_period: u64,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub proposal: Proposal,
+    /// The proposal's current conviction.
+    pub conviction: Decimal256,
+    /// The conviction the proposal must reach to be executed, or
+    /// `None` if it requests too large a share of the DAO's
+    /// treasury to pass.
+    pub threshold: Option<Decimal256>,
+}
+
+/// Returns the conviction period that BLOCK falls in.
+fn period(block: &BlockInfo, conviction_period: Duration) -> u64 {
+    match conviction_period {
+        Duration::Height(h) => block.height / h,
+        Duration::Time(t) => block.time.seconds() / t,
+    }
+}
+
+impl Proposal {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        block: &BlockInfo,
+        config: &Config,
+        id: u64,
+        title: String,
+        description: String,
+        proposer: Addr,
+        beneficiary: Addr,
+        amount: Uint128,
+    ) -> Self {
+        Self {
+            id,
+            title,
+            description,
+            proposer,
+            beneficiary,
+            amount,
+            status: Status::Open,
+
+            denom: config.denom.clone(),
+            decay: config.decay,
+            max_ratio: config.max_ratio,
+            weight: config.weight,
+            conviction_period: config.conviction_period,
+            close_on_execution_failure: config.close_proposals_on_execution_failure,
+
+            staked: Uint128::zero(),
+            conviction: Decimal256::zero(),
+            last_period: period(block, config.conviction_period),
+        }
+    }
+
+    /// Returns the proposal's conviction as of BLOCK.
+    pub fn conviction(&self, block: &BlockInfo) -> Decimal256 {
+        let now = period(block, self.conviction_period);
+        conviction::decay(
+            self.conviction,
+            self.staked,
+            self.decay,
+            now.saturating_sub(self.last_period),
+        )
+    }
+
+    /// Returns the conviction the proposal must reach to be executed
+    /// given the DAO's treasury holds FUNDS and has TOTAL_POWER
+    /// voting power.
+    pub fn threshold(&self, funds: Uint128, total_power: Uint128) -> Option<Decimal256> {
+        conviction::threshold(
+            self.amount,
+            funds,
+            total_power,
+            self.decay,
+            self.max_ratio,
+            self.weight,
+        )
+    }
+
+    /// Brings the proposal's conviction up to date. This must be
+    /// called before the proposal's stake is changed so that the
+    /// previous stake is accounted for over the elapsed periods.
+    pub(crate) fn update_conviction(&mut self, block: &BlockInfo) {
+        self.conviction = self.conviction(block);
+        self.last_period = period(block, self.conviction_period);
+    }
+
+    pub(crate) fn stake(&mut self, block: &BlockInfo, amount: Uint128) {
+        debug_assert_eq!(self.status, Status::Open);
+
+        self.update_conviction(block);
+        self.staked += amount;
+    }
+
+    pub(crate) fn unstake(&mut self, block: &BlockInfo, amount: Uint128) {
+        if self.status == Status::Open {
+            self.update_conviction(block);
+        }
+        self.staked -= amount;
+    }
+
+    pub(crate) fn set_closed(&mut self) {
+        debug_assert_eq!(self.status, Status::Open);
+
+        self.status = Status::Closed;
+    }
+
+    /// Sets the proposal's status to executed and returns a
+    /// submessage transferring the requested funds from DAO to the
+    /// beneficiary.
+    pub(crate) fn set_executed(&mut self, dao: Addr) -> StdResult<SubMsg> {
+        debug_assert_eq!(self.status, Status::Open);
+
+        self.status = Status::Executed;
+
+        let core_exec = WasmMsg::Execute {
+            contract_addr: dao.into_string(),
+            msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![self
+                    .denom
+                    .get_transfer_to_message(&self.beneficiary, self.amount)?],
+            })?,
+            funds: vec![],
+        };
+        Ok(if self.close_on_execution_failure {
+            let masked_id = mask_proposal_execution_proposal_id(self.id);
+            SubMsg::reply_on_error(core_exec, masked_id)
+        } else {
+            SubMsg::new(core_exec)
+        })
+    }
+
+    pub(crate) fn set_execution_failed(&mut self) {
+        debug_assert_eq!(self.status, Status::Executed);
+
+        self.status = Status::ExecutionFailed;
+    }
+}
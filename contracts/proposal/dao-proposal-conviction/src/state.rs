@@ -0,0 +1,30 @@
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_hooks::Hooks;
+use cw_storage_plus::{Item, Map};
+
+use crate::{config::Config, proposal::Proposal};
+
+pub(crate) const DAO: Item<Addr> = Item::new("dao");
+pub(crate) const CONFIG: Item<Config> = Item::new("config");
+
+pub(crate) const PROPOSAL: Map<u64, Proposal> = Map::new("proposals");
+/// The amount of voting power each voter has staked on each
+/// proposal.
+pub(crate) const STAKE: Map<(&Addr, u64), Uint128> = Map::new("stakes");
+/// The total amount of voting power each voter has staked, including
+/// stake that remains on proposals that are no longer open.
+pub(crate) const STAKED: Map<&Addr, Uint128> = Map::new("staked");
+
+/// The maximum number of proposals a voter may have stake on at
+/// once, which bounds the work done by `ExecuteMsg::Sync`.
+pub const MAX_STAKES: usize = 64;
+
+pub(crate) const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+
+pub(crate) fn next_proposal_id(storage: &dyn Storage) -> StdResult<u64> {
+    PROPOSAL
+        .keys(storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()
+        .map(|id| id.unwrap_or(0) + 1)
+}
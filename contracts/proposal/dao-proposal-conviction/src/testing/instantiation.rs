@@ -0,0 +1,84 @@
+use cosmwasm_std::Decimal;
+use cw_utils::Duration;
+
+use crate::{config::UncheckedConfig, ContractError};
+
+use super::{is_error, suite::SuiteBuilder};
+
+fn config() -> UncheckedConfig {
+    SuiteBuilder::default().instantiate
+}
+
+#[test]
+fn test_instantiate_conviction() {
+    let suite = SuiteBuilder::default().build();
+    let config = suite.query_config();
+    assert_eq!(config.decay, Decimal::percent(50));
+    assert_eq!(config.max_ratio, Decimal::percent(20));
+    assert!(config.close_proposals_on_execution_failure);
+}
+
+#[test]
+#[should_panic(expected = "decay must be greater than zero and less than one")]
+fn test_instantiate_invalid_decay() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        decay: Decimal::one(),
+        ..config()
+    })
+    .build();
+}
+
+#[test]
+#[should_panic(expected = "max ratio must be greater than zero and at most one")]
+fn test_instantiate_invalid_max_ratio() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        max_ratio: Decimal::zero(),
+        ..config()
+    })
+    .build();
+}
+
+#[test]
+#[should_panic(expected = "weight must be non-zero")]
+fn test_instantiate_zero_weight() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        weight: Decimal::zero(),
+        ..config()
+    })
+    .build();
+}
+
+#[test]
+#[should_panic(expected = "conviction period must be non-zero")]
+fn test_instantiate_zero_conviction_period() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        conviction_period: Duration::Height(0),
+        ..config()
+    })
+    .build();
+}
+
+#[test]
+fn test_set_config() {
+    let mut suite = SuiteBuilder::default().build();
+
+    let err = suite.set_config(
+        suite.sender(),
+        UncheckedConfig {
+            decay: Decimal::percent(90),
+            ..config()
+        },
+    );
+    is_error!(err, &ContractError::NotDao {}.to_string());
+
+    suite
+        .set_config(
+            suite.core.clone(),
+            UncheckedConfig {
+                decay: Decimal::percent(90),
+                ..config()
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.query_config().decay, Decimal::percent(90));
+}
@@ -0,0 +1,315 @@
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env},
+    Decimal256, Reply, SubMsgResult, Uint128,
+};
+use dao_voting::status::Status;
+
+use crate::{contract::reply, state::MAX_STAKES, ContractError};
+
+use super::{is_error, suite::SuiteBuilder};
+
+fn decimal(n: u128) -> Decimal256 {
+    Decimal256::from_ratio(n, 1u128)
+}
+
+#[test]
+fn test_make_proposal() {
+    let mut suite = SuiteBuilder::default().build();
+    let id = suite.propose(suite.sender(), "beneficiary", 50).unwrap();
+    let response = suite.query_proposal(id);
+    let proposal = response.proposal.clone();
+
+    assert_eq!(proposal.id, id);
+    assert_eq!(proposal.proposer, suite.sender());
+    assert_eq!(proposal.beneficiary.as_str(), "beneficiary");
+    assert_eq!(proposal.amount, Uint128::new(50));
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(response.conviction, Decimal256::zero());
+    assert!(response.threshold.is_some());
+
+    let proposals = suite.query_list_proposals(None, None);
+    assert_eq!(proposals, vec![response]);
+}
+
+#[test]
+fn test_no_propose_zero_voting_power_or_amount() {
+    let mut suite = SuiteBuilder::default().build();
+    let err = suite.propose("someone", "beneficiary", 50);
+    is_error!(err, &ContractError::ZeroVotingPower {}.to_string());
+    let err = suite.propose(suite.sender(), "beneficiary", 0);
+    is_error!(err, &ContractError::ZeroAmount {}.to_string());
+}
+
+// requesting 50 of a 1000 token treasury with 100 total voting power
+// requires (0.01 * 100) / (0.5 * (0.2 - 0.05)^2) ~= 88.9 conviction.
+#[test]
+fn test_conviction_grows_until_executable() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let id = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", id, 50).unwrap();
+
+    // conviction accumulates 50, 75, 87.5, 93.75.
+    suite.a_day_passes();
+    assert_eq!(suite.query_conviction(id), decimal(50));
+    suite.a_day_passes();
+    assert_eq!(suite.query_conviction(id), decimal(75));
+    suite.a_day_passes();
+    let err = suite.execute("blue", id);
+    is_error!(err, &ContractError::InsufficientConviction {}.to_string());
+
+    suite.a_day_passes();
+    suite.execute("blue", id).unwrap();
+    assert_eq!(suite.query_balance("beneficiary"), Uint128::new(50));
+    assert_eq!(suite.query_balance(suite.core.as_str()), Uint128::new(950));
+
+    let response = suite.query_proposal(id);
+    assert_eq!(response.proposal.status, Status::Executed);
+    assert_eq!(response.threshold, None);
+
+    let err = suite.execute("blue", id);
+    is_error!(err, &ContractError::NotOpen {}.to_string());
+    let err = suite.stake("blue", id, 10);
+    is_error!(err, &ContractError::NotOpen {}.to_string());
+}
+
+#[test]
+fn test_more_stake_passes_sooner() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let id = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", id, 50).unwrap();
+    suite.stake("blue", id, 50).unwrap();
+
+    suite.a_day_passes();
+    suite.execute("ekez", id).unwrap();
+    assert_eq!(suite.query_balance("beneficiary"), Uint128::new(50));
+}
+
+#[test]
+fn test_conviction_decays_after_unstake() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let id = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", id, 50).unwrap();
+    suite.a_day_passes();
+    suite.a_day_passes();
+    assert_eq!(suite.query_conviction(id), decimal(75));
+
+    let err = suite.unstake("ekez", id, 51);
+    is_error!(
+        err,
+        &ContractError::InsufficientStake {
+            staked: Uint128::new(50)
+        }
+        .to_string()
+    );
+
+    suite.unstake("ekez", id, 50).unwrap();
+    assert_eq!(suite.query_stake(id, "ekez"), Uint128::zero());
+    assert_eq!(suite.query_staked("ekez"), Uint128::zero());
+
+    suite.a_day_passes();
+    assert_eq!(
+        suite.query_conviction(id),
+        Decimal256::from_ratio(75u128, 2u128)
+    );
+}
+
+#[test]
+fn test_stake_limited_by_voting_power() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let one = suite.propose("ekez", "beneficiary", 50).unwrap();
+    let two = suite.propose("ekez", "beneficiary", 50).unwrap();
+
+    let err = suite.stake("ekez", one, 51);
+    is_error!(
+        err,
+        &ContractError::InsufficientPower {
+            available: Uint128::new(50)
+        }
+        .to_string()
+    );
+    let err = suite.stake("someone", one, 1);
+    is_error!(
+        err,
+        &ContractError::InsufficientPower {
+            available: Uint128::zero()
+        }
+        .to_string()
+    );
+    let err = suite.stake("ekez", one, 0);
+    is_error!(err, &ContractError::ZeroStake {}.to_string());
+
+    suite.stake("ekez", one, 30).unwrap();
+    let err = suite.stake("ekez", two, 30);
+    is_error!(
+        err,
+        &ContractError::InsufficientPower {
+            available: Uint128::new(20)
+        }
+        .to_string()
+    );
+    suite.stake("ekez", two, 20).unwrap();
+    assert_eq!(suite.query_stake(one, "ekez"), Uint128::new(30));
+    assert_eq!(suite.query_stake(two, "ekez"), Uint128::new(20));
+    assert_eq!(suite.query_staked("ekez"), Uint128::new(50));
+}
+
+// stake on closed proposals counts towards a voter's staked total
+// until it is unstaked.
+#[test]
+fn test_unstake_from_closed_proposal() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let one = suite.propose("ekez", "beneficiary", 50).unwrap();
+    let two = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", one, 50).unwrap();
+
+    let err = suite.cancel("blue", one);
+    is_error!(err, &ContractError::NotProposer {}.to_string());
+    suite.cancel("ekez", one).unwrap();
+    assert_eq!(suite.query_proposal(one).proposal.status, Status::Closed);
+    let err = suite.cancel(suite.core.clone(), one);
+    is_error!(err, &ContractError::NotOpen {}.to_string());
+
+    let err = suite.stake("ekez", two, 50);
+    is_error!(
+        err,
+        &ContractError::InsufficientPower {
+            available: Uint128::zero()
+        }
+        .to_string()
+    );
+    suite.unstake("ekez", one, 50).unwrap();
+    suite.stake("ekez", two, 50).unwrap();
+
+    // the DAO may also cancel proposals.
+    suite.cancel(suite.core.clone(), two).unwrap();
+    assert_eq!(suite.query_proposal(two).proposal.status, Status::Closed);
+}
+
+#[test]
+fn test_sync_removes_stake() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    let one = suite.propose("ekez", "beneficiary", 50).unwrap();
+    let two = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", one, 25).unwrap();
+    suite.stake("ekez", two, 25).unwrap();
+
+    let err = suite.sync("blue", "ekez");
+    is_error!(err, &ContractError::Synced {}.to_string());
+
+    suite.set_voting_power("ekez", 10);
+    suite.sync("blue", "ekez").unwrap();
+
+    assert_eq!(suite.query_staked("ekez"), Uint128::zero());
+    assert_eq!(suite.query_stake(one, "ekez"), Uint128::zero());
+    assert_eq!(suite.query_proposal(one).proposal.staked, Uint128::zero());
+    assert_eq!(suite.query_proposal(two).proposal.staked, Uint128::zero());
+}
+
+#[test]
+fn test_sync_paginates() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 30), ("blue", 50)])
+        .build();
+    let one = suite.propose("ekez", "beneficiary", 50).unwrap();
+    let two = suite.propose("ekez", "beneficiary", 50).unwrap();
+    let three = suite.propose("ekez", "beneficiary", 50).unwrap();
+    suite.stake("ekez", one, 10).unwrap();
+    suite.stake("ekez", two, 10).unwrap();
+    suite.stake("ekez", three, 10).unwrap();
+
+    suite.set_voting_power("ekez", 5);
+    suite.sync_page("blue", "ekez", None, Some(1)).unwrap();
+    assert_eq!(suite.query_stake(one, "ekez"), Uint128::zero());
+    assert_eq!(suite.query_stake(two, "ekez"), Uint128::new(10));
+    assert_eq!(suite.query_staked("ekez"), Uint128::new(20));
+
+    suite.sync_page("blue", "ekez", Some(one), None).unwrap();
+    assert_eq!(suite.query_staked("ekez"), Uint128::zero());
+    assert_eq!(suite.query_proposal(three).proposal.staked, Uint128::zero());
+
+    // once stake is covered by voting power, sync is a no-op.
+    let err = suite.sync_page("blue", "ekez", Some(one), None);
+    is_error!(err, &ContractError::Synced {}.to_string());
+}
+
+#[test]
+fn test_stake_count_is_capped() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 1000), ("blue", 50)])
+        .build();
+    let mut ids = vec![];
+    for _ in 0..=MAX_STAKES {
+        ids.push(suite.propose("ekez", "beneficiary", 1).unwrap());
+    }
+    for id in &ids[..MAX_STAKES] {
+        suite.stake("ekez", *id, 1).unwrap();
+    }
+    // adding to an existing stake is allowed, staking on a new
+    // proposal is not.
+    suite.stake("ekez", ids[0], 1).unwrap();
+    let err = suite.stake("ekez", ids[MAX_STAKES], 1);
+    is_error!(
+        err,
+        &ContractError::TooManyStakes { max: MAX_STAKES }.to_string()
+    );
+
+    suite.unstake("ekez", ids[1], 1).unwrap();
+    suite.stake("ekez", ids[MAX_STAKES], 1).unwrap();
+}
+
+#[test]
+fn test_unexpected_reply() {
+    let mut deps = mock_dependencies();
+    let id = dao_voting::reply::mask_vote_hook_index(0);
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id,
+            result: SubMsgResult::Err("error".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InvalidReplyId { id }.to_string()
+    );
+}
+
+#[test]
+fn test_request_above_max_ratio_never_passes() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 50), ("blue", 50)])
+        .build();
+    // 20% of the treasury is the maximum.
+    let id = suite.propose("ekez", "beneficiary", 200).unwrap();
+    assert_eq!(suite.query_proposal(id).threshold, None);
+
+    suite.stake("ekez", id, 50).unwrap();
+    suite.stake("blue", id, 50).unwrap();
+    for _ in 0..10 {
+        suite.a_day_passes();
+    }
+    let err = suite.execute("ekez", id);
+    is_error!(err, &ContractError::InsufficientConviction {}.to_string());
+}
+
+#[test]
+fn test_only_dao_adds_proposal_hooks() {
+    let mut suite = SuiteBuilder::default().build();
+    let err = suite.add_proposal_hook(suite.sender(), "hook");
+    is_error!(err, &ContractError::NotDao {}.to_string());
+    suite.add_proposal_hook(suite.core.clone(), "hook").unwrap();
+}
@@ -0,0 +1,387 @@
+use cosmwasm_std::{coins, to_binary, Addr, Decimal, Decimal256, Uint128};
+use cw_denom::UncheckedDenom;
+use cw_multi_test::{next_block, App, BankSudo, Executor, SudoMsg};
+use cw_utils::Duration;
+use dao_interface::{voting::InfoResponse, Admin, ModuleInstantiateInfo};
+use dao_testing::contracts::{
+    cw4_group_contract, dao_core_contract, dao_voting_cw4_contract, proposal_conviction_contract,
+};
+
+use crate::{
+    config::{Config, UncheckedConfig},
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    proposal::ProposalResponse,
+};
+
+pub(crate) const DENOM: &str = "ujuno";
+
+pub(crate) struct Suite {
+    app: App,
+    sender: Addr,
+    pub conviction: Addr,
+    pub core: Addr,
+    pub group: Addr,
+}
+
+pub(crate) struct SuiteBuilder {
+    pub instantiate: InstantiateMsg,
+    with_voters: Vec<(String, u64)>,
+    with_treasury: u128,
+}
+
+impl Default for SuiteBuilder {
+    fn default() -> Self {
+        Self {
+            instantiate: UncheckedConfig {
+                denom: UncheckedDenom::Native(DENOM.to_string()),
+                decay: Decimal::percent(50),
+                max_ratio: Decimal::percent(20),
+                weight: Decimal::percent(1),
+                conviction_period: Duration::Time(60 * 60 * 24),
+                close_proposals_on_execution_failure: true,
+            },
+            with_voters: vec![("sender".to_string(), 10)],
+            with_treasury: 1000,
+        }
+    }
+}
+
+impl SuiteBuilder {
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn with_config(instantiate: UncheckedConfig) -> Self {
+        let mut b = Self::default();
+        b.instantiate = instantiate;
+        b
+    }
+
+    pub fn with_voters(mut self, voters: &[(&str, u64)]) -> Self {
+        self.with_voters = voters.iter().map(|(a, p)| (a.to_string(), *p)).collect();
+        self
+    }
+
+    pub fn build(self) -> Suite {
+        let initial_members: Vec<_> = self
+            .with_voters
+            .into_iter()
+            .map(|(addr, weight)| cw4::Member { addr, weight })
+            .collect();
+        let sender = Addr::unchecked(&initial_members[0].addr);
+
+        let mut app = App::default();
+        let conviction_id = app.store_code(proposal_conviction_contract());
+        let core_id = app.store_code(dao_core_contract());
+        let cw4_id = app.store_code(cw4_group_contract());
+        let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+
+        let core_instantiate = dao_core::msg::InstantiateMsg {
+            admin: None,
+            name: "core module".to_string(),
+            description: "core module".to_string(),
+            image_url: None,
+            automatically_add_cw20s: false,
+            automatically_add_cw721s: false,
+            voting_module_instantiate_info: ModuleInstantiateInfo {
+                code_id: cw4_voting_id,
+                msg: to_binary(&dao_voting_cw4::msg::InstantiateMsg {
+                    cw4_group_code_id: cw4_id,
+                    initial_members,
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "voting module".to_string(),
+            },
+            proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+                code_id: conviction_id,
+                msg: to_binary(&self.instantiate).unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "conviction module".to_string(),
+            }],
+            initial_items: None,
+            dao_uri: None,
+        };
+        let core = app
+            .instantiate_contract(
+                core_id,
+                sender.clone(),
+                &core_instantiate,
+                &[],
+                "core module".to_string(),
+                None,
+            )
+            .unwrap();
+        let modules: Vec<dao_core::state::ProposalModule> = app
+            .wrap()
+            .query_wasm_smart(
+                &core,
+                &dao_core::msg::QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let conviction = modules[0].address.clone();
+        let voting: Addr = app
+            .wrap()
+            .query_wasm_smart(&core, &dao_core::msg::QueryMsg::VotingModule {})
+            .unwrap();
+        let group: Addr = app
+            .wrap()
+            .query_wasm_smart(&voting, &dao_voting_cw4::msg::QueryMsg::GroupContract {})
+            .unwrap();
+
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: core.to_string(),
+            amount: coins(self.with_treasury, DENOM),
+        }))
+        .unwrap();
+
+        app.update_block(next_block);
+
+        let suite = Suite {
+            app,
+            sender,
+            conviction,
+            core,
+            group,
+        };
+
+        let next_id = suite.query_next_proposal_id();
+        assert_eq!(next_id, 1);
+
+        let dao = suite.query_dao();
+        assert_eq!(dao, suite.core);
+        let info = suite.query_info();
+        assert_eq!(info.info.version, CONTRACT_VERSION);
+        assert_eq!(info.info.contract, CONTRACT_NAME);
+
+        suite
+    }
+}
+
+impl Suite {
+    pub fn a_day_passes(&mut self) {
+        self.app.update_block(|b| {
+            b.time = b.time.plus_seconds(60 * 60 * 24);
+            b.height += 1;
+        })
+    }
+
+    pub fn sender(&self) -> Addr {
+        self.sender.clone()
+    }
+
+    /// Sets the voting power of VOTER to WEIGHT.
+    pub fn set_voting_power(&mut self, voter: &str, weight: u64) {
+        self.app
+            .execute_contract(
+                self.core.clone(),
+                self.group.clone(),
+                &cw4_group::msg::ExecuteMsg::UpdateMembers {
+                    remove: vec![],
+                    add: vec![cw4::Member {
+                        addr: voter.to_string(),
+                        weight,
+                    }],
+                },
+                &[],
+            )
+            .unwrap();
+        self.app.update_block(next_block);
+    }
+}
+
+// query
+impl Suite {
+    pub fn query_config(&self) -> Config {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.conviction, &QueryMsg::Config {})
+            .unwrap()
+    }
+
+    pub fn query_proposal(&self, id: u64) -> ProposalResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.conviction, &QueryMsg::Proposal { id })
+            .unwrap()
+    }
+
+    pub fn query_conviction(&self, id: u64) -> Decimal256 {
+        self.query_proposal(id).conviction
+    }
+
+    pub fn query_list_proposals(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<ProposalResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.conviction,
+                &QueryMsg::ListProposals { start_after, limit },
+            )
+            .unwrap()
+    }
+
+    pub fn query_stake(&self, proposal_id: u64, voter: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.conviction,
+                &QueryMsg::Stake {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_staked(&self, voter: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.conviction,
+                &QueryMsg::Staked {
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_balance(&self, who: &str) -> Uint128 {
+        self.app.wrap().query_balance(who, DENOM).unwrap().amount
+    }
+
+    pub fn query_next_proposal_id(&self) -> u64 {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.conviction, &QueryMsg::NextProposalId {})
+            .unwrap()
+    }
+
+    pub fn query_dao(&self) -> Addr {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.conviction, &QueryMsg::Dao {})
+            .unwrap()
+    }
+
+    pub fn query_info(&self) -> InfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.conviction, &QueryMsg::Info {})
+            .unwrap()
+    }
+}
+
+// execute
+impl Suite {
+    pub fn propose<S: Into<String>>(
+        &mut self,
+        sender: S,
+        beneficiary: &str,
+        amount: u128,
+    ) -> anyhow::Result<u64> {
+        let id = self.query_next_proposal_id();
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.conviction.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                beneficiary: beneficiary.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )?;
+        Ok(id)
+    }
+
+    fn execute_msg<S: Into<String>>(&mut self, sender: S, msg: ExecuteMsg) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.conviction.clone(), &msg, &[])
+            .map(|_| ())
+    }
+
+    pub fn stake<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u64,
+        amount: u128,
+    ) -> anyhow::Result<()> {
+        self.execute_msg(
+            sender,
+            ExecuteMsg::Stake {
+                proposal_id,
+                amount: Uint128::new(amount),
+            },
+        )
+    }
+
+    pub fn unstake<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u64,
+        amount: u128,
+    ) -> anyhow::Result<()> {
+        self.execute_msg(
+            sender,
+            ExecuteMsg::Unstake {
+                proposal_id,
+                amount: Uint128::new(amount),
+            },
+        )
+    }
+
+    pub fn sync<S: Into<String>>(&mut self, sender: S, voter: &str) -> anyhow::Result<()> {
+        self.sync_page(sender, voter, None, None)
+    }
+
+    pub fn sync_page<S: Into<String>>(
+        &mut self,
+        sender: S,
+        voter: &str,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    ) -> anyhow::Result<()> {
+        self.execute_msg(
+            sender,
+            ExecuteMsg::Sync {
+                voter: voter.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    pub fn execute<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.execute_msg(sender, ExecuteMsg::Execute { proposal_id })
+    }
+
+    pub fn cancel<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.execute_msg(sender, ExecuteMsg::Cancel { proposal_id })
+    }
+
+    pub fn set_config<S: Into<String>>(
+        &mut self,
+        sender: S,
+        config: UncheckedConfig,
+    ) -> anyhow::Result<()> {
+        self.execute_msg(sender, ExecuteMsg::SetConfig(config))
+    }
+
+    pub fn add_proposal_hook<S: Into<String>>(
+        &mut self,
+        sender: S,
+        address: &str,
+    ) -> anyhow::Result<()> {
+        self.execute_msg(
+            sender,
+            ExecuteMsg::AddProposalHook {
+                address: address.to_string(),
+            },
+        )
+    }
+}
@@ -1,22 +1,24 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
-    Storage, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use cw2::set_contract_version;
 use cw_hooks::Hooks;
 use cw_storage_plus::Bound;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use dao_interface::voting::IsActiveResponse;
 use dao_pre_propose_multiple::contract::ExecuteMsg as PreProposeMsg;
 use dao_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use dao_vote_hooks::new_vote_hooks;
 use dao_voting::{
+    anti_sniping::AntiSnipingConfig,
     multiple_choice::{
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
+        MultipleChoiceOptions, MultipleChoiceVote, TopN, VotingStrategy, MAX_RUNOFF_CHOICES,
     },
+    power_transform::{transform_voting_power, PowerTransform},
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     reply::{
@@ -24,18 +26,27 @@ use dao_voting::{
     },
     status::Status,
     voting::{get_total_power, get_voting_power, validate_voting_period},
+    voting_period::{proposal_voting_period, VotingPeriodChoice},
 };
 
-use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
+    delegation::{cast_delegated_power, revoke_delegated_power},
+    legacy::migrate_proposal_options,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    options::{ranked_options, save_options},
     proposal::{MultipleChoiceProposal, VoteResult},
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
+    query::{
+        OptionListResponse, ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse,
+        VoteResponse, VoterBallotInfo, VoterBallotListResponse,
+    },
+    runoff::{proposal_runoff, tally_runoff_page},
     state::{
-        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+        Ballot, Config, BALLOTS, CONFIG, OPTIONS, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS,
+        VOTER_BALLOTS, VOTE_HOOKS,
     },
     ContractError,
 };
+use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 
 pub const CONTRACT_NAME: &str = "crates.io:dao-proposal-multiple";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -55,6 +66,15 @@ pub fn instantiate(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
+    if let Some(anti_sniping) = msg.anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+    if let Some(power_transform) = msg.power_transform {
+        power_transform.validate()?;
+    }
+    if let Some(choice) = &msg.voting_period_choice {
+        choice.validate(min_voting_period.as_ref(), &max_voting_period)?;
+    }
 
     let (initial_policy, pre_propose_messages) = msg
         .pre_propose_info
@@ -68,6 +88,13 @@ pub fn instantiate(
         allow_revoting: msg.allow_revoting,
         dao,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        anti_sniping: msg.anti_sniping,
+        delegation_registry: msg
+            .delegation_registry
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        power_transform: msg.power_transform,
+        voting_period_choice: msg.voting_period_choice,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -95,6 +122,9 @@ pub fn execute(
             description,
             choices,
             proposer,
+            execute_at,
+            top_n,
+            voting_period,
         } => execute_propose(
             deps,
             env,
@@ -103,6 +133,9 @@ pub fn execute(
             description,
             choices,
             proposer,
+            execute_at,
+            top_n,
+            voting_period,
         ),
         ExecuteMsg::Vote {
             proposal_id,
@@ -111,6 +144,9 @@ pub fn execute(
         } => execute_vote(deps, env, info, proposal_id, vote, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::TallyRunoff { proposal_id, limit } => {
+            execute_tally_runoff(deps, env, info, proposal_id, limit)
+        }
         ExecuteMsg::UpdateConfig {
             voting_strategy,
             min_voting_period,
@@ -119,6 +155,10 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            delegation_registry,
+            power_transform,
+            voting_period_choice,
         } => execute_update_config(
             deps,
             info,
@@ -129,6 +169,10 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            delegation_registry,
+            power_transform,
+            voting_period_choice,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -150,6 +194,7 @@ pub fn execute(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -158,6 +203,9 @@ pub fn execute_propose(
     description: String,
     options: MultipleChoiceOptions,
     proposer: Option<String>,
+    execute_at: Option<Expiration>,
+    top_n: Option<TopN>,
+    voting_period: Option<Duration>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
@@ -196,12 +244,43 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
+    if execute_at == Some(Expiration::Never {}) {
+        return Err(ContractError::InvalidExecuteAt {});
+    }
+    if let Some(top_n) = top_n {
+        top_n.validate()?;
+        if matches!(config.voting_strategy, VotingStrategy::InstantRunoff { .. }) {
+            return Err(ContractError::TopNWithInstantRunoff {});
+        }
+    }
+
     // Validate options.
+    if matches!(config.voting_strategy, VotingStrategy::InstantRunoff { .. })
+        && options.options.len() > MAX_RUNOFF_CHOICES as usize
+    {
+        return Err(ContractError::TooManyRunoffChoices {
+            max: MAX_RUNOFF_CHOICES,
+        });
+    }
     let checked_multiple_choice_options = options.into_checked()?.options;
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // Proposals with short voting periods may require a higher
+    // quorum than the module's voting strategy.
+    let choice = config.voting_period_choice.as_ref();
+    let period = proposal_voting_period(choice, voting_period, config.max_voting_period)?;
+    let voting_strategy = match choice.and_then(|choice| choice.quorum(&period)) {
+        Some(quorum) => config.voting_strategy.raise_quorum(quorum),
+        None => config.voting_strategy.clone(),
+    };
+
+    let expiration = period.after(&env.block);
     let total_power = get_total_power(deps.as_ref(), &config.dao, None)?;
+    let total_power = match config.power_transform {
+        Some(power_transform) => power_transform.total_power(total_power),
+        None => total_power,
+    };
 
+    let id = advance_proposal_id(deps.storage)?;
     let proposal = {
         // Limit mutability to this block.
         let mut proposal = MultipleChoiceProposal {
@@ -211,19 +290,26 @@ pub fn execute_propose(
             start_height: env.block.height,
             min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
             expiration,
-            voting_strategy: config.voting_strategy,
+            voting_strategy,
             total_power,
             status: Status::Open,
-            votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
+            power_cast: Uint128::zero(),
             allow_revoting: config.allow_revoting,
-            choices: checked_multiple_choice_options,
+            num_choices: checked_multiple_choice_options.len() as u32,
+            anti_sniping: config.anti_sniping,
+            extensions: 0,
+            execute_at,
+            power_transform: config.power_transform,
+            top_n,
+            runoff: None,
+            voting_period,
         };
+        save_options(deps.storage, id, &checked_multiple_choice_options)?;
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
-        proposal.update_status(&env.block)?;
+        proposal.update_status(deps.storage, id, &env.block)?;
         proposal
     };
-    let id = advance_proposal_id(deps.storage)?;
 
     // Limit the size of proposals.
     //
@@ -239,12 +325,24 @@ pub fn execute_propose(
     //
     // `to_vec` is the method used by cosmwasm to convert a struct
     // into it's byte representation in storage.
+    //
+    // Options are stored and queried separately from the proposal,
+    // so each is held to the same limit.
+    let largest_option_size = checked_multiple_choice_options
+        .iter()
+        .map(|option| Ok(cosmwasm_std::to_vec(option)?.len() as u64))
+        .collect::<StdResult<Vec<u64>>>()?
+        .into_iter()
+        .max()
+        .unwrap_or_default();
     let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
-    if proposal_size > MAX_PROPOSAL_SIZE {
-        return Err(ContractError::ProposalTooLarge {
-            size: proposal_size,
-            max: MAX_PROPOSAL_SIZE,
-        });
+    for size in [proposal_size, largest_option_size] {
+        if size > MAX_PROPOSAL_SIZE {
+            return Err(ContractError::ProposalTooLarge {
+                size,
+                max: MAX_PROPOSAL_SIZE,
+            });
+        }
     }
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
@@ -260,7 +358,7 @@ pub fn execute_propose(
 }
 
 pub fn execute_vote(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
@@ -273,7 +371,10 @@ pub fn execute_vote(
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     // Check that this is a valid vote.
-    if vote.option_id as usize >= prop.choices.len() {
+    if !prop
+        .voting_strategy
+        .is_valid_vote(&vote, prop.num_choices as usize)
+    {
         return Err(ContractError::InvalidVote {});
     }
 
@@ -288,49 +389,79 @@ pub fn execute_vote(
         return Err(ContractError::Expired { id: proposal_id });
     }
 
-    let vote_power = get_voting_power(
-        deps.as_ref(),
-        info.sender.clone(),
-        &config.dao,
-        Some(prop.start_height),
-    )?;
-    if vote_power.is_zero() {
+    let vote_power = transform_voting_power(
+        prop.power_transform,
+        get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            &config.dao,
+            Some(prop.start_height),
+        )?,
+        prop.total_power,
+    );
+    // The power of delegators who have not voted is cast with their
+    // delegate's first vote and moves with it if they revote.
+    let delegated_power = match BALLOTS.may_load(deps.storage, (proposal_id, &info.sender))? {
+        Some(ballot) => ballot.delegated_power,
+        None => cast_delegated_power(
+            deps.branch(),
+            &env,
+            &config,
+            proposal_id,
+            &prop,
+            &info.sender,
+        )?,
+    };
+    if vote_power.is_zero() && delegated_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
-    BALLOTS.update(deps.storage, (proposal_id, &info.sender), |bal| match bal {
-        Some(current_ballot) => {
-            if prop.allow_revoting {
-                if current_ballot.vote == vote {
-                    // Don't allow casting the same vote more than
-                    // once. This seems liable to be confusing
-                    // behavior.
-                    Err(ContractError::AlreadyCast {})
-                } else {
-                    // Remove the old vote if this is a re-vote.
-                    prop.votes
-                        .remove_vote(current_ballot.vote, current_ballot.power)?;
-                    Ok(Ballot {
-                        power: vote_power,
-                        vote,
-                        rationale,
-                    })
-                }
-            } else {
-                Err(ContractError::AlreadyVoted {})
-            }
+    // The outcome is taken before a revoter's old vote is removed
+    // so that a revote which flips it is seen as doing so.
+    let old_status = prop.status;
+    let previous_outcome = prop.outcome_at_expiration(deps.storage, proposal_id, &env.block)?;
+
+    if let Some(current_ballot) = BALLOTS.may_load(deps.storage, (proposal_id, &info.sender))? {
+        if !prop.allow_revoting {
+            return Err(ContractError::AlreadyVoted {});
         }
-        None => Ok(Ballot {
-            vote,
+        if current_ballot.vote == vote {
+            // Don't allow casting the same vote more than
+            // once. This seems liable to be confusing
+            // behavior.
+            return Err(ContractError::AlreadyCast {});
+        }
+        // Remove the old vote if this is a re-vote.
+        prop.remove_vote(
+            deps.storage,
+            proposal_id,
+            &current_ballot.vote,
+            current_ballot.power + current_ballot.delegated_power,
+        )?;
+    }
+    BALLOTS.save(
+        deps.storage,
+        (proposal_id, &info.sender),
+        &Ballot {
+            vote: vote.clone(),
             power: vote_power,
             rationale,
-        }),
-    })?;
-
-    let old_status = prop.status;
+            delegated_power,
+        },
+    )?;
+    VOTER_BALLOTS.save(deps.storage, (&info.sender, proposal_id), &Empty {})?;
 
-    prop.votes.add_vote(vote, vote_power)?;
-    prop.update_status(&env.block)?;
+    // Voting directly overrides the voter's delegate.
+    revoke_delegated_power(deps.storage, proposal_id, &mut prop, &info.sender)?;
+    prop.add_vote(
+        deps.storage,
+        proposal_id,
+        &vote,
+        vote_power + delegated_power,
+    )?;
+    let extended =
+        prop.extend_if_outcome_changed(deps.storage, proposal_id, &env.block, previous_outcome)?;
+    prop.update_status(deps.storage, proposal_id, &env.block)?;
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
@@ -347,14 +478,21 @@ pub fn execute_vote(
         info.sender.to_string(),
         vote.to_string(),
     )?;
-    Ok(Response::default()
+    let response = Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
-        .add_attribute("status", prop.status.to_string()))
+        .add_attribute("delegated_power", delegated_power)
+        .add_attribute("status", prop.status.to_string());
+
+    Ok(if extended {
+        response.add_attribute("extended_expiration", prop.expiration.to_string())
+    } else {
+        response
+    })
 }
 
 pub fn execute_execute(
@@ -368,7 +506,9 @@ pub fn execute_execute(
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     let config = CONFIG.load(deps.storage)?;
-    if config.only_members_execute {
+    // Scheduled proposals may be executed by anyone once their
+    // execution time is reached.
+    if config.only_members_execute && prop.execute_at.is_none() {
         let power = get_voting_power(
             deps.as_ref(),
             info.sender.clone(),
@@ -383,80 +523,139 @@ pub fn execute_execute(
     // Check here that the proposal is passed. Allow it to be
     // executed even if it is expired so long as it passed during its
     // voting period.
-    prop.update_status(&env.block)?;
+    check_runoff_tallied(&prop, proposal_id, &env.block)?;
+    prop.update_status(deps.storage, proposal_id, &env.block)?;
     let old_status = prop.status;
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
+    if let Some(execute_at) = prop.execute_at {
+        if !execute_at.is_expired(&env.block) {
+            return Err(ContractError::ExecuteAtNotReached {
+                id: proposal_id,
+                execute_at,
+            });
+        }
+    }
 
     prop.status = Status::Executed;
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let vote_result = prop.calculate_vote_result()?;
-    match vote_result {
-        VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
-        VoteResult::SingleWinner(winning_choice) => {
-            let response = if !winning_choice.msgs.is_empty() {
-                let execute_message = WasmMsg::Execute {
-                    contract_addr: config.dao.to_string(),
-                    msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook {
-                        msgs: winning_choice.msgs,
-                    })?,
-                    funds: vec![],
-                };
-                match config.close_proposal_on_execution_failure {
-                    true => {
-                        let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                        Response::default().add_submessage(SubMsg::reply_on_error(
-                            execute_message,
-                            masked_proposal_id,
-                        ))
-                    }
-                    false => Response::default().add_message(execute_message),
-                }
-            } else {
+    let vote_result = prop.calculate_vote_result(deps.storage, proposal_id)?;
+    let msgs = match vote_result {
+        VoteResult::Tie => return Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
+        VoteResult::SingleWinner(winning_choice) => winning_choice.msgs,
+        // The messages of each winning option are executed in the
+        // order the winners are returned: by rank under TOP_N and by
+        // index otherwise.
+        VoteResult::MultipleWinners(winners) => {
+            winners.into_iter().flat_map(|winner| winner.msgs).collect()
+        }
+    };
+    let response = if !msgs.is_empty() {
+        let execute_message = WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        };
+        match config.close_proposal_on_execution_failure {
+            true => {
+                let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
                 Response::default()
-            };
+                    .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+            }
+            false => Response::default().add_message(execute_message),
+        }
+    } else {
+        Response::default()
+    };
 
-            let hooks = proposal_status_changed_hooks(
-                PROPOSAL_HOOKS,
-                deps.storage,
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = match proposal_creation_policy {
+        ProposalCreationPolicy::Anyone {} => hooks,
+        ProposalCreationPolicy::Module { addr } => {
+            let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
                 proposal_id,
-                old_status.to_string(),
-                prop.status.to_string(),
-            )?;
+                new_status: prop.status,
+            })?;
+            let mut hooks = hooks;
+            hooks.push(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: addr.into_string(),
+                    msg,
+                    funds: vec![],
+                },
+                failed_pre_propose_module_hook_id(),
+            ));
+            hooks
+        }
+    };
 
-            // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-            let hooks = match proposal_creation_policy {
-                ProposalCreationPolicy::Anyone {} => hooks,
-                ProposalCreationPolicy::Module { addr } => {
-                    let msg = to_binary(&PreProposeMsg::ProposalCompletedHook {
-                        proposal_id,
-                        new_status: prop.status,
-                    })?;
-                    let mut hooks = hooks;
-                    hooks.push(SubMsg::reply_on_error(
-                        WasmMsg::Execute {
-                            contract_addr: addr.into_string(),
-                            msg,
-                            funds: vec![],
-                        },
-                        failed_pre_propose_module_hook_id(),
-                    ));
-                    hooks
-                }
-            };
+    Ok(response
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
 
-            Ok(response
-                .add_submessages(hooks)
-                .add_attribute("action", "execute")
-                .add_attribute("sender", info.sender)
-                .add_attribute("proposal_id", proposal_id.to_string())
-                .add_attribute("dao", config.dao))
-        }
+/// Errors if PROP is an instant runoff proposal whose voting has
+/// closed but whose runoff has not been tallied, so that its status
+/// can not yet be determined.
+fn check_runoff_tallied(
+    prop: &MultipleChoiceProposal,
+    proposal_id: u64,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    if prop.is_runoff_pending() && prop.expiration.is_expired(block) {
+        return Err(ContractError::RunoffNotTallied { id: proposal_id });
+    }
+    Ok(())
+}
+
+pub fn execute_tally_runoff(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    limit: Option<u64>,
+) -> Result<Response<Empty>, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if !prop.is_runoff_pending() {
+        return Err(ContractError::NoRunoffToTally { id: proposal_id });
+    }
+    // Ballots may change until voting closes.
+    if !prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::NotExpired {});
     }
+
+    tally_runoff_page(
+        deps.storage,
+        proposal_id,
+        &mut prop,
+        limit.unwrap_or(DEFAULT_LIMIT),
+    )?;
+    let complete = prop.runoff.is_some();
+    prop.update_status(deps.storage, proposal_id, &env.block)?;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "tally_runoff")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("complete", complete.to_string()))
 }
 
 pub fn execute_close(
@@ -467,7 +666,8 @@ pub fn execute_close(
 ) -> Result<Response<Empty>, ContractError> {
     let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
 
-    prop.update_status(&env.block)?;
+    check_runoff_tallied(&prop, proposal_id, &env.block)?;
+    prop.update_status(deps.storage, proposal_id, &env.block)?;
     if prop.status != Status::Rejected {
         return Err(ContractError::WrongCloseStatus {});
     }
@@ -525,6 +725,10 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    anti_sniping: Option<AntiSnipingConfig>,
+    delegation_registry: Option<String>,
+    power_transform: Option<PowerTransform>,
+    voting_period_choice: Option<VotingPeriodChoice>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -539,6 +743,18 @@ pub fn execute_update_config(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
+    if let Some(anti_sniping) = anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+    if let Some(power_transform) = power_transform {
+        power_transform.validate()?;
+    }
+    if let Some(choice) = &voting_period_choice {
+        choice.validate(min_voting_period.as_ref(), &max_voting_period)?;
+    }
+    let delegation_registry = delegation_registry
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
 
     CONFIG.save(
         deps.storage,
@@ -550,6 +766,10 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            delegation_registry,
+            power_transform,
+            voting_period_choice,
         },
     )?;
 
@@ -740,7 +960,22 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListVotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => query_list_votes_by_voter(deps, voter, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
+        QueryMsg::ListOptions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_options(deps, proposal_id, start_after, limit),
+        QueryMsg::ListOptionsByVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_options_by_votes(deps, proposal_id, start_after, limit),
         QueryMsg::ReverseProposals {
             start_before,
             limit,
@@ -749,6 +984,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::Runoff { proposal_id } => query_runoff(deps, proposal_id),
     }
 }
 
@@ -764,7 +1000,15 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     let proposal = PROPOSALS.load(deps.storage, id)?;
-    to_binary(&proposal.into_response(&env.block, id)?)
+    to_binary(&proposal.into_response(deps.storage, &env.block, id)?)
+}
+
+pub fn query_runoff(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    match proposal.runoff {
+        Some(runoff) => to_binary(&runoff),
+        None => to_binary(&proposal_runoff(deps.storage, proposal_id, &proposal)?),
+    }
 }
 
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
@@ -785,7 +1029,7 @@ pub fn query_list_proposals(
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal.into_response(deps.storage, &env.block, id))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -804,7 +1048,7 @@ pub fn query_reverse_proposals(
         .take(limit as usize)
         .collect::<Result<Vec<(u64, MultipleChoiceProposal)>, _>>()?
         .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .map(|(id, proposal)| proposal.into_response(deps.storage, &env.block, id))
         .collect::<StdResult<Vec<ProposalResponse>>>()?;
 
     to_binary(&ProposalListResponse { proposals: props })
@@ -827,10 +1071,43 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         vote: ballot.vote,
         power: ballot.power,
         rationale: ballot.rationale,
+        delegated_power: ballot.delegated_power,
     });
     to_binary(&VoteResponse { vote })
 }
 
+pub fn query_list_options(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u32>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let min = start_after.map(Bound::exclusive);
+    let options = OPTIONS
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| item.map(|(_, option)| option))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&OptionListResponse { options })
+}
+
+pub fn query_list_options_by_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u32>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let options = ranked_options(deps.storage, proposal_id, start_after)?
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&OptionListResponse { options })
+}
+
 pub fn query_list_votes(
     deps: Deps,
     proposal_id: u64,
@@ -854,6 +1131,7 @@ pub fn query_list_votes(
                 vote: ballot.vote,
                 power: ballot.power,
                 rationale: ballot.rationale,
+                delegated_power: ballot.delegated_power,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -861,6 +1139,36 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_list_votes_by_voter(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let ballots = VOTER_BALLOTS
+        .prefix(&voter)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|proposal_id| {
+            let proposal_id = proposal_id?;
+            Ok(VoterBallotInfo {
+                proposal_id,
+                ballot: BALLOTS.load(deps.storage, (proposal_id, &voter))?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoterBallotListResponse { ballots })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&dao_interface::voting::InfoResponse { info })
@@ -925,11 +1233,28 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             };
             Ok(Response::new().add_attribute("failed_prepropose_hook", format!("{addr}")))
         }
+        // Proposals in this module are executed in a single message
+        // and do not recur.
+        TaggedReplyId::FailedProposalMessage(..) | TaggedReplyId::FailedInstalment(..) => {
+            Err(ContractError::InvalidReplyID { id: msg.id })
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Add existing ballots to the voter index. This is idempotent.
+    let keys = BALLOTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Addr)>>>()?;
+    for (proposal_id, voter) in keys {
+        VOTER_BALLOTS.save(deps.storage, (&voter, proposal_id), &Empty {})?;
+    }
+
+    // Move the options of existing proposals out of them.
+    migrate_proposal_options(deps.storage)?;
+
     Ok(Response::default())
 }
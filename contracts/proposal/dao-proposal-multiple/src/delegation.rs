@@ -0,0 +1,88 @@
+use cosmwasm_std::{Addr, DepsMut, Env, Storage, Uint128};
+use dao_delegation_registry::helpers::query_delegators;
+use dao_voting::{power_transform::transform_voting_power, voting::get_voting_power};
+
+use crate::{
+    proposal::MultipleChoiceProposal,
+    state::{Config, DelegatedBallot, BALLOTS, DELEGATED_BALLOTS},
+    ContractError,
+};
+
+/// Casts the voting power of VOTER's delegators who have not voted on
+/// proposal PROPOSAL_ID along with VOTER's vote, returning the total
+/// power cast. Delegations and voting power are read as of the
+/// proposal's start height. The cost of this grows with the number
+/// of delegators VOTER has, which the registry caps at
+/// `MAX_DELEGATORS`.
+pub(crate) fn cast_delegated_power(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    proposal_id: u64,
+    prop: &MultipleChoiceProposal,
+    voter: &Addr,
+) -> Result<Uint128, ContractError> {
+    let registry = match &config.delegation_registry {
+        Some(registry) => registry,
+        None => return Ok(Uint128::zero()),
+    };
+    let delegators = query_delegators(
+        &deps.querier,
+        registry,
+        voter,
+        &env.contract.address,
+        prop.start_height,
+    )?;
+
+    let mut total = Uint128::zero();
+    for delegator in delegators {
+        // Delegators who have voted override their delegate.
+        if BALLOTS.has(deps.storage, (proposal_id, &delegator)) {
+            continue;
+        }
+        let power = transform_voting_power(
+            prop.power_transform,
+            get_voting_power(
+                deps.as_ref(),
+                delegator.clone(),
+                &config.dao,
+                Some(prop.start_height),
+            )?,
+            prop.total_power,
+        );
+        if power.is_zero() {
+            continue;
+        }
+        DELEGATED_BALLOTS.save(
+            deps.storage,
+            (proposal_id, &delegator),
+            &DelegatedBallot {
+                delegate: voter.clone(),
+                power,
+            },
+        )?;
+        total += power;
+    }
+    Ok(total)
+}
+
+/// If VOTER's voting power on proposal PROPOSAL_ID has been cast by
+/// their delegate, removes it from the delegate's vote so that VOTER
+/// may vote themselves.
+pub(crate) fn revoke_delegated_power(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    prop: &mut MultipleChoiceProposal,
+    voter: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(DelegatedBallot { delegate, power }) =
+        DELEGATED_BALLOTS.may_load(storage, (proposal_id, voter))?
+    {
+        let mut ballot = BALLOTS.load(storage, (proposal_id, &delegate))?;
+        prop.remove_vote(storage, proposal_id, &ballot.vote, power)?;
+        ballot.delegated_power -= power;
+        BALLOTS.save(storage, (proposal_id, &delegate), &ballot)?;
+        DELEGATED_BALLOTS.remove(storage, (proposal_id, voter));
+    }
+    Ok(())
+}
@@ -2,7 +2,7 @@ use std::u64;
 
 use cosmwasm_std::StdError;
 use cw_hooks::HookError;
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
 use dao_voting::{reply::error::TagError, threshold::ThresholdError};
 use thiserror::Error;
 
@@ -98,4 +98,22 @@ pub enum ContractError {
 
     #[error("received a reply failure with an invalid ID: ({id})")]
     InvalidReplyID { id: u64 },
+
+    #[error("execute_at may not be never")]
+    InvalidExecuteAt {},
+
+    #[error("proposal ({id}) may not be executed until {execute_at}")]
+    ExecuteAtNotReached { id: u64, execute_at: Expiration },
+
+    #[error("the runoff of proposal ({id}) has not been tallied. call `tally_runoff` until it is complete")]
+    RunoffNotTallied { id: u64 },
+
+    #[error("proposal ({id}) has no runoff to tally")]
+    NoRunoffToTally { id: u64 },
+
+    #[error("top_n may not be used with instant runoff voting, which selects a single winner")]
+    TopNWithInstantRunoff {},
+
+    #[error("instant runoff proposals may have at most ({max}) options")]
+    TooManyRunoffChoices { max: u32 },
 }
@@ -0,0 +1,180 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+use cw_utils::Expiration;
+use dao_voting::{
+    anti_sniping::AntiSnipingConfig,
+    multiple_choice::{CheckedMultipleChoiceOption, MultipleChoiceVotes, TopN, VotingStrategy},
+    power_transform::PowerTransform,
+    status::Status,
+};
+
+use crate::{
+    options::save_options, proposal::MultipleChoiceProposal, runoff::Runoff, state::PROPOSALS,
+};
+
+/// A proposal as stored before its options and their tallies were
+/// moved out of it.
+#[cw_serde]
+struct LegacyProposal {
+    title: String,
+    description: String,
+    proposer: Addr,
+    start_height: u64,
+    min_voting_period: Option<Expiration>,
+    expiration: Expiration,
+    choices: Vec<CheckedMultipleChoiceOption>,
+    status: Status,
+    voting_strategy: VotingStrategy,
+    total_power: Uint128,
+    votes: MultipleChoiceVotes,
+    allow_revoting: bool,
+    #[serde(default)]
+    anti_sniping: Option<AntiSnipingConfig>,
+    #[serde(default)]
+    extensions: u64,
+    #[serde(default)]
+    execute_at: Option<Expiration>,
+    #[serde(default)]
+    power_transform: Option<PowerTransform>,
+    #[serde(default)]
+    top_n: Option<TopN>,
+    #[serde(default)]
+    runoff: Option<Runoff>,
+}
+
+const LEGACY_PROPOSALS: Map<u64, LegacyProposal> = Map::new("proposals");
+
+/// Moves the options and tallies of proposals stored by older
+/// versions of this module into `OPTIONS`. Proposals already in the
+/// current format are skipped, so this is idempotent.
+pub(crate) fn migrate_proposal_options(storage: &mut dyn Storage) -> StdResult<()> {
+    // Raw keys, as listing keys through `PROPOSALS` parses the
+    // proposals themselves.
+    let ids = PROPOSALS
+        .keys_raw(storage, None, None, Order::Ascending)
+        .map(|key| u64::from_be_bytes(key.try_into().unwrap()))
+        .collect::<Vec<u64>>();
+    for id in ids {
+        let legacy = match LEGACY_PROPOSALS.load(storage, id) {
+            Ok(legacy) => legacy,
+            Err(_) => continue,
+        };
+        let options: Vec<CheckedMultipleChoiceOption> = legacy
+            .choices
+            .into_iter()
+            .map(|option| CheckedMultipleChoiceOption {
+                vote_count: legacy.votes.vote_weights[option.index as usize],
+                ..option
+            })
+            .collect();
+        save_options(storage, id, &options)?;
+        PROPOSALS.save(
+            storage,
+            id,
+            &MultipleChoiceProposal {
+                title: legacy.title,
+                description: legacy.description,
+                proposer: legacy.proposer,
+                start_height: legacy.start_height,
+                min_voting_period: legacy.min_voting_period,
+                expiration: legacy.expiration,
+                num_choices: options.len() as u32,
+                status: legacy.status,
+                voting_strategy: legacy.voting_strategy,
+                total_power: legacy.total_power,
+                power_cast: legacy.votes.total(),
+                allow_revoting: legacy.allow_revoting,
+                anti_sniping: legacy.anti_sniping,
+                extensions: legacy.extensions,
+                execute_at: legacy.execute_at,
+                power_transform: legacy.power_transform,
+                top_n: legacy.top_n,
+                runoff: legacy.runoff,
+                voting_period: None,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use dao_voting::{
+        multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions},
+        threshold::PercentageThreshold,
+    };
+
+    use super::*;
+    use crate::state::OPTIONS;
+
+    #[test]
+    fn test_migrate_proposal_options() {
+        let mut storage = MockStorage::default();
+        let choices = MultipleChoiceOptions {
+            options: vec![
+                MultipleChoiceOption {
+                    title: "owl".to_string(),
+                    description: String::new(),
+                    msgs: vec![],
+                },
+                MultipleChoiceOption {
+                    title: "fox".to_string(),
+                    description: String::new(),
+                    msgs: vec![],
+                },
+            ],
+        }
+        .into_checked()
+        .unwrap()
+        .options;
+        let legacy = LegacyProposal {
+            title: "mascot".to_string(),
+            description: "pick the DAO's mascot".to_string(),
+            proposer: Addr::unchecked("ekez"),
+            start_height: 1,
+            min_voting_period: None,
+            expiration: Expiration::AtHeight(10),
+            choices,
+            status: Status::Open,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            total_power: Uint128::new(10),
+            votes: MultipleChoiceVotes {
+                vote_weights: vec![Uint128::new(3), Uint128::new(1), Uint128::zero()],
+                power_cast: None,
+            },
+            allow_revoting: false,
+            anti_sniping: None,
+            extensions: 0,
+            execute_at: None,
+            power_transform: None,
+            top_n: None,
+            runoff: None,
+        };
+        LEGACY_PROPOSALS.save(&mut storage, 1, &legacy).unwrap();
+
+        migrate_proposal_options(&mut storage).unwrap();
+        // Migrating again leaves the proposal as is.
+        migrate_proposal_options(&mut storage).unwrap();
+
+        let proposal = PROPOSALS.load(&storage, 1).unwrap();
+        assert_eq!(proposal.num_choices, 3);
+        assert_eq!(proposal.power_cast, Uint128::new(4));
+        let fox = OPTIONS.load(&storage, (1, 1)).unwrap();
+        assert_eq!(fox.title, "fox");
+        assert_eq!(fox.vote_count, Uint128::new(1));
+        assert_eq!(
+            proposal
+                .calculate_vote_result(&storage, 1)
+                .map(|result| match result {
+                    crate::proposal::VoteResult::SingleWinner(winner) => winner.index,
+                    _ => panic!("expected a single winner"),
+                })
+                .unwrap(),
+            0
+        );
+    }
+}
@@ -1,10 +1,14 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 pub mod contract;
+mod delegation;
 mod error;
+mod legacy;
 pub mod msg;
+mod options;
 pub mod proposal;
 pub mod query;
+pub mod runoff;
 pub mod state;
 pub use crate::error::ContractError;
 
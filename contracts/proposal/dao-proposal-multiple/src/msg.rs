@@ -1,9 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_macros::proposal_module_query;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy},
+    anti_sniping::AntiSnipingConfig,
+    multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, TopN, VotingStrategy},
+    power_transform::PowerTransform,
     pre_propose::PreProposeInfo,
+    voting_period::VotingPeriodChoice,
 };
 
 #[cw_serde]
@@ -37,6 +40,23 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, votes cast in the final window of a proposal's voting
+    /// period that change its outcome (the winning option, or
+    /// passing to failing and vice versa) will extend the proposal's
+    /// expiration.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// If set, the votes of delegates in this
+    /// `dao-delegation-registry` are counted on behalf of their
+    /// delegators who do not vote themselves.
+    pub delegation_registry: Option<String>,
+    /// If set, voters' voting power is transformed before it is
+    /// tallied, dampening the influence of large holders.
+    pub power_transform: Option<PowerTransform>,
+    /// If set, proposers may choose a voting period between its
+    /// minimum and `max_voting_period`, and proposals with short
+    /// voting periods may require a higher quorum.
+    #[serde(default)]
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 #[cw_serde]
@@ -55,6 +75,20 @@ pub enum ExecuteMsg {
         /// pre-propose module is attached, this must be Some and will
         /// set the proposer of the proposal it creates.
         proposer: Option<String>,
+        /// If set, the proposal may not be executed before this time
+        /// once passed. Once this time is reached, any address may
+        /// execute the proposal.
+        #[serde(default)]
+        execute_at: Option<Expiration>,
+        /// If set, up to this many options may win the proposal and
+        /// have their messages executed.
+        #[serde(default)]
+        top_n: Option<TopN>,
+        /// The voting period of the proposal, if the module allows
+        /// proposers to choose one. If None, the module's max voting
+        /// period is used.
+        #[serde(default)]
+        voting_period: Option<Duration>,
     },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
@@ -81,6 +115,16 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Counts up to `limit` ballots towards the runoff of an instant
+    /// runoff proposal whose voting has closed. May be called by any
+    /// address, and must be called until the runoff is complete
+    /// before the proposal may be executed or closed.
+    TallyRunoff {
+        /// The ID of the proposal whose runoff to tally.
+        proposal_id: u64,
+        /// The maximum number of ballots to count.
+        limit: Option<u64>,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal voting strategy. This will only apply
@@ -116,6 +160,24 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// If set, votes cast in the final window of a proposal's
+        /// voting period that change its outcome will extend the
+        /// proposal's expiration. This will only apply to proposals
+        /// created after the config update.
+        anti_sniping: Option<AntiSnipingConfig>,
+        /// If set, the votes of delegates in this delegation
+        /// registry are counted on behalf of their delegators. This
+        /// applies to votes cast after the config update.
+        delegation_registry: Option<String>,
+        /// If set, voters' voting power is transformed before it is
+        /// tallied. This will only apply to proposals created after
+        /// the config update.
+        power_transform: Option<PowerTransform>,
+        /// If set, proposers may choose the voting periods of their
+        /// proposals. This will only apply to proposals created after
+        /// the config update.
+        #[serde(default)]
+        voting_period_choice: Option<VotingPeriodChoice>,
     },
     /// Updates the sender's rationale for their vote on the specified
     /// proposal. Errors if no vote vote has been cast.
@@ -165,6 +227,23 @@ pub enum QueryMsg {
         start_before: Option<u64>,
         limit: Option<u64>,
     },
+    /// Lists the options of a proposal, along with their tallies, in
+    /// the order they are listed on the proposal.
+    #[returns(crate::query::OptionListResponse)]
+    ListOptions {
+        proposal_id: u64,
+        start_after: Option<u32>,
+        limit: Option<u64>,
+    },
+    /// Lists the options of a proposal, along with their tallies, in
+    /// descending order of votes. Options with equal votes are listed
+    /// in the order they are listed on the proposal.
+    #[returns(crate::query::OptionListResponse)]
+    ListOptionsByVotes {
+        proposal_id: u64,
+        start_after: Option<u32>,
+        limit: Option<u64>,
+    },
     /// Returns a voters position on a proposal.
     #[returns(crate::query::VoteResponse)]
     GetVote { proposal_id: u64, voter: String },
@@ -175,6 +254,14 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u64>,
     },
+    /// Lists the ballots cast by VOTER in ascending order of
+    /// proposal ID.
+    #[returns(crate::query::VoterBallotListResponse)]
+    ListVotesByVoter {
+        voter: String,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this module.
     #[returns(::std::primitive::u64)]
     ProposalCount {},
@@ -187,6 +274,11 @@ pub enum QueryMsg {
     /// Lists all of the consumers of vote hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the eliminations and final round of an instant runoff
+    /// proposal's runoff. Until voting closes this is the runoff of
+    /// the ballots cast so far.
+    #[returns(crate::runoff::Runoff)]
+    Runoff { proposal_id: u64 },
 }
 
 #[cw_serde]
@@ -0,0 +1,126 @@
+use cosmwasm_std::{Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use dao_voting::multiple_choice::CheckedMultipleChoiceOption;
+
+use crate::state::{OPTIONS, OPTIONS_BY_VOTES};
+
+/// Key of OPTION in `OPTIONS_BY_VOTES`. The option's index is
+/// inverted so that options with equal votes are ranked in the order
+/// they are listed.
+fn rank_key(option: &CheckedMultipleChoiceOption) -> (u128, u32) {
+    (option.vote_count.u128(), u32::MAX - option.index)
+}
+
+/// Saves the options of a new proposal.
+pub(crate) fn save_options(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    options: &[CheckedMultipleChoiceOption],
+) -> StdResult<()> {
+    for option in options {
+        OPTIONS.save(storage, (proposal_id, option.index), option)?;
+        OPTIONS_BY_VOTES.save(storage, (proposal_id, rank_key(option)), &option.index)?;
+    }
+    Ok(())
+}
+
+/// Adds POWER to the tally of option OPTION_ID, or removes it if
+/// SUBTRACT is set.
+pub(crate) fn tally_option(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    option_id: u32,
+    power: Uint128,
+    subtract: bool,
+) -> StdResult<()> {
+    let mut option = OPTIONS.load(storage, (proposal_id, option_id))?;
+    OPTIONS_BY_VOTES.remove(storage, (proposal_id, rank_key(&option)));
+    option.vote_count = if subtract {
+        option.vote_count.checked_sub(power)
+    } else {
+        option.vote_count.checked_add(power)
+    }
+    .map_err(StdError::overflow)?;
+    OPTIONS.save(storage, (proposal_id, option_id), &option)?;
+    OPTIONS_BY_VOTES.save(storage, (proposal_id, rank_key(&option)), &option.index)?;
+    Ok(())
+}
+
+/// Iterates over a proposal's options from the most voted for to the
+/// least, starting after option START_AFTER if set. Options with
+/// equal votes are ranked in the order they are listed.
+pub(crate) fn ranked_options(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    start_after: Option<u32>,
+) -> StdResult<impl Iterator<Item = StdResult<CheckedMultipleChoiceOption>> + '_> {
+    let max = match start_after {
+        Some(index) => Some(Bound::exclusive(rank_key(
+            &OPTIONS.load(storage, (proposal_id, index))?,
+        ))),
+        None => None,
+    };
+    Ok(OPTIONS_BY_VOTES
+        .prefix(proposal_id)
+        .range(storage, None, max, Order::Descending)
+        .map(move |item| {
+            let (_, index) = item?;
+            OPTIONS.load(storage, (proposal_id, index))
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use dao_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
+
+    use super::*;
+
+    fn ranking(storage: &dyn Storage, start_after: Option<u32>) -> Vec<(u32, u128)> {
+        ranked_options(storage, 1, start_after)
+            .unwrap()
+            .map(|option| {
+                let option = option.unwrap();
+                (option.index, option.vote_count.u128())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ranked_options() {
+        let mut storage = MockStorage::default();
+        let options = MultipleChoiceOptions {
+            options: (0..3)
+                .map(|i| MultipleChoiceOption {
+                    title: format!("option {i}"),
+                    description: String::new(),
+                    msgs: vec![],
+                })
+                .collect(),
+        }
+        .into_checked()
+        .unwrap()
+        .options;
+        save_options(&mut storage, 1, &options).unwrap();
+        assert_eq!(
+            ranking(&storage, None),
+            vec![(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+
+        tally_option(&mut storage, 1, 2, Uint128::new(5), false).unwrap();
+        tally_option(&mut storage, 1, 1, Uint128::new(3), false).unwrap();
+        tally_option(&mut storage, 1, 3, Uint128::new(3), false).unwrap();
+        assert_eq!(
+            ranking(&storage, None),
+            vec![(2, 5), (1, 3), (3, 3), (0, 0)]
+        );
+        assert_eq!(ranking(&storage, Some(1)), vec![(3, 3), (0, 0)]);
+
+        tally_option(&mut storage, 1, 2, Uint128::new(4), true).unwrap();
+        assert_eq!(
+            ranking(&storage, None),
+            vec![(1, 3), (3, 3), (2, 1), (0, 0)]
+        );
+        tally_option(&mut storage, 1, 0, Uint128::new(1), true).unwrap_err();
+    }
+}
@@ -1,15 +1,23 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
-use cw_utils::Expiration;
+use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Storage, Uint128};
+use cw_utils::{Duration, Expiration};
 use dao_voting::{
+    anti_sniping::{block_at_expiration, AntiSnipingConfig},
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
+        AllocationWinners, CheckedMultipleChoiceOption, MultipleChoiceOptionType,
+        MultipleChoiceVote, TopN, VotingStrategy,
     },
+    power_transform::PowerTransform,
     status::Status,
     voting::does_vote_count_pass,
 };
 
-use crate::query::ProposalResponse;
+use crate::{
+    options::{ranked_options, tally_option},
+    query::ProposalResponse,
+    runoff::Runoff,
+    state::OPTIONS,
+};
 
 #[cw_serde]
 pub struct MultipleChoiceProposal {
@@ -28,25 +36,65 @@ pub struct MultipleChoiceProposal {
     /// The the time at which this proposal will expire and close for
     /// additional votes.
     pub expiration: Expiration,
-    /// The options to be chosen from in the vote.
-    pub choices: Vec<CheckedMultipleChoiceOption>,
+    /// The number of options to be chosen from in the vote,
+    /// including "None of the above". The options themselves are
+    /// stored apart from the proposal.
+    pub num_choices: u32,
     /// Prosal status (Open, rejected, executed, execution failed, closed, passed)
     pub status: Status,
     /// Voting settings (threshold, quorum, etc.)
     pub voting_strategy: VotingStrategy,
     /// The total power when the proposal started (used to calculate percentages)
     pub total_power: Uint128,
-    /// The vote tally.
-    pub votes: MultipleChoiceVotes,
+    /// The total power of the votes cast. Votes selecting more than
+    /// one option add their power to each option's tally, so this may
+    /// be less than the sum of the tallies.
+    pub power_cast: Uint128,
     /// Whether DAO members are allowed to change their votes.
     /// When disabled, proposals can be executed as soon as they pass.
     /// When enabled, proposals can only be executed after the voting
     /// perid has ended and the proposal passed.
     pub allow_revoting: bool,
+    /// The anti-sniping configuration of the module at the time of
+    /// this proposal's creation, if any.
+    #[serde(default)]
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// The number of times this proposal's expiration has been
+    /// extended by outcome changing votes cast shortly before it
+    /// expired.
+    #[serde(default)]
+    pub extensions: u64,
+    /// If set, the time before which this proposal may not be
+    /// executed.
+    #[serde(default)]
+    pub execute_at: Option<Expiration>,
+    /// The power transform of the module at the time of this
+    /// proposal's creation, if any. `total_power` has already been
+    /// transformed.
+    #[serde(default)]
+    pub power_transform: Option<PowerTransform>,
+    /// If set, the number of options that may win this proposal and
+    /// the votes each must receive. Takes precedence over the winner
+    /// rule of the voting strategy.
+    #[serde(default)]
+    pub top_n: Option<TopN>,
+    /// The instant runoff of this proposal's ballots, if it uses the
+    /// instant runoff voting strategy and its runoff has been
+    /// tallied.
+    #[serde(default)]
+    pub runoff: Option<Runoff>,
+    /// The voting period chosen by the proposer, if any. Proposals
+    /// whose proposer did not choose use the module's max voting
+    /// period.
+    #[serde(default)]
+    pub voting_period: Option<Duration>,
 }
 
 pub enum VoteResult {
     SingleWinner(CheckedMultipleChoiceOption),
+    /// Every winning option, under voting strategies that may have
+    /// more than one. Empty if no option won.
+    MultipleWinners(Vec<CheckedMultipleChoiceOption>),
     Tie,
 }
 
@@ -58,17 +106,31 @@ impl MultipleChoiceProposal {
     /// the proposal expiring has changed its status. This method
     /// recomputes the status so that queries get accurate
     /// information.
-    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> StdResult<ProposalResponse> {
-        self.update_status(block)?;
+    pub fn into_response(
+        mut self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        id: u64,
+    ) -> StdResult<ProposalResponse> {
+        self.update_status(storage, id, block)?;
         Ok(ProposalResponse { id, proposal: self })
     }
 
     /// Gets the current status of the proposal.
-    pub fn current_status(&self, block: &BlockInfo) -> StdResult<Status> {
-        if self.status == Status::Open && self.is_passed(block)? {
+    pub fn current_status(
+        &self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+    ) -> StdResult<Status> {
+        if self.status == Status::Open && self.is_passed(storage, id, block)? {
             Ok(Status::Passed)
+        } else if self.status == Status::Open && self.is_runoff_pending() {
+            // Instant runoff proposals remain open, even once expired,
+            // until their runoff has been tallied.
+            Ok(Status::Open)
         } else if self.status == Status::Open
-            && (self.expiration.is_expired(block) || self.is_rejected(block)?)
+            && (self.expiration.is_expired(block) || self.is_rejected(storage, id, block)?)
         {
             Ok(Status::Rejected)
         } else {
@@ -77,22 +139,83 @@ impl MultipleChoiceProposal {
     }
 
     /// Sets a proposals status to its current status.
-    pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
-        let new_status = self.current_status(block)?;
+    pub fn update_status(
+        &mut self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+    ) -> StdResult<()> {
+        let new_status = self.current_status(storage, id, block)?;
         self.status = new_status;
         Ok(())
     }
 
+    /// Adds VOTE, cast with POWER, to the tally of proposal ID.
+    pub(crate) fn add_vote(
+        &mut self,
+        storage: &mut dyn Storage,
+        id: u64,
+        vote: &MultipleChoiceVote,
+        power: Uint128,
+    ) -> StdResult<()> {
+        self.power_cast = self
+            .power_cast
+            .checked_add(power)
+            .map_err(StdError::overflow)?;
+        self.tally_vote(storage, id, vote, power, false)
+    }
+
+    /// Removes VOTE, cast with POWER, from the tally of proposal ID.
+    pub(crate) fn remove_vote(
+        &mut self,
+        storage: &mut dyn Storage,
+        id: u64,
+        vote: &MultipleChoiceVote,
+        power: Uint128,
+    ) -> StdResult<()> {
+        self.power_cast = self
+            .power_cast
+            .checked_sub(power)
+            .map_err(StdError::overflow)?;
+        self.tally_vote(storage, id, vote, power, true)
+    }
+
+    fn tally_vote(
+        &self,
+        storage: &mut dyn Storage,
+        id: u64,
+        vote: &MultipleChoiceVote,
+        power: Uint128,
+        subtract: bool,
+    ) -> StdResult<()> {
+        let vote = self.voting_strategy.tallied_vote(vote);
+        for (option_id, power) in vote.option_powers(power) {
+            tally_option(storage, id, option_id, power, subtract)?;
+        }
+        Ok(())
+    }
+
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail). Passing in the case of multiple choice proposals
     /// means that quorum has been met,
     /// one of the options that is not "None of the above"
     /// has won the most votes, and there is no tie.
-    pub fn is_passed(&self, block: &BlockInfo) -> StdResult<bool> {
-        // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+    pub fn is_passed(&self, storage: &dyn Storage, id: u64, block: &BlockInfo) -> StdResult<bool> {
+        // If re-voting is allowed, or the votes still to be cast are
+        // not bounded by the total power, nothing is known until the
+        // proposal has expired.
+        if (self.allow_revoting || !self.bounds_votes()) && !self.expiration.is_expired(block) {
+            return Ok(false);
+        }
+        // Instant runoff proposals are decided by their runoff, which
+        // is only run once voting has closed.
+        if self.is_runoff_pending() {
+            return Ok(false);
+        }
+        // Proposals with more than one winner are only decided once
+        // they expire.
+        if self.has_multiple_winners() && !self.expiration.is_expired(block) {
             return Ok(false);
         }
         // If the min voting period is set and not expired the
@@ -108,14 +231,17 @@ impl MultipleChoiceProposal {
 
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
-            self.votes.total(),
+            self.power_cast,
             self.total_power,
             self.voting_strategy.get_quorum(),
         ) {
-            let vote_result = self.calculate_vote_result()?;
+            let vote_result = self.calculate_vote_result(storage, id)?;
             match vote_result {
                 // Proposal is not passed if there is a tie.
                 VoteResult::Tie => return Ok(false),
+                VoteResult::MultipleWinners(winners) => {
+                    return Ok(!winners.is_empty() && self.expiration.is_expired(block))
+                }
                 VoteResult::SingleWinner(winning_choice) => {
                     // Proposal is not passed if winning choice is None.
                     if winning_choice.option_type != MultipleChoiceOptionType::None {
@@ -125,7 +251,7 @@ impl MultipleChoiceProposal {
                         } else {
                             // If the proposal is not expired but the leading choice cannot
                             // possibly be outwon by any other choices, the proposal has passed.
-                            return self.is_choice_unbeatable(&winning_choice);
+                            return self.is_choice_unbeatable(storage, id, &winning_choice);
                         }
                     }
                 }
@@ -134,26 +260,93 @@ impl MultipleChoiceProposal {
         Ok(false)
     }
 
-    pub fn is_rejected(&self, block: &BlockInfo) -> StdResult<bool> {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+    /// Returns the indexes of the options that would win were voting
+    /// to close with the current tally, or None if the proposal would
+    /// not pass.
+    pub fn outcome_at_expiration(
+        &self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+    ) -> StdResult<Option<Vec<u32>>> {
+        let block = block_at_expiration(block, &self.expiration);
+        if !self.is_passed(storage, id, &block)? {
+            return Ok(None);
+        }
+        Ok(match self.calculate_vote_result(storage, id)? {
+            VoteResult::SingleWinner(winning_choice) => Some(vec![winning_choice.index]),
+            VoteResult::MultipleWinners(winners) => {
+                Some(winners.iter().map(|winner| winner.index).collect())
+            }
+            VoteResult::Tie => None,
+        })
+    }
+
+    /// Extends this proposal's expiration if anti-sniping is enabled,
+    /// BLOCK is inside of the extension window, and the proposal's
+    /// outcome at expiration differs from PREVIOUS_OUTCOME. Returns
+    /// true if the expiration was extended.
+    pub fn extend_if_outcome_changed(
+        &mut self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+        previous_outcome: Option<Vec<u32>>,
+    ) -> StdResult<bool> {
+        let config = match self.anti_sniping {
+            Some(config) => config,
+            None => return Ok(false),
+        };
+        if self.status != Status::Open
+            || self.extensions >= config.max_extensions
+            || !config.is_in_window(block, &self.expiration)
+            || self.outcome_at_expiration(storage, id, block)? == previous_outcome
+        {
+            return Ok(false);
+        }
+        self.expiration = (self.expiration + config.extension)?;
+        self.extensions += 1;
+        Ok(true)
+    }
+
+    pub fn is_rejected(
+        &self,
+        storage: &dyn Storage,
+        id: u64,
+        block: &BlockInfo,
+    ) -> StdResult<bool> {
+        // If re-voting is allowed, or the votes still to be cast are
+        // not bounded by the total power, and the proposal is not
+        // expired no information is known.
+        if (self.allow_revoting || !self.bounds_votes()) && !self.expiration.is_expired(block) {
+            return Ok(false);
+        }
+        if self.is_runoff_pending()
+            || (self.has_multiple_winners() && !self.expiration.is_expired(block))
+        {
             return Ok(false);
         }
 
-        let vote_result = self.calculate_vote_result()?;
+        let vote_result = self.calculate_vote_result(storage, id)?;
         match vote_result {
             // Proposal is rejected if there is a tie, and either the proposal is expired or
             // there is no voting power left.
             VoteResult::Tie => {
                 let rejected =
-                    self.expiration.is_expired(block) || self.total_power == self.votes.total();
+                    self.expiration.is_expired(block) || self.total_power == self.power_cast;
                 Ok(rejected)
             }
+            VoteResult::MultipleWinners(winners) => Ok(self.expiration.is_expired(block)
+                && (winners.is_empty()
+                    || !does_vote_count_pass(
+                        self.power_cast,
+                        self.total_power,
+                        self.voting_strategy.get_quorum(),
+                    ))),
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
-                        self.votes.total(),
+                        self.power_cast,
                         self.total_power,
                         self.voting_strategy.get_quorum(),
                     ),
@@ -172,7 +365,7 @@ impl MultipleChoiceProposal {
                         // If the proposal is not expired and the leading choice is None and it cannot
                         // possibly be outwon by any other choices, the proposal is rejected.
                         if winning_choice.option_type == MultipleChoiceOptionType::None {
-                            return self.is_choice_unbeatable(&winning_choice);
+                            return self.is_choice_unbeatable(storage, id, &winning_choice);
                         }
                         Ok(false)
                     }
@@ -183,60 +376,133 @@ impl MultipleChoiceProposal {
         }
     }
 
-    /// Find the option with the highest vote weight, and note if there is a tie.
-    pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
+    /// Find the option with the highest vote weight, and note if there
+    /// is a tie, or find every winning option under strategies with
+    /// more than one winner. Only the leading options of the proposal
+    /// are loaded.
+    pub fn calculate_vote_result(&self, storage: &dyn Storage, id: u64) -> StdResult<VoteResult> {
+        if let Some(top_n) = self.top_n {
+            return Ok(VoteResult::MultipleWinners(
+                self.top_n_winners(storage, id, top_n)?,
+            ));
+        }
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
-                // We expect to have at least 3 vote weights
-                if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
-                    let top_choices: Vec<(usize, &Uint128)> = self
-                        .votes
-                        .vote_weights
-                        .iter()
-                        .enumerate()
-                        .filter(|x| x.1 == max_weight)
-                        .collect();
-
-                    // If more than one choice has the highest number of votes, we have a tie.
-                    if top_choices.len() > 1 {
-                        return Ok(VoteResult::Tie);
+            VotingStrategy::InstantRunoff { .. } => {
+                match self.runoff.as_ref().and_then(|runoff| runoff.winner) {
+                    Some(winner) => Ok(VoteResult::SingleWinner(
+                        OPTIONS.load(storage, (id, winner))?,
+                    )),
+                    None => Ok(VoteResult::Tie),
+                }
+            }
+            VotingStrategy::Allocation {
+                winners: AllocationWinners::AboveThreshold { threshold },
+                ..
+            } => {
+                let mut winners = vec![];
+                // Options are ranked by votes, so once one falls below
+                // the threshold every option after it does too.
+                for option in ranked_options(storage, id, None)? {
+                    let option = option?;
+                    if !does_vote_count_pass(option.vote_count, self.power_cast, threshold) {
+                        break;
                     }
-
-                    match top_choices.first() {
-                        Some(winning_choice) => {
-                            return Ok(VoteResult::SingleWinner(
-                                self.choices[winning_choice.0].clone(),
-                            ));
-                        }
-                        None => {
-                            return Err(StdError::generic_err("no votes found"));
-                        }
+                    if option.option_type == MultipleChoiceOptionType::Standard {
+                        winners.push(option);
                     }
                 }
-                Err(StdError::not_found("max vote weight"))
+                winners.sort_by_key(|winner| winner.index);
+                Ok(VoteResult::MultipleWinners(winners))
+            }
+            // Approval and allocation votes are tallied the same way,
+            // with each voter's power counted towards every option they
+            // select.
+            VotingStrategy::SingleChoice { .. }
+            | VotingStrategy::Approval { .. }
+            | VotingStrategy::Allocation { .. } => {
+                let leaders = ranked_options(storage, id, None)?
+                    .take(2)
+                    .collect::<StdResult<Vec<_>>>()?;
+                match leaders.as_slice() {
+                    // If more than one choice has the highest number of votes, we have a tie.
+                    [first, second] if first.vote_count == second.vote_count => Ok(VoteResult::Tie),
+                    [winning_choice, ..] => Ok(VoteResult::SingleWinner(winning_choice.clone())),
+                    [] => Err(StdError::not_found("max vote weight")),
+                }
+            }
+        }
+    }
+
+    /// True if this is an instant runoff proposal whose runoff has not
+    /// yet been run.
+    pub(crate) fn is_runoff_pending(&self) -> bool {
+        matches!(self.voting_strategy, VotingStrategy::InstantRunoff { .. })
+            && self.top_n.is_none()
+            && self.runoff.is_none()
+    }
+
+    /// True if the votes on this proposal can not exceed its total
+    /// power, so that the outcome may be known before it expires.
+    fn bounds_votes(&self) -> bool {
+        match self.power_transform {
+            Some(transform) => transform.bounds_votes(),
+            None => true,
+        }
+    }
+
+    /// True if more than one option may win this proposal.
+    fn has_multiple_winners(&self) -> bool {
+        self.top_n.is_some()
+            || matches!(
+                self.voting_strategy,
+                VotingStrategy::Allocation {
+                    winners: AllocationWinners::AboveThreshold { .. },
+                    ..
+                }
+            )
+    }
+
+    /// The options winning under TOP_N in rank order.
+    fn top_n_winners(
+        &self,
+        storage: &dyn Storage,
+        id: u64,
+        top_n: TopN,
+    ) -> StdResult<Vec<CheckedMultipleChoiceOption>> {
+        let mut winners = vec![];
+        // Ties are broken in favour of the option listed first.
+        for option in ranked_options(storage, id, None)? {
+            let option = option?;
+            if winners.len() == top_n.winners as usize
+                || option.vote_count.is_zero()
+                || !does_vote_count_pass(option.vote_count, self.power_cast, top_n.min_votes)
+            {
+                break;
+            }
+            if option.option_type == MultipleChoiceOptionType::Standard {
+                winners.push(option);
             }
         }
+        Ok(winners)
     }
 
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
     fn is_choice_unbeatable(
         &self,
+        storage: &dyn Storage,
+        id: u64,
         winning_choice: &CheckedMultipleChoiceOption,
     ) -> StdResult<bool> {
-        let winning_choice_power = self.votes.vote_weights[winning_choice.index as usize];
-        if let Some(second_choice_power) = self
-            .votes
-            .vote_weights
-            .iter()
-            .filter(|&x| x < &winning_choice_power)
-            .max_by(|&a, &b| a.cmp(b))
+        let winning_choice_power = winning_choice.vote_count;
+        if let Some(second_choice) = ranked_options(storage, id, Some(winning_choice.index))?.next()
         {
+            let second_choice_power = second_choice?.vote_count;
             // Check if the remaining vote power can be used to overtake the current winning choice.
-            let remaining_vote_power = self.total_power - self.votes.total();
+            let remaining_vote_power = self.total_power.saturating_sub(self.power_cast);
             match winning_choice.option_type {
                 MultipleChoiceOptionType::Standard => {
-                    if winning_choice_power > *second_choice_power + remaining_vote_power {
+                    if winning_choice_power > second_choice_power + remaining_vote_power {
                         return Ok(true);
                     }
                 }
@@ -244,7 +510,7 @@ impl MultipleChoiceProposal {
                     // If the winning choice is None, and we can at most achieve a tie,
                     // this choice is unbeatable because a tie will also fail the proposal. This is why we check for '>=' in this case
                     // rather than '>'.
-                    if winning_choice_power >= *second_choice_power + remaining_vote_power {
+                    if winning_choice_power >= second_choice_power + remaining_vote_power {
                         return Ok(true);
                     }
                 }
@@ -260,8 +526,12 @@ impl MultipleChoiceProposal {
 mod tests {
     use super::*;
 
-    use cosmwasm_std::testing::mock_env;
-    use dao_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+    use dao_voting::multiple_choice::{
+        MultipleChoiceOption, MultipleChoiceOptions, MultipleChoiceVotes,
+    };
+
+    use crate::options::save_options;
 
     fn create_proposal(
         block: &BlockInfo,
@@ -270,7 +540,7 @@ mod tests {
         total_power: Uint128,
         is_expired: bool,
         allow_revoting: bool,
-    ) -> MultipleChoiceProposal {
+    ) -> (MockStorage, MultipleChoiceProposal) {
         // The last option that gets added in into_checked is always the none of the above option
         let options = vec![
             MultipleChoiceOption {
@@ -292,21 +562,45 @@ mod tests {
         };
 
         let mc_options = MultipleChoiceOptions { options };
-        MultipleChoiceProposal {
+        // The last option that gets added in into_checked is always the none of the above option
+        let choices: Vec<CheckedMultipleChoiceOption> = mc_options
+            .into_checked()
+            .unwrap()
+            .options
+            .into_iter()
+            .map(|option| CheckedMultipleChoiceOption {
+                vote_count: votes.vote_weights[option.index as usize],
+                ..option
+            })
+            .collect();
+        let mut storage = MockStorage::default();
+        save_options(&mut storage, 1, &choices).unwrap();
+        let proposal = MultipleChoiceProposal {
             title: "A simple text proposal".to_string(),
             description: "A simple text proposal".to_string(),
             proposer: Addr::unchecked("CREATOR"),
             start_height: mock_env().block.height,
             expiration,
-            // The last option that gets added in into_checked is always the none of the above option
-            choices: mc_options.into_checked().unwrap().options,
+            num_choices: choices.len() as u32,
             status: Status::Open,
             voting_strategy,
             total_power,
-            votes,
+            power_cast: votes.total(),
             allow_revoting,
             min_voting_period: None,
-        }
+            anti_sniping: None,
+            extensions: 0,
+            execute_at: None,
+            top_n: None,
+            runoff: None,
+            voting_period: None,
+            power_transform: None,
+        };
+        (storage, proposal)
+    }
+
+    fn vote_count(storage: &dyn Storage, option: u32) -> Uint128 {
+        OPTIONS.load(storage, (1, option)).unwrap().vote_count
     }
 
     #[test]
@@ -318,9 +612,10 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -330,13 +625,14 @@ mod tests {
         );
 
         // Quorum was met and all votes were cast, should be passed.
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(0), Uint128::new(1)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -346,13 +642,14 @@ mod tests {
         );
 
         // Quorum was met but none of the above won, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -362,13 +659,14 @@ mod tests {
         );
 
         // Quorum was not met and is not expired, should be open.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -378,13 +676,14 @@ mod tests {
         );
 
         // Quorum was not met and it is expired, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -394,13 +693,14 @@ mod tests {
         );
 
         // Quorum was met but it is a tie and expired, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -410,8 +710,8 @@ mod tests {
         );
 
         // Quorum was met but it is a tie but not expired and still voting power remains, should be open.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -425,9 +725,10 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -437,13 +738,14 @@ mod tests {
         );
 
         // Quorum was met and all votes were cast, should be passed.
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(0), Uint128::new(1)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -453,13 +755,14 @@ mod tests {
         );
 
         // Quorum was met but none of the above won, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -469,13 +772,14 @@ mod tests {
         );
 
         // Quorum was not met and is not expired, should be open.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -485,13 +789,14 @@ mod tests {
         );
 
         // Quorum was not met and it is expired, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -501,13 +806,14 @@ mod tests {
         );
 
         // Quorum was met but it is a tie and expired, should be rejected.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(50), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -517,8 +823,8 @@ mod tests {
         );
 
         // Quorum was met but it is a tie but not expired and still voting power remains, should be open.
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -531,8 +837,9 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(0), Uint128::new(50), Uint128::new(500)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -542,8 +849,8 @@ mod tests {
         );
 
         // Quorum was met but none of the above is winning, but it also can't be beat (only a tie at best), should be rejected
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -556,8 +863,9 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(10), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -567,8 +875,8 @@ mod tests {
         );
 
         // Quorum was met and proposal expired, should pass
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         // High Precision rounding
         let voting_strategy = VotingStrategy::SingleChoice {
@@ -579,8 +887,9 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(999999), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -590,8 +899,8 @@ mod tests {
         );
 
         // Quorum was not met and expired, should reject
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         // High Precision rounding
         let voting_strategy = VotingStrategy::SingleChoice {
@@ -602,8 +911,9 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(9888889), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -613,8 +923,8 @@ mod tests {
         );
 
         // Quorum was not met and expired, should reject
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -627,8 +937,9 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(7), Uint128::new(0), Uint128::new(6)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes.clone(),
@@ -638,10 +949,10 @@ mod tests {
         );
 
         // Should pass if expired
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -651,8 +962,8 @@ mod tests {
         );
 
         // Should pass if not expired
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -664,8 +975,9 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(7), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes.clone(),
@@ -675,10 +987,10 @@ mod tests {
         );
 
         // Should pass if majority voted
-        assert!(prop.is_passed(&env.block).unwrap());
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -688,8 +1000,8 @@ mod tests {
         );
 
         // Shouldn't pass if only half voted
-        assert!(!prop.is_passed(&env.block).unwrap());
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -702,9 +1014,10 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(6), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes.clone(),
@@ -713,9 +1026,9 @@ mod tests {
             true,
         );
         // Quorum reached, but proposal is still active => no pass
-        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -724,7 +1037,7 @@ mod tests {
             true,
         );
         // Quorum reached & proposal has expired => pass
-        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -737,9 +1050,10 @@ mod tests {
         };
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(5), Uint128::new(5), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes.clone(),
@@ -748,12 +1062,12 @@ mod tests {
             true,
         );
         // Everyone voted and proposal is in a tie...
-        assert_eq!(prop.total_power, prop.votes.total());
-        assert_eq!(prop.votes.vote_weights[0], prop.votes.vote_weights[1]);
+        assert_eq!(prop.total_power, prop.power_cast);
+        assert_eq!(vote_count(&storage, 0), vote_count(&storage, 1));
         // ... but proposal is still active => no rejection
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -762,8 +1076,8 @@ mod tests {
             true,
         );
         // Proposal has expired and ended in a tie => rejection
-        assert_eq!(prop.votes.vote_weights[0], prop.votes.vote_weights[1]);
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert_eq!(vote_count(&storage, 0), vote_count(&storage, 1));
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -779,9 +1093,10 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(81), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes.clone(),
@@ -790,9 +1105,9 @@ mod tests {
             true,
         );
         // Quorum reached, but proposal is still active => no pass
-        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_passed(&storage, 1, &env.block).unwrap());
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -801,7 +1116,7 @@ mod tests {
             true,
         );
         // Quorum reached & proposal has expired => pass
-        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(prop.is_passed(&storage, 1, &env.block).unwrap());
     }
 
     #[test]
@@ -817,9 +1132,10 @@ mod tests {
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(90), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy.clone(),
             votes,
@@ -828,13 +1144,14 @@ mod tests {
             true,
         );
         // Quorum reached, but proposal is still active => no rejection
-        assert!(!prop.is_rejected(&env.block).unwrap());
+        assert!(!prop.is_rejected(&storage, 1, &env.block).unwrap());
 
         let votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(50), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
         };
 
-        let prop = create_proposal(
+        let (storage, prop) = create_proposal(
             &env.block,
             voting_strategy,
             votes,
@@ -843,6 +1160,65 @@ mod tests {
             true,
         );
         // No quorum reached & proposal has expired => rejection
-        assert!(prop.is_rejected(&env.block).unwrap());
+        assert!(prop.is_rejected(&storage, 1, &env.block).unwrap());
+    }
+
+    #[test]
+    fn test_extend_if_outcome_changed() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::SingleChoice {
+            quorum: dao_voting::threshold::PercentageThreshold::Percent(
+                cosmwasm_std::Decimal::percent(10),
+            ),
+        };
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(1), Uint128::new(0), Uint128::new(0)],
+            power_cast: None,
+        };
+        let (storage, mut prop) = create_proposal(
+            &env.block,
+            voting_strategy,
+            votes,
+            Uint128::new(10),
+            false,
+            false,
+        );
+        let original_expiration = prop.expiration;
+        assert_eq!(
+            prop.outcome_at_expiration(&storage, 1, &env.block).unwrap(),
+            Some(vec![0])
+        );
+
+        // Without anti-sniping nothing happens.
+        assert!(!prop
+            .extend_if_outcome_changed(&storage, 1, &env.block, None)
+            .unwrap());
+
+        prop.anti_sniping = Some(AntiSnipingConfig {
+            window: cw_utils::Duration::Height(10),
+            extension: cw_utils::Duration::Height(3),
+            max_extensions: 1,
+        });
+
+        // Same winner, no extension.
+        assert!(!prop
+            .extend_if_outcome_changed(&storage, 1, &env.block, Some(vec![0]))
+            .unwrap());
+
+        // Different winner, extension.
+        assert!(prop
+            .extend_if_outcome_changed(&storage, 1, &env.block, Some(vec![1]))
+            .unwrap());
+        assert_eq!(
+            prop.expiration,
+            (original_expiration + cw_utils::Duration::Height(3)).unwrap()
+        );
+        assert_eq!(prop.extensions, 1);
+
+        // Max extensions reached.
+        assert!(!prop
+            .extend_if_outcome_changed(&storage, 1, &env.block, None)
+            .unwrap());
+        assert_eq!(prop.extensions, 1);
     }
 }
@@ -1,8 +1,11 @@
-use crate::{proposal::MultipleChoiceProposal, state::Config};
+use crate::{
+    proposal::MultipleChoiceProposal,
+    state::{Ballot, Config},
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
-use dao_voting::multiple_choice::MultipleChoiceVote;
+use dao_voting::multiple_choice::{CheckedMultipleChoiceOption, MultipleChoiceVote};
 
 #[cw_serde]
 pub struct ProposalListResponse {
@@ -16,6 +19,11 @@ pub struct ProposalResponse {
     pub proposal: MultipleChoiceProposal,
 }
 
+#[cw_serde]
+pub struct OptionListResponse {
+    pub options: Vec<CheckedMultipleChoiceOption>,
+}
+
 /// Information about a vote that was cast.
 #[cw_serde]
 pub struct VoteInfo {
@@ -27,6 +35,9 @@ pub struct VoteInfo {
     pub power: Uint128,
     /// The rationale behind the vote.
     pub rationale: Option<String>,
+    /// The voting power of the voter's delegators cast along with
+    /// their vote.
+    pub delegated_power: Uint128,
 }
 
 #[cw_serde]
@@ -39,6 +50,20 @@ pub struct VoteListResponse {
     pub votes: Vec<VoteInfo>,
 }
 
+/// A ballot cast by a voter, returned by `ListVotesByVoter`.
+#[cw_serde]
+pub struct VoterBallotInfo {
+    /// The proposal the ballot was cast on.
+    pub proposal_id: u64,
+    /// The ballot, including its rationale.
+    pub ballot: Ballot,
+}
+
+#[cw_serde]
+pub struct VoterBallotListResponse {
+    pub ballots: Vec<VoterBallotInfo>,
+}
+
 #[cw_serde]
 pub struct VoterResponse {
     pub weight: Option<Uint128>,
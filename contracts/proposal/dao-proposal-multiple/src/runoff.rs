@@ -0,0 +1,289 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+
+use crate::{
+    proposal::MultipleChoiceProposal,
+    state::{BALLOTS, RUNOFF_TALLIES},
+};
+
+/// The result of an instant runoff.
+#[cw_serde]
+pub struct Runoff {
+    /// The options eliminated at the end of each round before the
+    /// final one, in order of rounds.
+    pub eliminations: Vec<Vec<u32>>,
+    /// The power counted towards each option in the final round,
+    /// indexed by option. Each ballot counts towards its highest
+    /// ranked option that has not been eliminated.
+    pub votes: Vec<Uint128>,
+    /// The winning option, or None if no ballots were cast or every
+    /// remaining option tied.
+    pub winner: Option<u32>,
+}
+
+/// The progress of a runoff being tallied with `TallyRunoff`.
+#[cw_serde]
+pub struct RunoffTally {
+    /// The options eliminated at the end of each completed round.
+    pub eliminations: Vec<Vec<u32>>,
+    /// The power counted towards each option in the current round.
+    pub votes: Vec<Uint128>,
+    /// The last voter whose ballot was counted in the current round.
+    pub last_voter: Option<Addr>,
+}
+
+/// How a round of a runoff ends.
+enum RoundEnd {
+    /// The runoff is decided, with the winner if there is one.
+    Decided(Option<u32>),
+    /// The runoff continues without these options.
+    Eliminated(Vec<u32>),
+}
+
+/// Runs an instant runoff between NUM_CHOICES options over BALLOTS,
+/// each a ranking of options and the power behind it.
+///
+/// An option wins once it has a majority of the power still counted
+/// in a round. Otherwise every option that can no longer win is
+/// eliminated, or if none can be ruled out, the option with the least
+/// power, with ties broken by eliminating the option listed last. If
+/// every remaining option ties there is no winner.
+pub fn instant_runoff(num_choices: usize, ballots: &[(Vec<u32>, Uint128)]) -> Runoff {
+    let mut eliminations = vec![];
+    loop {
+        let eliminated = eliminated_options(num_choices, &eliminations);
+        let mut votes = vec![Uint128::zero(); num_choices];
+        for (ranking, power) in ballots {
+            count_ballot(&mut votes, &eliminated, ranking, *power);
+        }
+        match end_round(&votes, &eliminated) {
+            RoundEnd::Decided(winner) => {
+                return Runoff {
+                    eliminations,
+                    votes,
+                    winner,
+                }
+            }
+            RoundEnd::Eliminated(options) => eliminations.push(options),
+        }
+    }
+}
+
+/// The options eliminated by ELIMINATIONS, indexed by option.
+fn eliminated_options(num_choices: usize, eliminations: &[Vec<u32>]) -> Vec<bool> {
+    let mut eliminated = vec![false; num_choices];
+    for option in eliminations.iter().flatten() {
+        eliminated[*option as usize] = true;
+    }
+    eliminated
+}
+
+/// Counts POWER towards the highest ranked option of RANKING that has
+/// not been eliminated.
+fn count_ballot(votes: &mut [Uint128], eliminated: &[bool], ranking: &[u32], power: Uint128) {
+    if let Some(option) = ranking.iter().find(|option| !eliminated[**option as usize]) {
+        votes[*option as usize] += power;
+    }
+}
+
+/// Ends a round with VOTES counted.
+fn end_round(votes: &[Uint128], eliminated: &[bool]) -> RoundEnd {
+    let counted: Uint128 = votes.iter().sum();
+    // The remaining options from least to most power, with ties
+    // ordered so that the option listed last comes first.
+    let mut remaining: Vec<usize> = (0..votes.len()).filter(|i| !eliminated[*i]).collect();
+    remaining.sort_by(|a, b| votes[*a].cmp(&votes[*b]).then(b.cmp(a)));
+    let (least, most) = match (remaining.first(), remaining.last()) {
+        (Some(least), Some(most)) => (*least, *most),
+        _ => return RoundEnd::Decided(None),
+    };
+
+    if counted.is_zero() || votes[most] == votes[least] {
+        return RoundEnd::Decided(None);
+    }
+    // A majority is held by a single option, which sorts last.
+    if votes[most].full_mul(2u64) > counted.into() {
+        return RoundEnd::Decided(Some(most as u32));
+    }
+
+    // If the options with the least power together have less than
+    // the next option, none of them can win, as even with all of
+    // their ballots transferred they would trail it. The largest such
+    // group is eliminated, which removes every option without votes
+    // at once. Otherwise the option with the least power is.
+    let mut losers = 1;
+    let mut trailing = Uint128::zero();
+    for (i, option) in remaining.iter().enumerate().take(remaining.len() - 1) {
+        trailing += votes[*option];
+        if trailing < votes[remaining[i + 1]] {
+            losers = i + 1;
+        }
+    }
+    let mut losers: Vec<u32> = remaining[..losers].iter().map(|i| *i as u32).collect();
+    losers.sort_unstable();
+    RoundEnd::Eliminated(losers)
+}
+
+/// Runs the instant runoff for a proposal from its ballots. This
+/// loads every ballot, so it is only used by queries.
+pub fn proposal_runoff(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    prop: &MultipleChoiceProposal,
+) -> StdResult<Runoff> {
+    let ballots = BALLOTS
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(_, ballot)| {
+                (
+                    ballot.vote.option_ids().collect(),
+                    ballot.power + ballot.delegated_power,
+                )
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(instant_runoff(prop.num_choices as usize, &ballots))
+}
+
+/// Counts up to LIMIT ballots towards the current round of the runoff
+/// of a proposal whose voting has closed, ending the round once every
+/// ballot has been counted. Sets the proposal's runoff once a round
+/// decides it.
+pub fn tally_runoff_page(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    prop: &mut MultipleChoiceProposal,
+    limit: u64,
+) -> StdResult<()> {
+    // An empty page would end the round early.
+    let limit = limit.max(1);
+    let num_choices = prop.num_choices as usize;
+    let mut tally = RUNOFF_TALLIES
+        .may_load(storage, proposal_id)?
+        .unwrap_or_else(|| RunoffTally {
+            eliminations: vec![],
+            votes: vec![Uint128::zero(); num_choices],
+            last_voter: None,
+        });
+    let eliminated = eliminated_options(num_choices, &tally.eliminations);
+
+    let ballots = BALLOTS
+        .prefix(proposal_id)
+        .range(
+            storage,
+            tally.last_voter.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, ballot) in &ballots {
+        let ranking: Vec<u32> = ballot.vote.option_ids().collect();
+        count_ballot(
+            &mut tally.votes,
+            &eliminated,
+            &ranking,
+            ballot.power + ballot.delegated_power,
+        );
+    }
+
+    // A short page means every ballot has been counted.
+    if ballots.len() as u64 == limit {
+        tally.last_voter = ballots.last().map(|(voter, _)| voter.clone());
+        return RUNOFF_TALLIES.save(storage, proposal_id, &tally);
+    }
+    let votes = std::mem::replace(&mut tally.votes, vec![Uint128::zero(); num_choices]);
+    tally.last_voter = None;
+    match end_round(&votes, &eliminated) {
+        RoundEnd::Decided(winner) => {
+            prop.runoff = Some(Runoff {
+                eliminations: tally.eliminations,
+                votes,
+                winner,
+            });
+            RUNOFF_TALLIES.remove(storage, proposal_id);
+            Ok(())
+        }
+        RoundEnd::Eliminated(options) => {
+            tally.eliminations.push(options);
+            RUNOFF_TALLIES.save(storage, proposal_id, &tally)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ballots(ballots: &[(&[u32], u128)]) -> Vec<(Vec<u32>, Uint128)> {
+        ballots
+            .iter()
+            .map(|(ranking, power)| (ranking.to_vec(), Uint128::new(*power)))
+            .collect()
+    }
+
+    fn votes(votes: &[u128]) -> Vec<Uint128> {
+        votes.iter().map(|v| Uint128::new(*v)).collect()
+    }
+
+    #[test]
+    fn test_majority_in_first_round() {
+        let runoff = instant_runoff(3, &ballots(&[(&[0], 6), (&[1, 0], 4)]));
+        assert_eq!(runoff.winner, Some(0));
+        assert!(runoff.eliminations.is_empty());
+        assert_eq!(runoff.votes, votes(&[6, 4, 0]));
+    }
+
+    #[test]
+    fn test_eliminations() {
+        // Option 0 leads on first preferences, but option 1 wins
+        // once option 2 is eliminated and its ballots transfer.
+        // Option 3 has no votes, so is eliminated in the same round.
+        let runoff = instant_runoff(4, &ballots(&[(&[0], 40), (&[1, 0], 35), (&[2, 1], 25)]));
+        assert_eq!(runoff.winner, Some(1));
+        assert_eq!(runoff.eliminations, vec![vec![2, 3]]);
+        assert_eq!(runoff.votes, votes(&[40, 60, 0, 0]));
+    }
+
+    #[test]
+    fn test_options_without_votes() {
+        // Every option without votes is eliminated in the first
+        // round. Options 1 and 2 tie, so only option 2 may be ruled
+        // out in the next.
+        let runoff = instant_runoff(100, &ballots(&[(&[0], 5), (&[1], 4), (&[2, 1], 4)]));
+        assert_eq!(runoff.winner, Some(1));
+        assert_eq!(
+            runoff.eliminations,
+            vec![(3..100).collect::<Vec<u32>>(), vec![2]]
+        );
+        assert_eq!(runoff.votes[..3], votes(&[5, 8, 0]));
+    }
+
+    #[test]
+    fn test_exhausted_ballots() {
+        // Ballots with no remaining options are not counted, so 5
+        // of the 9 power still counted is a majority.
+        let runoff = instant_runoff(3, &ballots(&[(&[0], 5), (&[1], 4), (&[2], 3)]));
+        assert_eq!(runoff.winner, Some(0));
+        assert_eq!(runoff.eliminations, vec![vec![2]]);
+        assert_eq!(runoff.votes, votes(&[5, 4, 0]));
+    }
+
+    #[test]
+    fn test_ties() {
+        // Ties for last place eliminate the option listed last.
+        let runoff = instant_runoff(3, &ballots(&[(&[0], 2), (&[1], 1), (&[2, 0], 1)]));
+        assert_eq!(runoff.eliminations, vec![vec![2]]);
+        assert_eq!(runoff.winner, Some(0));
+
+        // A tie between every remaining option has no winner.
+        let runoff = instant_runoff(2, &ballots(&[(&[0], 5), (&[1], 5)]));
+        assert_eq!(runoff.winner, None);
+        assert!(runoff.eliminations.is_empty());
+
+        let runoff = instant_runoff(3, &[]);
+        assert_eq!(runoff.winner, None);
+    }
+}
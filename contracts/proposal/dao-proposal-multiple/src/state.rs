@@ -1,12 +1,15 @@
-use crate::proposal::MultipleChoiceProposal;
+use crate::{proposal::MultipleChoiceProposal, runoff::RunoffTally};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_hooks::Hooks;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use dao_voting::{
-    multiple_choice::{MultipleChoiceVote, VotingStrategy},
+    anti_sniping::AntiSnipingConfig,
+    multiple_choice::{CheckedMultipleChoiceOption, MultipleChoiceVote, VotingStrategy},
+    power_transform::PowerTransform,
     pre_propose::ProposalCreationPolicy,
+    voting_period::VotingPeriodChoice,
 };
 
 /// The proposal module's configuration.
@@ -43,6 +46,27 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, votes cast shortly before a proposal's expiration
+    /// that change its outcome will extend its voting period. If
+    /// the key is missing (i.e. the config was saved by an older
+    /// version of this module), we deserialize into None.
+    #[serde(default)]
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// If set, the votes of delegates in this delegation registry
+    /// are counted on behalf of their delegators who do not vote
+    /// themselves. If the key is missing, we deserialize into None.
+    #[serde(default)]
+    pub delegation_registry: Option<Addr>,
+    /// If set, the transformation applied to voters' voting power
+    /// before it is tallied. If the key is missing, we deserialize
+    /// into None.
+    #[serde(default)]
+    pub power_transform: Option<PowerTransform>,
+    /// If set, the voting periods proposers may choose and the
+    /// quorums proposals with short voting periods require. If the
+    /// key is missing, we deserialize into None.
+    #[serde(default)]
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 // Each ballot stores a chosen vote and corresponding voting power and rationale.
@@ -54,13 +78,46 @@ pub struct Ballot {
     pub vote: MultipleChoiceVote,
     /// An optional rationale for why this vote was cast.
     pub rationale: Option<String>,
+    /// The voting power of the voter's delegators cast along with
+    /// this ballot. Ballots saved by older versions of this module
+    /// deserialize into zero.
+    #[serde(default)]
+    pub delegated_power: Uint128,
+}
+
+/// A record of a delegator's voting power having been cast by their
+/// delegate.
+#[cw_serde]
+pub struct DelegatedBallot {
+    /// The delegate whose vote the power was cast with.
+    pub delegate: Addr,
+    /// The delegator's voting power.
+    pub power: Uint128,
 }
 
 /// The current top level config for the module.
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, MultipleChoiceProposal> = Map::new("proposals");
+/// Maps (proposal_id, option index) to the option and its tally.
+/// Options are kept apart from their proposals so that the cost of a
+/// vote depends on the options it selects and not on the number of
+/// options a proposal has.
+pub const OPTIONS: Map<(u64, u32), CheckedMultipleChoiceOption> = Map::new("options");
+/// Index over `OPTIONS` by vote count, mapping (proposal_id,
+/// (vote_count, u32::MAX - index)) to the option's index, so that a
+/// proposal's leading options may be found without loading all of
+/// them.
+pub const OPTIONS_BY_VOTES: Map<(u64, (u128, u32)), u32> = Map::new("options_by_votes");
 pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
+/// Index over `BALLOTS` by voter so that the proposals an address has
+/// voted on may be listed without scanning every proposal.
+pub const VOTER_BALLOTS: Map<(&Addr, u64), Empty> = Map::new("voter_ballots");
+/// Maps (proposal_id, delegator) to the delegate who cast the
+/// delegator's voting power on the proposal.
+pub const DELEGATED_BALLOTS: Map<(u64, &Addr), DelegatedBallot> = Map::new("delegated_ballots");
+/// Maps proposal IDs to the progress of runoffs being tallied.
+pub const RUNOFF_TALLIES: Map<u64, RunoffTally> = Map::new("runoff_tallies");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
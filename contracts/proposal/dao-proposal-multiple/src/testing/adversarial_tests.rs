@@ -6,6 +6,7 @@ use crate::testing::instantiate::{
 };
 use crate::testing::queries::{
     query_balance_cw20, query_dao_token, query_multiple_proposal_module, query_proposal,
+    query_vote_weights,
 };
 use crate::testing::tests::{get_pre_propose_info, ALTERNATIVE_ADDR, CREATOR_ADDR};
 use crate::ContractError;
@@ -112,7 +113,7 @@ fn test_execute_proposal_rejected_closed() {
     assert_eq!(prop.proposal.status, Status::Open);
 
     // Vote on both options to reject the proposal
-    let vote = MultipleChoiceVote { option_id: 0 };
+    let vote = MultipleChoiceVote::single(0);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         proposal_module.clone(),
@@ -125,7 +126,7 @@ fn test_execute_proposal_rejected_closed() {
     )
     .unwrap();
 
-    let vote = MultipleChoiceVote { option_id: 1 };
+    let vote = MultipleChoiceVote::single(1);
     app.execute_contract(
         Addr::unchecked(ALTERNATIVE_ADDR),
         proposal_module.clone(),
@@ -198,13 +199,13 @@ fn test_execute_proposal_more_than_once() {
     app.update_block(next_block);
 
     // get the proposal to pass
-    let vote = MultipleChoiceVote { option_id: 0 };
+    let vote = MultipleChoiceVote::single(0);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id,
-            vote,
+            vote: vote.clone(),
             rationale: None,
         },
         &[],
@@ -280,6 +281,10 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
             false,
         ),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_multiple_staked_balances_governance(
@@ -337,7 +342,7 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
 
     app.update_block(next_block);
 
-    let vote = MultipleChoiceVote { option_id: 0 };
+    let vote = MultipleChoiceVote::single(0);
 
     // someone votes enough to pass the proposal
     app.execute_contract(
@@ -357,12 +362,13 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
     // assert proposal is passed with expected votes
     let prop = query_proposal(&app, &proposal_module, proposal_id);
     assert_eq!(prop.proposal.status, Status::Passed);
-    assert_eq!(prop.proposal.votes.vote_weights[0], Uint128::new(100000000));
-    assert_eq!(prop.proposal.votes.vote_weights[1], Uint128::new(0));
+    let vote_weights = query_vote_weights(&app, &proposal_module, proposal_id);
+    assert_eq!(vote_weights[0], Uint128::new(100000000));
+    assert_eq!(vote_weights[1], Uint128::new(0));
 
     // someone wakes up and casts their vote to express their
     // opinion (not affecting the result of proposal)
-    let vote = MultipleChoiceVote { option_id: 1 };
+    let vote = MultipleChoiceVote::single(1);
     app.execute_contract(
         Addr::unchecked(ALTERNATIVE_ADDR),
         proposal_module.clone(),
@@ -380,8 +386,9 @@ pub fn test_allow_voting_after_proposal_execution_pre_expiration_cw20() {
     // assert proposal is passed with expected votes
     let prop = query_proposal(&app, &proposal_module, proposal_id);
     assert_eq!(prop.proposal.status, Status::Passed);
-    assert_eq!(prop.proposal.votes.vote_weights[0], Uint128::new(100000000));
-    assert_eq!(prop.proposal.votes.vote_weights[1], Uint128::new(50000000));
+    let vote_weights = query_vote_weights(&app, &proposal_module, proposal_id);
+    assert_eq!(vote_weights[0], Uint128::new(100000000));
+    assert_eq!(vote_weights[1], Uint128::new(50000000));
 
     // execute the proposal expecting
     app.execute_contract(
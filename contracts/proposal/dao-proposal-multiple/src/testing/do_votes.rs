@@ -129,7 +129,11 @@ where
         allow_revoting: false,
         voting_strategy,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info,
+        voting_period_choice: None,
     };
 
     let governance_addr = setup_governance(&mut app, instantiate, Some(initial_balances));
@@ -212,6 +216,9 @@ where
                 title: "A simple text proposal".to_string(),
                 description: "This is a simple text proposal".to_string(),
                 choices: mc_options,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
         },
         &funds,
@@ -232,7 +239,7 @@ where
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: position,
+                vote: position.clone(),
                 rationale: None,
             },
             &[],
@@ -274,6 +281,7 @@ where
                             // expected voting power.
                             _ => weight,
                         },
+                        delegated_power: Uint128::zero(),
                         rationale: None,
                     }),
                 };
@@ -333,7 +341,7 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -349,7 +357,7 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -370,7 +378,7 @@ where
     do_test_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 10 },
+            position: MultipleChoiceVote::single(10),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::No,
         }],
@@ -390,7 +398,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(u128::max_value()),
             should_execute: ShouldExecute::Yes,
         }],
@@ -406,13 +414,13 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bob".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(u128::max_value() - 1),
                 should_execute: ShouldExecute::Yes,
             },
@@ -434,13 +442,13 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bob".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(1),
                 should_execute: ShouldExecute::Yes,
             },
@@ -461,7 +469,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 }, // the last index is none of the above
+            position: MultipleChoiceVote::single(2), // the last index is none of the above
             weight: Uint128::new(u64::max_value().into()),
             should_execute: ShouldExecute::Yes,
         }],
@@ -477,7 +485,7 @@ where
         do_votes(
             vec![TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 2 },
+                position: MultipleChoiceVote::single(2),
                 weight: Uint128::new(u64::max_value().into()),
                 should_execute: ShouldExecute::Yes,
             }],
@@ -502,7 +510,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(1),
             should_execute: ShouldExecute::Yes,
         }],
@@ -517,7 +525,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -534,7 +542,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 1 },
+            position: MultipleChoiceVote::single(1),
             weight: Uint128::new(9999999),
             should_execute: ShouldExecute::Yes,
         }],
@@ -550,7 +558,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(1),
             should_execute: ShouldExecute::Yes,
         }],
@@ -571,13 +579,13 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(2),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(2),
                 should_execute: ShouldExecute::No,
             },
@@ -607,13 +615,13 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
@@ -631,13 +639,13 @@ where
         vec![
             TestMultipleChoiceVote {
                 voter: "bluenote".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
@@ -658,7 +666,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(60),
             should_execute: ShouldExecute::Yes,
         }],
@@ -674,7 +682,7 @@ where
     do_votes(
         vec![TestMultipleChoiceVote {
             voter: "bluenote".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(60),
             should_execute: ShouldExecute::Yes,
         }],
@@ -717,7 +725,7 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("zero_{idx}"),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -726,7 +734,7 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("one_{idx}"),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -736,7 +744,7 @@ where
             .enumerate()
             .map(|(idx, weight)| TestMultipleChoiceVote {
                 voter: format!("none_{idx}"),
-                position: MultipleChoiceVote { option_id: 2 },
+                position: MultipleChoiceVote::single(2),
                 weight: Uint128::new(weight as u128),
                 should_execute: ShouldExecute::Meh,
             });
@@ -73,6 +73,9 @@ pub fn make_proposal(
                     description: "description".to_string(),
                     choices,
                     proposer: None,
+                    execute_at: None,
+                    top_n: None,
+                    voting_period: None,
                 },
                 &[],
             )
@@ -86,6 +89,9 @@ pub fn make_proposal(
                         title: "title".to_string(),
                         description: "description".to_string(),
                         choices,
+                        execute_at: None,
+                        top_n: None,
+                        voting_period: None,
                     },
                 },
                 &funds,
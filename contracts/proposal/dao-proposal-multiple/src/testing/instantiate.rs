@@ -67,6 +67,10 @@ pub fn _get_default_token_dao_proposal_module_instantiate(app: &mut App) -> Inst
             false,
         ),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        voting_period_choice: None,
     }
 }
 
@@ -83,6 +87,10 @@ fn _get_default_non_token_dao_proposal_module_instantiate(app: &mut App) -> Inst
         allow_revoting: false,
         pre_propose_info: get_pre_propose_info(app, None, false),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        voting_period_choice: None,
     }
 }
 
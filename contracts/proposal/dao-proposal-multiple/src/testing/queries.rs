@@ -3,11 +3,13 @@ use cw_hooks::HooksResponse;
 use cw_multi_test::App;
 use dao_core::state::{ProposalModule, ProposalModuleStatus};
 use dao_pre_propose_multiple as cppm;
-use dao_voting::pre_propose::ProposalCreationPolicy;
+use dao_voting::{
+    multiple_choice::CheckedMultipleChoiceOption, pre_propose::ProposalCreationPolicy,
+};
 
 use crate::{
     msg::QueryMsg,
-    query::{ProposalListResponse, ProposalResponse},
+    query::{OptionListResponse, ProposalListResponse, ProposalResponse},
     state::Config,
 };
 
@@ -86,6 +88,33 @@ pub fn query_list_proposals(
         .unwrap()
 }
 
+pub fn query_options(
+    app: &App,
+    proposal_multiple: &Addr,
+    proposal_id: u64,
+) -> Vec<CheckedMultipleChoiceOption> {
+    let response: OptionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_multiple,
+            &QueryMsg::ListOptions {
+                proposal_id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    response.options
+}
+
+/// The tally of each option of a proposal, in option order.
+pub fn query_vote_weights(app: &App, proposal_multiple: &Addr, proposal_id: u64) -> Vec<Uint128> {
+    query_options(app, proposal_multiple, proposal_id)
+        .into_iter()
+        .map(|option| option.vote_count)
+        .collect()
+}
+
 pub fn query_proposal_hooks(app: &App, proposal_multiple: &Addr) -> HooksResponse {
     app.wrap()
         .query_wasm_smart(proposal_multiple, &QueryMsg::ProposalHooks {})
@@ -1,21 +1,27 @@
-use cosmwasm_std::{to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Timestamp, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Empty, Timestamp, Uint128, WasmMsg,
+};
 use cw20::Cw20Coin;
 use cw_denom::{CheckedDenom, UncheckedDenom};
 use cw_hooks::HooksResponse;
 use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_core::state::ProposalModule;
 use dao_interface::{Admin, ModuleInstantiateInfo};
 use dao_voting::{
+    anti_sniping::AntiSnipingConfig,
     deposit::{CheckedDepositInfo, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    error::VotingError,
     multiple_choice::{
-        CheckedMultipleChoiceOption, MultipleChoiceOption, MultipleChoiceOptionType,
-        MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
-        MAX_NUM_CHOICES,
+        AllocationWinners, CheckedMultipleChoiceOption, MultipleChoiceOption,
+        MultipleChoiceOptionType, MultipleChoiceOptions, MultipleChoiceVote, TopN, VotingStrategy,
+        MAX_NUM_CHOICES, MAX_RUNOFF_CHOICES,
     },
+    power_transform::PowerTransform,
     pre_propose::PreProposeInfo,
     status::Status,
-    threshold::{PercentageThreshold, Threshold},
+    threshold::{PercentageThreshold, Threshold, ThresholdError},
+    voting_period::{QuorumStep, VotingPeriodChoice},
 };
 use dao_voting_cw20_staked::msg::ActiveThreshold;
 use std::panic;
@@ -23,8 +29,12 @@ use std::panic;
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::MultipleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::Config,
+    query::{
+        OptionListResponse, ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse,
+        VoteResponse, VoterBallotInfo, VoterBallotListResponse,
+    },
+    runoff::Runoff,
+    state::{Ballot, Config},
     testing::{
         do_votes::do_test_votes_cw20_balances,
         execute::make_proposal,
@@ -35,8 +45,9 @@ use crate::{
         queries::{
             query_balance_cw20, query_balance_native, query_cw20_token_staking_contracts,
             query_dao_token, query_deposit_config_and_pre_propose_module, query_list_proposals,
-            query_list_proposals_reverse, query_multiple_proposal_module, query_proposal,
-            query_proposal_config, query_proposal_hooks, query_vote_hooks,
+            query_list_proposals_reverse, query_multiple_proposal_module, query_options,
+            query_proposal, query_proposal_config, query_proposal_hooks, query_vote_hooks,
+            query_vote_weights,
         },
     },
     ContractError,
@@ -44,7 +55,7 @@ use crate::{
 use dao_pre_propose_multiple as cppm;
 
 use dao_testing::{
-    contracts::{cw20_balances_voting_contract, cw20_base_contract},
+    contracts::{cw20_balances_voting_contract, cw20_base_contract, delegation_registry_contract},
     ShouldExecute,
 };
 
@@ -119,7 +130,11 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -135,6 +150,10 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        voting_period_choice: None,
     };
     assert_eq!(config, expected);
 
@@ -166,19 +185,25 @@ fn test_propose() {
         proposer: Addr::unchecked(CREATOR_ADDR),
         start_height: current_block.height,
         expiration: max_voting_period.after(&current_block),
-        choices: checked_options.options,
+        num_choices: 3,
         status: Status::Open,
         voting_strategy,
         total_power: Uint128::new(100_000_000),
-        votes: MultipleChoiceVotes {
-            vote_weights: vec![Uint128::zero(); 3],
-        },
+        power_cast: Uint128::zero(),
         allow_revoting: false,
+        anti_sniping: None,
+        extensions: 0,
+        execute_at: None,
+        top_n: None,
+        runoff: None,
+        voting_period: None,
+        power_transform: None,
         min_voting_period: None,
     };
 
     assert_eq!(created.proposal, expected);
     assert_eq!(created.id, 1u64);
+    assert_eq!(query_options(&app, &govmod, 1), checked_options.options);
 }
 
 #[test]
@@ -194,11 +219,15 @@ fn test_propose_wrong_num_choices() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy: voting_strategy.clone(),
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -209,11 +238,15 @@ fn test_propose_wrong_num_choices() {
     let expected = Config {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         dao: core_addr,
         voting_strategy,
+        voting_period_choice: None,
     };
     assert_eq!(config, expected);
 
@@ -229,6 +262,9 @@ fn test_propose_wrong_num_choices() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     );
@@ -255,6 +291,9 @@ fn test_propose_wrong_num_choices() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     );
@@ -272,9 +311,13 @@ fn test_proposal_count_initialized_to_zero() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
     let core_addr = instantiate_with_staked_balances_governance(&mut app, msg, None);
 
@@ -308,7 +351,11 @@ fn test_no_early_pass_with_min_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -358,6 +405,9 @@ fn test_no_early_pass_with_min_duration() {
             description: "This is a simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -370,7 +420,7 @@ fn test_no_early_pass_with_min_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -400,9 +450,13 @@ fn test_propose_with_messages() {
         max_voting_period: Duration::Height(10),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         only_members_execute: true,
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -435,10 +489,14 @@ fn test_propose_with_messages() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period: cw_utils::Duration::Height(20),
         only_members_execute: false,
         allow_revoting: false,
         dao: "dao".to_string(),
+        voting_period_choice: None,
     };
 
     let wasm_msg = WasmMsg::Execute {
@@ -470,6 +528,9 @@ fn test_propose_with_messages() {
             description: "This is a simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -480,7 +541,7 @@ fn test_propose_with_messages() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -521,7 +582,11 @@ fn test_min_duration_units_missmatch() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -553,7 +618,11 @@ fn test_min_duration_larger_than_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -584,7 +653,11 @@ fn test_min_duration_same_as_proposal_duration() {
         only_members_execute: true,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -634,6 +707,9 @@ fn test_min_duration_same_as_proposal_duration() {
             description: "This is a simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -646,7 +722,7 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -663,7 +739,7 @@ fn test_min_duration_same_as_proposal_duration() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 2 },
+            vote: MultipleChoiceVote::single(2),
             rationale: None,
         },
         &[],
@@ -692,6 +768,9 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -705,6 +784,7 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -762,6 +842,9 @@ fn test_different_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -777,6 +860,7 @@ fn test_different_token_proposal_deposit() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -823,6 +907,9 @@ fn test_bad_token_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -838,6 +925,7 @@ fn test_bad_token_proposal_deposit() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -855,6 +943,9 @@ fn test_take_proposal_deposit() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -868,6 +959,7 @@ fn test_take_proposal_deposit() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_cw20_balances_governance(
@@ -918,6 +1010,9 @@ fn test_take_proposal_deposit() {
                     title: "title".to_string(),
                     description: "description".to_string(),
                     choices: mc_options.clone(),
+                    execute_at: None,
+                    top_n: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -962,6 +1057,9 @@ fn test_native_proposal_deposit() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: get_pre_propose_info(
             &mut app,
             Some(UncheckedDepositInfo {
@@ -973,6 +1071,7 @@ fn test_native_proposal_deposit() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1027,6 +1126,9 @@ fn test_native_proposal_deposit() {
                     title: "title".to_string(),
                     description: "description".to_string(),
                     choices: mc_options.clone(),
+                    execute_at: None,
+                    top_n: None,
+                    voting_period: None,
                 },
             },
             &[],
@@ -1060,7 +1162,7 @@ fn test_native_proposal_deposit() {
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 1 },
+                vote: MultipleChoiceVote::single(1),
                 rationale: None,
             },
             &[],
@@ -1097,7 +1199,7 @@ fn test_deposit_return_on_execute() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1158,7 +1260,7 @@ fn test_deposit_return_zero() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1203,13 +1305,13 @@ fn test_query_list_votes() {
         vec![
             TestMultipleChoiceVote {
                 voter: "blue".to_string(),
-                position: MultipleChoiceVote { option_id: 0 },
+                position: MultipleChoiceVote::single(0),
                 weight: Uint128::new(10),
                 should_execute: ShouldExecute::Yes,
             },
             TestMultipleChoiceVote {
                 voter: "note".to_string(),
-                position: MultipleChoiceVote { option_id: 1 },
+                position: MultipleChoiceVote::single(1),
                 weight: Uint128::new(20),
                 should_execute: ShouldExecute::Yes,
             },
@@ -1247,19 +1349,1357 @@ fn test_query_list_votes() {
     let expected = vec![
         VoteInfo {
             voter: Addr::unchecked("blue"),
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             power: Uint128::new(10),
+            delegated_power: Uint128::zero(),
             rationale: None,
         },
         VoteInfo {
             voter: Addr::unchecked("note"),
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote::single(1),
             power: Uint128::new(20),
+            delegated_power: Uint128::zero(),
+            rationale: None,
+        },
+    ];
+
+    assert_eq!(list_votes.votes, expected)
+}
+
+#[test]
+fn test_query_list_votes_by_voter() {
+    let (app, core_addr) = do_test_votes_cw20_balances(
+        vec![
+            TestMultipleChoiceVote {
+                voter: "blue".to_string(),
+                position: MultipleChoiceVote::single(0),
+                weight: Uint128::new(10),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestMultipleChoiceVote {
+                voter: "note".to_string(),
+                position: MultipleChoiceVote::single(1),
+                weight: Uint128::new(20),
+                should_execute: ShouldExecute::Yes,
+            },
+        ],
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        Status::Passed,
+        None,
+        None,
+        true,
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let list_votes: VoterBallotListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListVotesByVoter {
+                voter: "note".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        list_votes.ballots,
+        vec![VoterBallotInfo {
+            proposal_id: 1,
+            ballot: Ballot {
+                power: Uint128::new(20),
+                delegated_power: Uint128::zero(),
+                vote: MultipleChoiceVote::single(1),
+                rationale: None,
+            },
+        }]
+    );
+
+    let list_votes: VoterBallotListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListVotesByVoter {
+                voter: "blue".to_string(),
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(list_votes.ballots.is_empty());
+}
+
+#[test]
+fn test_delegated_voting() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            only_members_execute: false,
+            allow_revoting: true,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "note".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(30),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let registry_id = app.store_code(delegation_registry_contract());
+    let registry = app
+        .instantiate_contract(
+            registry_id,
+            core_addr.clone(),
+            &dao_delegation_registry::msg::InstantiateMsg {},
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap();
+    let config = query_proposal_config(&app, &govmod);
+    app.execute_contract(
+        core_addr,
+        govmod.clone(),
+        &ExecuteMsg::UpdateConfig {
+            voting_strategy: config.voting_strategy,
+            min_voting_period: config.min_voting_period,
+            max_voting_period: config.max_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.into_string(),
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            anti_sniping: config.anti_sniping,
+            delegation_registry: Some(registry.to_string()),
+            power_transform: None,
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    for delegator in ["blue", "note"] {
+        app.execute_contract(
+            Addr::unchecked(delegator),
+            registry.clone(),
+            &dao_delegation_registry::msg::ExecuteMsg::Delegate {
+                delegate: "zeke".to_string(),
+                proposal_module: Some(govmod.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.update_block(next_block);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+    ];
+    let proposal_id = make_proposal(&mut app, &govmod, "zeke", MultipleChoiceOptions { options });
+
+    let vote = |app: &mut App, voter: &str, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let weights = |app: &App| query_vote_weights(app, &govmod, proposal_id);
+
+    // The delegate votes for both of their delegators.
+    vote(&mut app, "zeke", 0);
+    assert_eq!(weights(&app)[0], Uint128::new(60));
+    let ballot: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id,
+                voter: "zeke".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(ballot.vote.unwrap().delegated_power, Uint128::new(30));
+
+    // A delegator voting overrides their delegate.
+    vote(&mut app, "note", 1);
+    assert_eq!(weights(&app)[0], Uint128::new(40));
+    assert_eq!(weights(&app)[1], Uint128::new(20));
+
+    // Delegated power moves with the delegate's vote.
+    vote(&mut app, "zeke", 1);
+    assert_eq!(weights(&app)[0], Uint128::zero());
+    assert_eq!(weights(&app)[1], Uint128::new(60));
+}
+
+#[test]
+fn test_execute_at() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            only_members_execute: true,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
+        },
+        Some(vec![Cw20Coin {
+            address: "blue".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.update_block(next_block);
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let propose = |app: &mut App, execute_at: Expiration| {
+        app.execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: options.clone(),
+                proposer: None,
+                execute_at: Some(execute_at),
+                top_n: None,
+                voting_period: None,
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(&mut app, Expiration::Never {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExecuteAt {}));
+
+    let execute_at = Expiration::AtHeight(app.block_info().height + 5);
+    propose(&mut app, execute_at).unwrap();
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
-    ];
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Passed
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ExecuteAtNotReached { id: 1, execute_at: e } if e == execute_at
+    ));
+
+    // Once the execution time is reached anyone may execute the
+    // proposal.
+    app.update_block(|b| b.height += 5);
+    app.execute_contract(
+        Addr::unchecked("crank"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Executed
+    );
+}
+
+#[test]
+fn test_approval_voting() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::Approval {
+            quorum: PercentageThreshold::Majority {},
+            max_selections: 2,
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.update_block(next_block);
+
+    let option = |title: &str| MultipleChoiceOption {
+        description: "candidate".to_string(),
+        msgs: vec![],
+        title: title.to_string(),
+    };
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "committee election".to_string(),
+            description: "elect two members".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![option("a"), option("b"), option("c")],
+            },
+            proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote = |app: &mut App, voter: &str, option_id: u32, additional_option_ids: Vec<u32>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id,
+                    additional_option_ids,
+                    weights: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    // More than `max_selections` options.
+    let err: ContractError = vote(&mut app, "ekez", 0, vec![1, 2])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+    // "None of the above" along with a candidate.
+    let err: ContractError = vote(&mut app, "ekez", 0, vec![3])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+
+    vote(&mut app, "ekez", 0, vec![1]).unwrap();
+    vote(&mut app, "blue", 1, vec![2]).unwrap();
+    vote(&mut app, "zeke", 2, vec![]).unwrap();
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(
+        query_vote_weights(&app, &govmod, 1),
+        vec![
+            Uint128::new(40),
+            Uint128::new(75),
+            Uint128::new(60),
+            Uint128::zero()
+        ]
+    );
+    // Each voter's power counts towards turnout once.
+    assert_eq!(proposal.power_cast, Uint128::new(100));
+
+    // Revoting replaces the whole set of approvals.
+    let err: ContractError = vote(&mut app, "ekez", 0, vec![1])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+    vote(&mut app, "ekez", 0, vec![]).unwrap();
+
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(
+        query_vote_weights(&app, &govmod, 1),
+        vec![
+            Uint128::new(40),
+            Uint128::new(35),
+            Uint128::new(60),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.power_cast, Uint128::new(100));
+    assert_eq!(proposal.status, Status::Passed);
+    let vote: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "blue".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vote.vote.unwrap().vote,
+        MultipleChoiceVote {
+            option_id: 1,
+            additional_option_ids: vec![2],
+            weights: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_allocation_voting() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::Allocation {
+            quorum: PercentageThreshold::Majority {},
+            winners: AllocationWinners::AboveThreshold {
+                threshold: PercentageThreshold::Percent(Decimal::percent(30)),
+            },
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(300),
+        }],
+    }))
+    .unwrap();
+    app.update_block(next_block);
+
+    let grant = |recipient: &str| MultipleChoiceOption {
+        description: format!("fund {recipient}"),
+        msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(100),
+            }],
+        })],
+        title: recipient.to_string(),
+    };
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod.clone(),
+        &ExecuteMsg::Propose {
+            title: "budget".to_string(),
+            description: "split the budget".to_string(),
+            choices: MultipleChoiceOptions {
+                options: vec![grant("zeke"), grant("oscar"), grant("wes")],
+            },
+            proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote = |app: &mut App, voter: &str, allocation: Vec<(u32, u64)>| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: allocation[0].0,
+                    additional_option_ids: allocation[1..].iter().map(|(id, _)| *id).collect(),
+                    weights: allocation
+                        .iter()
+                        .map(|(_, percent)| Decimal::percent(*percent))
+                        .collect(),
+                },
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    // Weights must sum to 100%.
+    let err: ContractError = vote(&mut app, "ekez", vec![(0, 50), (1, 40)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+
+    vote(&mut app, "ekez", vec![(0, 50), (1, 50)]).unwrap();
+    vote(&mut app, "blue", vec![(0, 25), (3, 75)]).unwrap();
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(
+        query_vote_weights(&app, &govmod, 1),
+        vec![
+            Uint128::new(40),
+            Uint128::new(30),
+            Uint128::zero(),
+            Uint128::new(30)
+        ]
+    );
+    // Winners are only decided once voting closes.
+    assert_eq!(proposal.status, Status::Open);
+
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+
+    // Both options allocated at least 30% of the power cast are
+    // funded. "None of the above" never wins.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance = |app: &App, addr: &str| app.wrap().query_balance(addr, "ujuno").unwrap().amount;
+    assert_eq!(balance(&app, "zeke"), Uint128::new(100));
+    assert_eq!(balance(&app, "oscar"), Uint128::new(100));
+    assert_eq!(balance(&app, "wes"), Uint128::zero());
+    assert_eq!(balance(&app, core_addr.as_str()), Uint128::new(100));
+}
+
+#[test]
+fn test_top_n_winners() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let voters = [
+        ("ekez", 30),
+        ("blue", 30),
+        ("zeke", 25),
+        ("oscar", 10),
+        ("wes", 5),
+    ];
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(
+            voters
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        ),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(400),
+        }],
+    }))
+    .unwrap();
+    app.update_block(next_block);
+
+    let grant = |recipient: &str| MultipleChoiceOption {
+        description: format!("fund {recipient}"),
+        msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(100),
+            }],
+        })],
+        title: recipient.to_string(),
+    };
+    let propose = |app: &mut App, top_n: TopN| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "grant round".to_string(),
+                description: "fund the top applications".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![grant("app0"), grant("app1"), grant("app2"), grant("app3")],
+                },
+                proposer: None,
+                execute_at: None,
+                top_n: Some(top_n),
+                voting_period: None,
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(
+        &mut app,
+        TopN {
+            winners: 0,
+            min_votes: PercentageThreshold::Percent(Decimal::zero()),
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ThresholdError(ThresholdError::ZeroWinners {})
+    ));
+
+    propose(
+        &mut app,
+        TopN {
+            winners: 3,
+            min_votes: PercentageThreshold::Percent(Decimal::percent(10)),
+        },
+    )
+    .unwrap();
+    for (voter, option_id) in [
+        ("ekez", 2),
+        ("blue", 1),
+        ("zeke", 0),
+        ("wes", 3),
+        ("oscar", 4),
+    ] {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    // Winners are only decided once voting closes.
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Open);
+
+    // app1 and app2 tie for first place, and the tie is broken in
+    // favour of app1 as it is listed first. app3 has too few votes to
+    // win, and "None of the above" never wins.
+    let ranking: OptionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &govmod,
+            &QueryMsg::ListOptionsByVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: Some(3),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        ranking
+            .options
+            .iter()
+            .map(|option| option.index)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 0]
+    );
+
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let balance = |app: &App, addr: &str| app.wrap().query_balance(addr, "ujuno").unwrap().amount;
+    for (recipient, amount) in [("app0", 100), ("app1", 100), ("app2", 100), ("app3", 0)] {
+        assert_eq!(balance(&app, recipient), Uint128::new(amount));
+    }
+
+    // app3 leads, app1 and app2 tie for second place, and app0 has
+    // 5% of the votes.
+    let vote_and_execute = |app: &mut App, proposal_id: u64| {
+        for (voter, option_id) in [
+            ("zeke", 3),
+            ("oscar", 3),
+            ("blue", 1),
+            ("ekez", 2),
+            ("wes", 0),
+        ] {
+            app.execute_contract(
+                Addr::unchecked(voter),
+                govmod.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote: MultipleChoiceVote::single(option_id),
+                    rationale: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        app.update_block(|b| b.height += 10);
+        let res = app
+            .execute_contract(
+                Addr::unchecked("ekez"),
+                govmod.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .unwrap();
+        // The recipients of the executed grants, in execution order.
+        res.events
+            .iter()
+            .filter(|event| event.ty == "transfer")
+            .flat_map(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .filter(|attr| attr.key == "recipient")
+                    .map(|attr| attr.value.clone())
+            })
+            .collect::<Vec<_>>()
+    };
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: core_addr.to_string(),
+        amount: vec![Coin {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(500),
+        }],
+    }))
+    .unwrap();
+
+    // Only one of the tied options fits in the top two, and the tie
+    // is broken in favour of the lower index. Messages are executed
+    // in rank order rather than index order.
+    propose(
+        &mut app,
+        TopN {
+            winners: 2,
+            min_votes: PercentageThreshold::Percent(Decimal::percent(1)),
+        },
+    )
+    .unwrap();
+    assert_eq!(vote_and_execute(&mut app, 2), vec!["app3", "app1"]);
+
+    // Options below the minimum do not win even when there are fewer
+    // winners than allowed.
+    propose(
+        &mut app,
+        TopN {
+            winners: 4,
+            min_votes: PercentageThreshold::Percent(Decimal::percent(10)),
+        },
+    )
+    .unwrap();
+    assert_eq!(vote_and_execute(&mut app, 3), vec!["app3", "app1", "app2"]);
+    for (recipient, amount) in [("app0", 100), ("app1", 300), ("app2", 200), ("app3", 200)] {
+        assert_eq!(balance(&app, recipient), Uint128::new(amount));
+    }
+}
+
+#[test]
+fn test_instant_runoff() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::InstantRunoff {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let voters = [("ekez", 40), ("blue", 35), ("zeke", 25)];
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(
+            voters
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        ),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.update_block(next_block);
+
+    let option = |title: &str| MultipleChoiceOption {
+        description: format!("choose {title}"),
+        msgs: vec![],
+        title: title.to_string(),
+    };
+    let propose = |app: &mut App, options: Vec<MultipleChoiceOption>, top_n: Option<TopN>| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "mascot".to_string(),
+                description: "pick the DAO's mascot".to_string(),
+                choices: MultipleChoiceOptions { options },
+                proposer: None,
+                execute_at: None,
+                top_n,
+                voting_period: None,
+            },
+            &[],
+        )
+    };
+
+    // Runoffs select a single winner, so may not be combined with
+    // top-n winners.
+    let mascots = vec![option("owl"), option("fox"), option("bee")];
+    let err: ContractError = propose(
+        &mut app,
+        mascots.clone(),
+        Some(TopN {
+            winners: 2,
+            min_votes: PercentageThreshold::Percent(Decimal::percent(10)),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(err, ContractError::TopNWithInstantRunoff {}));
+
+    // Runoffs may be held between at most `MAX_RUNOFF_CHOICES`
+    // options.
+    let many = |n: u32| (0..n).map(|i| option(&i.to_string())).collect();
+    let err: ContractError = propose(&mut app, many(MAX_RUNOFF_CHOICES + 1), None)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::TooManyRunoffChoices {
+            max: MAX_RUNOFF_CHOICES
+        }
+    ));
+    propose(&mut app, mascots, None).unwrap();
+
+    let vote = |app: &mut App, voter: &str, ranking: &[u32]| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: ranking[0],
+                    additional_option_ids: ranking[1..].to_vec(),
+                    weights: vec![],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+    };
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote {
+                    option_id: 0,
+                    additional_option_ids: vec![1],
+                    weights: vec![Decimal::percent(50), Decimal::percent(50)],
+                },
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidVote {}));
+
+    vote(&mut app, "ekez", &[0]).unwrap();
+    vote(&mut app, "blue", &[1, 0]).unwrap();
+    vote(&mut app, "zeke", &[2, 1]).unwrap();
+
+    // Only first preferences are tallied, and the proposal remains
+    // open until voting closes even though every member has voted.
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(
+        query_vote_weights(&app, &govmod, 1),
+        vec![
+            Uint128::new(40),
+            Uint128::new(35),
+            Uint128::new(25),
+            Uint128::zero()
+        ]
+    );
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(proposal.runoff, None);
+
+    let tally = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("crank"),
+            govmod.clone(),
+            &ExecuteMsg::TallyRunoff {
+                proposal_id: 1,
+                limit: Some(2),
+            },
+            &[],
+        )
+    };
+    let err: ContractError = tally(&mut app).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NotExpired {}));
+
+    // Once voting closes the proposal may not be executed until its
+    // runoff has been tallied.
+    app.update_block(|b| b.height += 10);
+    assert_eq!(
+        query_proposal(&app, &govmod, 1).proposal.status,
+        Status::Open
+    );
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("blue"),
+            govmod.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::RunoffNotTallied { id: 1 }));
+
+    // Each round counts the three ballots over two pages. The bee
+    // and "None of the above" are eliminated in the first round, and
+    // the fox wins the second.
+    let mut calls = 0;
+    loop {
+        calls += 1;
+        let res = tally(&mut app).unwrap();
+        let complete = res
+            .custom_attrs(1)
+            .iter()
+            .any(|attr| attr.key == "complete" && attr.value == "true");
+        if complete {
+            break;
+        }
+        assert_eq!(
+            query_proposal(&app, &govmod, 1).proposal.status,
+            Status::Open
+        );
+    }
+    assert_eq!(calls, 4);
+    let err: ContractError = tally(&mut app).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NoRunoffToTally { id: 1 }));
+
+    // The owl leads on first preferences, but the fox wins once the
+    // bee is eliminated and its ballots transfer.
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    assert_eq!(proposal.runoff.as_ref().unwrap().winner, Some(1));
+    assert_eq!(query_options(&app, &govmod, 1)[1].title, "fox");
+
+    app.execute_contract(
+        Addr::unchecked("blue"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Executed);
+
+    let runoff: Runoff = app
+        .wrap()
+        .query_wasm_smart(&govmod, &QueryMsg::Runoff { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(runoff, proposal.runoff.unwrap());
+    assert_eq!(runoff.eliminations, vec![vec![2, 3]]);
+    assert_eq!(
+        runoff.votes,
+        vec![
+            Uint128::new(40),
+            Uint128::new(60),
+            Uint128::zero(),
+            Uint128::zero()
+        ]
+    );
+}
+
+#[test]
+fn test_many_options() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: true,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.update_block(next_block);
+
+    let options = (0..200)
+        .map(|i| MultipleChoiceOption {
+            description: format!("entry {i}"),
+            msgs: vec![],
+            title: format!("entry {i}"),
+        })
+        .collect();
+    make_proposal(&mut app, &govmod, "ekez", MultipleChoiceOptions { options });
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.num_choices, 201);
+
+    let vote = |app: &mut App, voter: &str, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    vote(&mut app, "ekez", 150);
+    vote(&mut app, "blue", 199);
+    vote(&mut app, "ekez", 42);
+
+    let list_options = |query: QueryMsg| -> Vec<(u32, Uint128)> {
+        let response: OptionListResponse = app.wrap().query_wasm_smart(&govmod, &query).unwrap();
+        response
+            .options
+            .into_iter()
+            .map(|option| (option.index, option.vote_count))
+            .collect()
+    };
+    assert_eq!(
+        list_options(QueryMsg::ListOptions {
+            proposal_id: 1,
+            start_after: Some(148),
+            limit: Some(3),
+        }),
+        vec![
+            (149, Uint128::zero()),
+            (150, Uint128::zero()),
+            (151, Uint128::zero())
+        ]
+    );
+    assert_eq!(
+        list_options(QueryMsg::ListOptionsByVotes {
+            proposal_id: 1,
+            start_after: None,
+            limit: Some(3),
+        }),
+        vec![
+            (42, Uint128::new(60)),
+            (199, Uint128::new(40)),
+            (0, Uint128::zero())
+        ]
+    );
+    assert_eq!(
+        list_options(QueryMsg::ListOptionsByVotes {
+            proposal_id: 1,
+            start_after: Some(0),
+            limit: Some(2),
+        }),
+        vec![(1, Uint128::zero()), (2, Uint128::zero())]
+    );
+
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+    assert_eq!(proposal.power_cast, Uint128::new(100));
+}
+
+#[test]
+fn test_power_transform() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let instantiate = InstantiateMsg {
+        min_voting_period: None,
+        max_voting_period: Duration::Height(10),
+        only_members_execute: true,
+        allow_revoting: false,
+        voting_strategy: VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        },
+        close_proposal_on_execution_failure: false,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: Some(PowerTransform::Cap {
+            percent: Decimal::percent(20),
+        }),
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
+    };
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate.clone(),
+        Some(vec![
+            Cw20Coin {
+                address: "whale".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+    app.update_block(next_block);
+
+    let options = MultipleChoiceOptions {
+        options: vec![
+            MultipleChoiceOption {
+                description: "multiple choice option 1".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+            MultipleChoiceOption {
+                description: "multiple choice option 2".to_string(),
+                msgs: vec![],
+                title: "title".to_string(),
+            },
+        ],
+    };
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("whale"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: options.clone(),
+                proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Without the cap the whale's vote would meet quorum and pass
+    // the proposal.
+    propose(&mut app);
+    vote(&mut app, "whale", 1, 0);
+    vote(&mut app, "blue", 1, 1);
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.total_power, Uint128::new(100));
+    assert_eq!(
+        query_vote_weights(&app, &govmod, 1)[..2],
+        [Uint128::new(20), Uint128::new(10)]
+    );
+    assert_eq!(proposal.status, Status::Open);
+
+    app.execute_contract(
+        core_addr.clone(),
+        govmod.clone(),
+        &ExecuteMsg::UpdateConfig {
+            voting_strategy: instantiate.voting_strategy,
+            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            only_members_execute: true,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: Some(PowerTransform::SquareRoot {}),
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    assert_eq!(list_votes.votes, expected)
+    propose(&mut app);
+    vote(&mut app, "whale", 2, 0);
+    let proposal = query_proposal(&app, &govmod, 2).proposal;
+    assert_eq!(proposal.total_power, Uint128::new(10));
+    assert_eq!(query_vote_weights(&app, &govmod, 2)[0], Uint128::new(9));
+    // The transformed votes on a proposal may exceed its transformed
+    // total power, so it can not pass before it expires.
+    assert_eq!(
+        query_proposal(&app, &govmod, 2).proposal.status,
+        Status::Open
+    );
+    vote(&mut app, "blue", 2, 1);
+    let proposal = query_proposal(&app, &govmod, 2).proposal;
+    assert_eq!(proposal.power_cast, Uint128::new(12));
+    assert_eq!(proposal.status, Status::Open);
+    app.update_block(|b| b.height += 10);
+    let proposal = query_proposal(&app, &govmod, 2).proposal;
+    assert_eq!(proposal.status, Status::Passed);
 }
 
 #[test]
@@ -1268,7 +2708,7 @@ fn test_invalid_quorum() {
     let (_app, _core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1288,7 +2728,7 @@ fn test_cant_vote_executed_or_closed() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1325,7 +2765,7 @@ fn test_cant_vote_executed_or_closed() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -1336,7 +2776,7 @@ fn test_cant_vote_executed_or_closed() {
     let (mut app, _core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1364,7 +2804,7 @@ fn test_cant_vote_executed_or_closed() {
         govmod,
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -1382,6 +2822,9 @@ fn test_cant_propose_zero_power() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
@@ -1395,6 +2838,7 @@ fn test_cant_propose_zero_power() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_cw20_balances_governance(
@@ -1466,6 +2910,9 @@ fn test_cant_propose_zero_power() {
                 title: "A simple text proposal".to_string(),
                 description: "A simple text proposal".to_string(),
                 choices: mc_options.clone(),
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
         },
         &[],
@@ -1481,6 +2928,9 @@ fn test_cant_propose_zero_power() {
                 title: "A simple text proposal".to_string(),
                 description: "A simple text proposal".to_string(),
                 choices: mc_options,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
         },
         &[],
@@ -1494,7 +2944,7 @@ fn test_cant_vote_not_registered() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1527,7 +2977,7 @@ fn test_cant_vote_not_registered() {
             govmod,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote::single(0),
                 rationale: None,
             },
             &[],
@@ -1554,11 +3004,15 @@ fn test_cant_execute_not_member() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: true,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1595,6 +3049,9 @@ fn test_cant_execute_not_member() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -1606,7 +3063,7 @@ fn test_cant_execute_not_member() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -1644,11 +3101,15 @@ fn test_cant_execute_not_member_when_proposal_created() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: true,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1685,6 +3146,9 @@ fn test_cant_execute_not_member_when_proposal_created() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -1696,7 +3160,7 @@ fn test_cant_execute_not_member_when_proposal_created() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -1765,7 +3229,11 @@ fn test_open_proposal_submission() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: get_pre_propose_info(&mut app, None, true),
+        voting_period_choice: None,
     };
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
     let govmod = query_multiple_proposal_module(&app, &core_addr);
@@ -1800,12 +3268,27 @@ fn test_open_proposal_submission() {
         expiration: max_voting_period.after(&current_block),
         min_voting_period: None,
         allow_revoting: false,
+        anti_sniping: None,
+        extensions: 0,
+        execute_at: None,
+        top_n: None,
+        runoff: None,
+        voting_period: None,
+        power_transform: None,
         total_power: Uint128::new(100_000_000),
         status: Status::Open,
         voting_strategy: VotingStrategy::SingleChoice {
             quorum: PercentageThreshold::Percent(Decimal::percent(100)),
         },
-        choices: vec![
+        num_choices: 3,
+        power_cast: Uint128::zero(),
+    };
+
+    assert_eq!(created.proposal, expected);
+    assert_eq!(created.id, 1u64);
+    assert_eq!(
+        query_options(&app, &govmod, 1),
+        vec![
             CheckedMultipleChoiceOption {
                 description: "multiple choice option 1".to_string(),
                 msgs: vec![],
@@ -1830,14 +3313,8 @@ fn test_open_proposal_submission() {
                 index: 2,
                 title: "None of the above".to_string(),
             },
-        ],
-        votes: MultipleChoiceVotes {
-            vote_weights: vec![Uint128::zero(); 3],
-        },
-    };
-
-    assert_eq!(created.proposal, expected);
-    assert_eq!(created.id, 1u64);
+        ]
+    );
 }
 
 #[test]
@@ -1845,7 +3322,7 @@ fn test_close_open_proposal() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1913,7 +3390,7 @@ fn test_no_refund_failed_proposal() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1971,7 +3448,7 @@ fn test_zero_deposit() {
     do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -1993,7 +3470,7 @@ fn test_deposit_return_on_close() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2054,11 +3531,15 @@ fn test_execute_expired_proposal() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -2108,6 +3589,9 @@ fn test_execute_expired_proposal() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -2118,7 +3602,7 @@ fn test_execute_expired_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -2173,7 +3657,7 @@ fn test_update_config() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 0 },
+            position: MultipleChoiceVote::single(0),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2217,10 +3701,14 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
             dao: dao.to_string(),
+            voting_period_choice: None,
         },
         &[],
     )
@@ -2236,10 +3724,14 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
             dao: Addr::unchecked(CREATOR_ADDR).to_string(),
+            voting_period_choice: None,
         },
         &[],
     )
@@ -2253,10 +3745,14 @@ fn test_update_config() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period: cw_utils::Duration::Height(10),
         only_members_execute: false,
         allow_revoting: false,
         dao: Addr::unchecked(CREATOR_ADDR),
+        voting_period_choice: None,
     };
     assert_eq!(govmod_config, expected);
 
@@ -2271,10 +3767,14 @@ fn test_update_config() {
             },
             min_voting_period: None,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             max_voting_period: cw_utils::Duration::Height(10),
             only_members_execute: false,
             allow_revoting: false,
             dao: Addr::unchecked(CREATOR_ADDR).to_string(),
+            voting_period_choice: None,
         },
         &[],
     )
@@ -2286,7 +3786,7 @@ fn test_no_return_if_no_refunds() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -2345,11 +3845,15 @@ fn test_query_list_proposals() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy: voting_strategy.clone(),
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
     let gov_addr = instantiate_with_staked_balances_governance(
         &mut app,
@@ -2398,6 +3902,9 @@ fn test_query_list_proposals() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2411,7 +3918,6 @@ fn test_query_list_proposals() {
     proposals_backward.proposals.reverse();
 
     assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
-    let checked_options = mc_options.into_checked().unwrap();
     let current_block = app.block_info();
     let expected = ProposalResponse {
         id: 1,
@@ -2421,18 +3927,27 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
-            choices: checked_options.options.clone(),
+            num_choices: 3,
             status: Status::Open,
             voting_strategy: voting_strategy.clone(),
             total_power: Uint128::new(100),
-            votes: MultipleChoiceVotes {
-                vote_weights: vec![Uint128::zero(); 3],
-            },
+            power_cast: Uint128::zero(),
             allow_revoting: false,
+            anti_sniping: None,
+            extensions: 0,
+            execute_at: None,
+            top_n: None,
+            runoff: None,
+            voting_period: None,
+            power_transform: None,
             min_voting_period: None,
         },
     };
     assert_eq!(proposals_forward.proposals[0], expected);
+    assert_eq!(
+        query_options(&app, &govmod, 1),
+        mc_options.into_checked().unwrap().options
+    );
 
     // Get proposals (3, 5]
     let proposals_forward: ProposalListResponse =
@@ -2449,14 +3964,19 @@ fn test_query_list_proposals() {
             proposer: Addr::unchecked(CREATOR_ADDR),
             start_height: current_block.height,
             expiration: max_voting_period.after(&current_block),
-            choices: checked_options.options,
+            num_choices: 3,
             status: Status::Open,
             voting_strategy,
             total_power: Uint128::new(100),
-            votes: MultipleChoiceVotes {
-                vote_weights: vec![Uint128::zero(); 3],
-            },
+            power_cast: Uint128::zero(),
             allow_revoting: false,
+            anti_sniping: None,
+            extensions: 0,
+            execute_at: None,
+            top_n: None,
+            runoff: None,
+            voting_period: None,
+            power_transform: None,
             min_voting_period: None,
         },
     };
@@ -2478,11 +3998,15 @@ fn test_hooks() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
@@ -2604,11 +4128,15 @@ fn test_active_threshold_absolute() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staking_active_threshold(
@@ -2667,6 +4195,9 @@ fn test_active_threshold_absolute() {
                 description: "This is a simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2692,6 +4223,9 @@ fn test_active_threshold_absolute() {
                 description: "This is a simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2715,6 +4249,9 @@ fn test_active_threshold_absolute() {
                 description: "This is a simple text proposal".to_string(),
                 choices: mc_options,
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2731,11 +4268,15 @@ fn test_active_threshold_percent() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     // 20% needed to be active, 20% of 100000000 is 20000000
@@ -2795,6 +4336,9 @@ fn test_active_threshold_percent() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2820,6 +4364,9 @@ fn test_active_threshold_percent() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2843,6 +4390,9 @@ fn test_active_threshold_percent() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options,
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2859,11 +4409,15 @@ fn test_active_threshold_none() {
     let instantiate = InstantiateMsg {
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         max_voting_period,
         only_members_execute: false,
         allow_revoting: false,
         voting_strategy,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr =
@@ -2926,6 +4480,9 @@ fn test_active_threshold_none() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options.clone(),
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2951,6 +4508,9 @@ fn test_active_threshold_none() {
                 description: "A simple text proposal".to_string(),
                 choices: mc_options,
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -2973,7 +4533,11 @@ fn test_revoting() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3012,6 +4576,9 @@ fn test_revoting() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3023,7 +4590,7 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3036,7 +4603,7 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote::single(1),
             rationale: None,
         },
         &[],
@@ -3050,11 +4617,11 @@ fn test_revoting() {
     let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
     assert_eq!(proposal.proposal.status, Status::Open);
     assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
+        query_vote_weights(&app, &govmod, 1)[0],
         Uint128::new(100_000_000),
     );
     assert_eq!(
-        proposal.proposal.votes.vote_weights[1],
+        query_vote_weights(&app, &govmod, 1)[1],
         Uint128::new(100_000_000),
     );
 
@@ -3068,7 +4635,7 @@ fn test_revoting() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3081,10 +4648,343 @@ fn test_revoting() {
     let proposal: ProposalResponse = query_proposal(&app, &govmod, 1);
     assert_eq!(proposal.proposal.status, Status::Passed);
     assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
+        query_vote_weights(&app, &govmod, 1)[0],
         Uint128::new(200_000_000),
     );
-    assert_eq!(proposal.proposal.votes.vote_weights[1], Uint128::new(0),);
+    assert_eq!(query_vote_weights(&app, &govmod, 1)[1], Uint128::new(0),);
+}
+
+/// Tests that a vote in the final blocks of a proposal's voting
+/// period that changes the winning option extends the proposal's
+/// expiration, up to the maximum number of extensions.
+#[test]
+fn test_anti_sniping_extension() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            only_members_execute: false,
+            allow_revoting: true,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: Some(AntiSnipingConfig {
+                window: Duration::Height(3),
+                extension: Duration::Height(5),
+                max_extensions: 1,
+            }),
+            delegation_registry: None,
+            power_transform: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-3".to_string(),
+                amount: Uint128::new(150_000_000),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+    ];
+    let proposal_id = make_proposal(&mut app, &govmod, "a-1", MultipleChoiceOptions { options });
+    let start = app.block_info().height;
+
+    let vote = |app: &mut App, voter: &str, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Option 2 is winning before the window.
+    vote(&mut app, "a-1", 0);
+    vote(&mut app, "a-3", 1);
+
+    // In the window, a-2 flips the outcome to option 1.
+    app.update_block(|b| b.height += 8);
+    vote(&mut app, "a-2", 0);
+
+    let proposal = query_proposal(&app, &govmod, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.extensions, 1);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(start + 15)
+    );
+
+    // Flipping again does not extend as the maximum number of
+    // extensions has been reached.
+    app.update_block(|b| b.height += 5);
+    vote(&mut app, "a-2", 1);
+
+    let proposal = query_proposal(&app, &govmod, proposal_id);
+    assert_eq!(proposal.proposal.extensions, 1);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(start + 15)
+    );
+
+    app.update_block(|b| b.height += 2);
+    let proposal = query_proposal(&app, &govmod, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_voting_period_choice() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(100),
+            only_members_execute: false,
+            allow_revoting: false,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: Some(VotingPeriodChoice {
+                min: Duration::Height(10),
+                quorum_schedule: vec![QuorumStep {
+                    max_voting_period: Duration::Height(20),
+                    quorum: PercentageThreshold::Majority {},
+                }],
+            }),
+        },
+        None,
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, voting_period: Option<Duration>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                choices: MultipleChoiceOptions {
+                    options: vec![
+                        MultipleChoiceOption {
+                            title: "title".to_string(),
+                            description: "option 1".to_string(),
+                            msgs: vec![],
+                        },
+                        MultipleChoiceOption {
+                            title: "title".to_string(),
+                            description: "option 2".to_string(),
+                            msgs: vec![],
+                        },
+                    ],
+                },
+                proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period,
+            },
+            &[],
+        )
+    };
+    let start = app.block_info().height;
+
+    propose(&mut app, Some(Duration::Height(10))).unwrap();
+    let proposal = query_proposal(&app, &govmod, 1).proposal;
+    assert_eq!(proposal.voting_period, Some(Duration::Height(10)));
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+    assert_eq!(
+        proposal.voting_strategy,
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {},
+        }
+    );
+
+    propose(&mut app, None).unwrap();
+    let proposal = query_proposal(&app, &govmod, 2).proposal;
+    assert_eq!(proposal.voting_period, None);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 100));
+    assert_eq!(
+        proposal.voting_strategy,
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+        }
+    );
+
+    let err: ContractError = propose(&mut app, Some(Duration::Height(9)))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidVotingPeriod {})
+    ));
+
+    // A step below the strategy's quorum does not lower it.
+    app.execute_contract(
+        core_addr.clone(),
+        govmod.clone(),
+        &ExecuteMsg::UpdateConfig {
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            },
+            min_voting_period: None,
+            max_voting_period: Duration::Height(100),
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            voting_period_choice: Some(VotingPeriodChoice {
+                min: Duration::Height(10),
+                quorum_schedule: vec![QuorumStep {
+                    max_voting_period: Duration::Height(20),
+                    quorum: PercentageThreshold::Majority {},
+                }],
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    propose(&mut app, Some(Duration::Height(10))).unwrap();
+    let proposal = query_proposal(&app, &govmod, 3).proposal;
+    assert_eq!(
+        proposal.voting_strategy,
+        VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+        }
+    );
+}
+
+/// Tests that a revote in the window which changes the winning option
+/// extends the proposal, as the outcome before the revote includes
+/// the revoter's old vote.
+#[test]
+fn test_anti_sniping_revote() {
+    let mut app = App::default();
+    let _govmod_id = app.store_code(proposal_multiple_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        InstantiateMsg {
+            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            only_members_execute: false,
+            allow_revoting: true,
+            voting_strategy: VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Majority {},
+            },
+            close_proposal_on_execution_failure: false,
+            anti_sniping: Some(AntiSnipingConfig {
+                window: Duration::Height(3),
+                extension: Duration::Height(5),
+                max_extensions: 1,
+            }),
+            delegation_registry: None,
+            power_transform: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "a-1".to_string(),
+                amount: Uint128::new(100_000_000),
+            },
+            Cw20Coin {
+                address: "a-2".to_string(),
+                amount: Uint128::new(150_000_000),
+            },
+        ]),
+    );
+    let govmod = query_multiple_proposal_module(&app, &core_addr);
+
+    let options = vec![
+        MultipleChoiceOption {
+            description: "multiple choice option 1".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+        MultipleChoiceOption {
+            description: "multiple choice option 2".to_string(),
+            msgs: vec![],
+            title: "title".to_string(),
+        },
+    ];
+    let proposal_id = make_proposal(&mut app, &govmod, "a-1", MultipleChoiceOptions { options });
+    let start = app.block_info().height;
+
+    let vote = |app: &mut App, voter: &str, option_id: u32| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: MultipleChoiceVote::single(option_id),
+                rationale: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    vote(&mut app, "a-1", 0);
+    vote(&mut app, "a-2", 1);
+
+    // Option 1 leads until the last block, when a-2 revotes and
+    // option 0 takes the lead.
+    assert_eq!(
+        query_vote_weights(&app, &govmod, proposal_id)[..2],
+        [Uint128::new(100_000_000), Uint128::new(150_000_000)]
+    );
+    app.update_block(|b| b.height += 9);
+    vote(&mut app, "a-2", 0);
+
+    let proposal = query_proposal(&app, &govmod, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.extensions, 1);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(start + 15)
+    );
+    assert_eq!(
+        query_vote_weights(&app, &govmod, proposal_id)[..2],
+        [Uint128::new(250_000_000), Uint128::zero()]
+    );
 }
 
 /// Tests that revoting is stored at a per-proposal level.
@@ -3105,7 +5005,11 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3144,6 +5048,9 @@ fn test_allow_revoting_config_changes() {
             description: "A simple text proposal".to_string(),
             choices: mc_options.clone(),
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3163,6 +5070,10 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
+            voting_period_choice: None,
         },
         &[],
     )
@@ -3177,7 +5088,7 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3188,7 +5099,7 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote::single(1),
             rationale: None,
         },
         &[],
@@ -3204,6 +5115,9 @@ fn test_allow_revoting_config_changes() {
             description: "A very complex text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3214,7 +5128,7 @@ fn test_allow_revoting_config_changes() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 2,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3227,7 +5141,7 @@ fn test_allow_revoting_config_changes() {
             proposal_module,
             &ExecuteMsg::Vote {
                 proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 1 },
+                vote: MultipleChoiceVote::single(1),
                 rationale: None,
             },
             &[],
@@ -3256,7 +5170,11 @@ fn test_revoting_same_vote_twice() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3295,6 +5213,9 @@ fn test_revoting_same_vote_twice() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3306,7 +5227,7 @@ fn test_revoting_same_vote_twice() {
         proprosal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3320,7 +5241,7 @@ fn test_revoting_same_vote_twice() {
             proprosal_module,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote::single(0),
                 rationale: None,
             },
             &[],
@@ -3350,7 +5271,11 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -3389,6 +5314,9 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3400,7 +5328,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3413,7 +5341,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         proposal_module.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote::single(1),
             rationale: None,
         },
         &[],
@@ -3426,11 +5354,11 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
     let proposal: ProposalResponse = query_proposal(&app, &proposal_module, 1);
     assert_eq!(proposal.proposal.status, Status::Open);
     assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
+        query_vote_weights(&app, &proposal_module, 1)[0],
         Uint128::new(100_000_000),
     );
     assert_eq!(
-        proposal.proposal.votes.vote_weights[1],
+        query_vote_weights(&app, &proposal_module, 1)[1],
         Uint128::new(100_000_000),
     );
 
@@ -3442,10 +5370,10 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
     let err: ContractError = app
         .execute_contract(
             Addr::unchecked("a-2"),
-            proposal_module,
+            proposal_module.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 99 },
+                vote: MultipleChoiceVote::single(99),
                 rationale: None,
             },
             &[],
@@ -3455,11 +5383,11 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
         .unwrap();
     // Assert that prior votes remained the same
     assert_eq!(
-        proposal.proposal.votes.vote_weights[0],
+        query_vote_weights(&app, &proposal_module, 1)[0],
         Uint128::new(100_000_000),
     );
     assert_eq!(
-        proposal.proposal.votes.vote_weights[1],
+        query_vote_weights(&app, &proposal_module, 1)[1],
         Uint128::new(100_000_000),
     );
     assert!(matches!(err, ContractError::InvalidVote {}));
@@ -3470,7 +5398,7 @@ fn test_return_deposit_to_dao_on_proposal_failure() {
     let (mut app, core_addr) = do_test_votes_cw20_balances(
         vec![TestMultipleChoiceVote {
             voter: "blue".to_string(),
-            position: MultipleChoiceVote { option_id: 2 },
+            position: MultipleChoiceVote::single(2),
             weight: Uint128::new(10),
             should_execute: ShouldExecute::Yes,
         }],
@@ -3538,7 +5466,11 @@ fn test_close_failed_proposal() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staking_active_threshold(&mut app, instantiate, None, None);
@@ -3614,6 +5546,9 @@ fn test_close_failed_proposal() {
             description: "Burning more tokens, than dao treasury have".to_string(),
             choices: mc_options.clone(),
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3625,7 +5560,7 @@ fn test_close_failed_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3673,6 +5608,10 @@ fn test_close_failed_proposal() {
                                     allow_revoting: false,
                                     dao: original.dao.to_string(),
                                     close_proposal_on_execution_failure: false,
+                                    anti_sniping: None,
+                                    delegation_registry: None,
+                                    power_transform: None,
+                                    voting_period_choice: None,
                                 })
                                 .unwrap(),
                                 funds: vec![],
@@ -3688,6 +5627,9 @@ fn test_close_failed_proposal() {
                     ],
                 },
                 proposer: None,
+                execute_at: None,
+                top_n: None,
+                voting_period: None,
             },
             &[],
         )
@@ -3699,7 +5641,7 @@ fn test_close_failed_proposal() {
             govmod.clone(),
             &ExecuteMsg::Vote {
                 proposal_id: 2,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote::single(0),
                 rationale: None,
             },
             &[],
@@ -3725,6 +5667,9 @@ fn test_close_failed_proposal() {
             description: "Burning more tokens, than dao treasury have".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -3736,7 +5681,7 @@ fn test_close_failed_proposal() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 3,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3775,6 +5720,9 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: get_pre_propose_info(
             &mut app,
             Some(UncheckedDepositInfo {
@@ -3787,6 +5735,7 @@ fn test_no_double_refund_on_execute_fail_and_close() {
             }),
             false,
         ),
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staking_active_threshold(
@@ -3903,7 +5852,7 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: None,
         },
         &[],
@@ -3964,7 +5913,11 @@ pub fn test_not_allow_voting_on_expired_proposal() {
         },
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        delegation_registry: None,
+        power_transform: None,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        voting_period_choice: None,
     };
     let core_addr = instantiate_with_staked_balances_governance(
         &mut app,
@@ -4006,6 +5959,9 @@ pub fn test_not_allow_voting_on_expired_proposal() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -4024,7 +5980,7 @@ pub fn test_not_allow_voting_on_expired_proposal() {
             govmod,
             &ExecuteMsg::Vote {
                 proposal_id: 1,
-                vote: MultipleChoiceVote { option_id: 0 },
+                vote: MultipleChoiceVote::single(0),
                 rationale: None,
             },
             &[],
@@ -4036,7 +5992,10 @@ pub fn test_not_allow_voting_on_expired_proposal() {
     // assert the vote got rejected and did not count towards the votes
     let proposal = query_proposal(&app, &proposal_module, 1);
     assert_eq!(proposal.proposal.status, Status::Rejected);
-    assert_eq!(proposal.proposal.votes.vote_weights[0], Uint128::zero());
+    assert_eq!(
+        query_vote_weights(&app, &proposal_module, 1)[0],
+        Uint128::zero()
+    );
     assert!(matches!(err, ContractError::Expired { id: _proposal_id }));
 }
 
@@ -4055,7 +6014,11 @@ fn test_next_proposal_id() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4100,6 +6063,9 @@ fn test_next_proposal_id() {
             description: "A simple text proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -4126,7 +6092,11 @@ fn test_vote_with_rationale() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4171,6 +6141,9 @@ fn test_vote_with_rationale() {
             description: "A simple proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -4181,7 +6154,7 @@ fn test_vote_with_rationale() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: Some("I think this is a good idea".to_string()),
         },
         &[],
@@ -4222,7 +6195,11 @@ fn test_revote_with_rationale() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4267,6 +6244,9 @@ fn test_revote_with_rationale() {
             description: "A simple proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -4277,7 +6257,7 @@ fn test_revote_with_rationale() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: Some("I think this is a good idea".to_string()),
         },
         &[],
@@ -4309,7 +6289,7 @@ fn test_revote_with_rationale() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 1 },
+            vote: MultipleChoiceVote::single(1),
             rationale: Some("Nah".to_string()),
         },
         &[],
@@ -4338,7 +6318,7 @@ fn test_revote_with_rationale() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 2 },
+            vote: MultipleChoiceVote::single(2),
             rationale: None,
         },
         &[],
@@ -4376,7 +6356,11 @@ fn test_update_rationale() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            delegation_registry: None,
+            power_transform: None,
             pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -4422,6 +6406,9 @@ fn test_update_rationale() {
             description: "A simple proposal".to_string(),
             choices: mc_options,
             proposer: None,
+            execute_at: None,
+            top_n: None,
+            voting_period: None,
         },
         &[],
     )
@@ -4433,7 +6420,7 @@ fn test_update_rationale() {
         govmod.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: MultipleChoiceVote { option_id: 0 },
+            vote: MultipleChoiceVote::single(0),
             rationale: Some("I think this is a good idea".to_string()),
         },
         &[],
@@ -0,0 +1,71 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps};
+use cw_utils::Duration;
+use dao_voting::{
+    pre_propose::ProposalCreationPolicy,
+    threshold::{validate_quorum, PercentageThreshold, ThresholdError},
+};
+
+use crate::ContractError;
+
+#[cw_serde]
+pub struct UncheckedConfig {
+    /// The percentage of voting power that must vote no for a
+    /// proposal to be objected to.
+    pub objection_threshold: PercentageThreshold,
+    /// The amount of time proposals are open for objections before
+    /// passing.
+    pub voting_period: Duration,
+    /// A `dao-proposal-single` module that objected proposals may be
+    /// escalated to for a regular vote. If unset, objected proposals
+    /// are rejected.
+    pub escalation_module: Option<String>,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub objection_threshold: PercentageThreshold,
+    pub voting_period: Duration,
+    pub escalation_module: Option<Addr>,
+    pub close_proposals_on_execution_failure: bool,
+}
+
+impl UncheckedConfig {
+    pub(crate) fn into_checked(self, deps: Deps, dao: &Addr) -> Result<Config, ContractError> {
+        validate_quorum(&self.objection_threshold)?;
+        if self.objection_threshold == PercentageThreshold::Percent(Default::default()) {
+            return Err(ThresholdError::ZeroThreshold {}.into());
+        }
+        let escalation_module = self
+            .escalation_module
+            .map(|module| validate_escalation_module(deps, dao, &module))
+            .transpose()?;
+        Ok(Config {
+            objection_threshold: self.objection_threshold,
+            voting_period: self.voting_period,
+            escalation_module,
+            close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+        })
+    }
+}
+
+/// Validates that MODULE is a proposal module belonging to DAO that
+/// this module may create proposals in.
+fn validate_escalation_module(deps: Deps, dao: &Addr, module: &str) -> Result<Addr, ContractError> {
+    let module = deps.api.addr_validate(module)?;
+    let module_dao: Addr = deps
+        .querier
+        .query_wasm_smart(&module, &dao_proposal_single::msg::QueryMsg::Dao {})?;
+    if module_dao != *dao {
+        return Err(ContractError::EscalationModuleDao {});
+    }
+    let policy: ProposalCreationPolicy = deps.querier.query_wasm_smart(
+        &module,
+        &dao_proposal_single::msg::QueryMsg::ProposalCreationPolicy {},
+    )?;
+    if policy != (ProposalCreationPolicy::Anyone {}) {
+        return Err(ContractError::EscalationModulePolicy {});
+    }
+    Ok(module)
+}
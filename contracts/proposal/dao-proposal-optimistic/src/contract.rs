@@ -0,0 +1,304 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response,
+    StdResult,
+};
+
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use dao_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use dao_voting::reply::TaggedReplyId;
+use dao_voting::voting::{get_total_power, get_voting_power, Vote};
+
+use crate::config::UncheckedConfig;
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::proposal::{Proposal, ProposalResponse, Status};
+use crate::state::{next_proposal_id, CONFIG, DAO, PROPOSAL, VOTE};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-optimistic";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let config = msg.into_checked(deps.as_ref(), &info.sender)?;
+    DAO.save(deps.storage, &info.sender)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            title,
+            description,
+            msgs,
+        } => execute_propose(deps, env, info, title, description, msgs),
+        ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Escalate { proposal_id } => execute_escalate(deps, env, info, proposal_id),
+
+        ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+    }
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    let sender_voting_power = get_voting_power(deps.as_ref(), info.sender.clone(), &dao, None)?;
+    if sender_voting_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let id = next_proposal_id(deps.storage)?;
+    let total_power = get_total_power(deps.as_ref(), &dao, None)?;
+
+    let mut proposal = Proposal::new(
+        &env.block,
+        &config,
+        id,
+        title,
+        description,
+        info.sender,
+        msgs,
+        total_power,
+    );
+    proposal.update_status(&env.block);
+
+    // Proposals must be small enough to be escalated to, and queried
+    // from, the escalation module.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    PROPOSAL.save(deps.storage, id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "propose")
+        .add_attribute("proposal_id", proposal.id.to_string())
+        .add_attribute("proposer", proposal.proposer))
+}
+
+fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &DAO.load(deps.storage)?,
+        Some(proposal.start_height),
+    )?;
+    if sender_power.is_zero() {
+        Err(ContractError::ZeroVotingPower {})
+    } else if VOTE.has(deps.storage, (proposal_id, info.sender.clone())) {
+        Err(ContractError::Voted {})
+    } else if proposal.update_status(&env.block) != Status::Open {
+        Err(ContractError::NotOpen {})
+    } else {
+        VOTE.save(deps.storage, (proposal_id, info.sender.clone()), &vote)?;
+
+        proposal.votes.add_vote(vote, sender_power);
+        let status = proposal.update_status(&env.block);
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "vote")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("voter", info.sender)
+            .add_attribute("power", sender_power)
+            .add_attribute("status", format!("{status:?}")))
+    }
+}
+
+fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    let dao = DAO.load(deps.storage)?;
+    let sender_power = get_voting_power(
+        deps.as_ref(),
+        info.sender.clone(),
+        &dao,
+        Some(proposal.start_height),
+    )?;
+    if sender_power.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+
+    if let Status::Passed = proposal.update_status(&env.block) {
+        let msgs = proposal.set_executed(dao)?;
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "execute")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("executor", info.sender)
+            .add_submessage(msgs))
+    } else {
+        Err(ContractError::Unexecutable {})
+    }
+}
+
+fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    if let Status::Rejected = proposal.update_status(&env.block) {
+        proposal.set_closed();
+        PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "close")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("closer", info.sender))
+    } else {
+        Err(ContractError::Unclosable {})
+    }
+}
+
+fn execute_escalate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+    match (
+        proposal.update_status(&env.block),
+        &proposal.escalation_module,
+    ) {
+        (Status::Objected, Some(escalation_module)) => {
+            // The escalated proposal is created by the message
+            // returned here, so it will be the next proposal created
+            // in the escalation module.
+            let escalated_id: u64 = deps.querier.query_wasm_smart(
+                escalation_module,
+                &dao_proposal_single::msg::QueryMsg::NextProposalId {},
+            )?;
+            let msg = proposal.set_escalated(escalated_id)?;
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+
+            Ok(Response::default()
+                .add_attribute("method", "escalate")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("escalated_proposal_id", escalated_id.to_string())
+                .add_attribute("escalator", info.sender)
+                .add_message(msg))
+        }
+        _ => Err(ContractError::Unescalatable {}),
+    }
+}
+
+fn execute_set_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: UncheckedConfig,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao {
+        Err(ContractError::NotDao {})
+    } else {
+        let config = config.into_checked(deps.as_ref(), &dao)?;
+        CONFIG.save(deps.storage, &config)?;
+        Ok(Response::default()
+            .add_attribute("method", "update_config")
+            .add_attribute("updater", info.sender))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proposal { id } => {
+            let mut proposal = PROPOSAL.load(deps.storage, id)?;
+            proposal.update_status(&env.block);
+            to_binary(&ProposalResponse { proposal })
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let proposals = PROPOSAL
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit.unwrap_or(DEFAULT_LIMIT) as usize)
+                .map(|item| {
+                    item.map(|(_, mut proposal)| {
+                        proposal.update_status(&env.block);
+                        ProposalResponse { proposal }
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_binary(&proposals)
+        }
+        QueryMsg::Vote { proposal_id, voter } => {
+            let voter = deps.api.addr_validate(&voter)?;
+            to_binary(&VOTE.may_load(deps.storage, (proposal_id, voter))?)
+        }
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = TaggedReplyId::new(msg.id)?;
+    match repl {
+        TaggedReplyId::FailedProposalExecution(proposal_id) => {
+            let mut proposal = PROPOSAL.load(deps.storage, proposal_id)?;
+            proposal.set_execution_failed();
+            PROPOSAL.save(deps.storage, proposal_id, &proposal)?;
+            Ok(Response::default()
+                .add_attribute("proposal_execution_failed", proposal_id.to_string()))
+        }
+        // This module neither has a pre-propose module nor fires
+        // hooks as submessages.
+        _ => Err(ContractError::InvalidReplyId { id: msg.id }),
+    }
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::StdError;
+use dao_voting::{error::VotingError, reply::error::TagError, threshold::ThresholdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    Threshold(#[from] ThresholdError),
+    #[error(transparent)]
+    Voting(#[from] VotingError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+
+    #[error("non-zero voting power required to perform this action")]
+    ZeroVotingPower {},
+
+    #[error("only proposals that are in the passed state may be executed")]
+    Unexecutable {},
+
+    #[error("only rejected proposals may be closed")]
+    Unclosable {},
+
+    #[error("only objected proposals may be escalated")]
+    Unescalatable {},
+
+    #[error("only the DAO may perform this action")]
+    NotDao {},
+
+    #[error("already voted")]
+    Voted {},
+
+    #[error("only open proposals may be voted on")]
+    NotOpen {},
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+
+    #[error("the escalation module must belong to this module's DAO")]
+    EscalationModuleDao {},
+
+    #[error("the escalation module must allow anyone to create proposals")]
+    EscalationModulePolicy {},
+
+    #[error("received unexpected reply ({id})")]
+    InvalidReplyId { id: u64 },
+}
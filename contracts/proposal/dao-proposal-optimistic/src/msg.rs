@@ -0,0 +1,49 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use dao_macros::proposal_module_query;
+use dao_voting::voting::Vote;
+
+use crate::config::UncheckedConfig;
+
+pub type InstantiateMsg = UncheckedConfig;
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a proposal that will pass at the end of the voting
+    /// period unless objected to.
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Votes on a proposal. Only no votes count as objections.
+    Vote { proposal_id: u64, vote: Vote },
+    /// Executes a passed proposal.
+    Execute { proposal_id: u64 },
+    /// Closes a rejected proposal.
+    Close { proposal_id: u64 },
+    /// Escalates an objected proposal to the escalation module by
+    /// creating a proposal there with the same title, description,
+    /// and messages.
+    Escalate { proposal_id: u64 },
+    /// Updates the module's config. Only the DAO may call this
+    /// method.
+    SetConfig(UncheckedConfig),
+}
+
+#[proposal_module_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::proposal::ProposalResponse)]
+    Proposal { id: u64 },
+    #[returns(Vec<crate::proposal::ProposalResponse>)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    #[returns(Option<dao_voting::voting::Vote>)]
+    Vote { proposal_id: u64, voter: String },
+    #[returns(crate::config::Config)]
+    Config {},
+}
@@ -0,0 +1,199 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, BlockInfo, CosmosMsg, Empty, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_utils::Expiration;
+use dao_voting::{
+    reply::mask_proposal_execution_proposal_id,
+    threshold::PercentageThreshold,
+    voting::{does_vote_count_pass, Votes},
+};
+
+use crate::config::Config;
+
+#[cw_serde]
+pub struct Proposal {
+    last_status: Status,
+
+    pub id: u64,
+    pub title: String,
+    pub description: String,
+    pub proposer: Addr,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+
+    pub start_height: u64,
+    pub expiration: Expiration,
+
+    pub objection_threshold: PercentageThreshold,
+    pub escalation_module: Option<Addr>,
+    pub close_on_execution_failure: bool,
+
+    pub total_power: Uint128,
+    pub votes: Votes,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum Status {
+    /// The proposal is open for objections.
+    Open,
+    /// Objections reached the objection threshold and the proposal
+    /// has no escalation module.
+    Rejected,
+    /// Objections reached the objection threshold and the proposal
+    /// may be escalated to its escalation module.
+    Objected,
+    /// The proposal has been escalated and is being voted on as
+    /// proposal `proposal_id` in the escalation module.
+    Escalated { proposal_id: u64 },
+    /// The proposal expired without reaching the objection threshold.
+    Passed,
+    /// The proposal has been passed and executed.
+    Executed,
+    /// The proposal has been rejected and closed.
+    Closed,
+    /// The proposal's execution failed.
+    ExecutionFailed,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub proposal: Proposal,
+}
+
+fn status(block: &BlockInfo, proposal: &Proposal) -> Status {
+    match proposal.last_status {
+        Status::Open => {
+            if proposal.is_objected() {
+                if proposal.escalation_module.is_some() {
+                    Status::Objected
+                } else {
+                    Status::Rejected
+                }
+            } else if proposal.expiration.is_expired(block) {
+                Status::Passed
+            } else {
+                Status::Open
+            }
+        }
+        _ => proposal.last_status,
+    }
+}
+
+impl Proposal {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        block: &BlockInfo,
+        config: &Config,
+        id: u64,
+        title: String,
+        description: String,
+        proposer: Addr,
+        msgs: Vec<CosmosMsg<Empty>>,
+        total_power: Uint128,
+    ) -> Self {
+        Self {
+            last_status: Status::Open,
+
+            id,
+            title,
+            description,
+            proposer,
+            msgs,
+
+            start_height: block.height,
+            expiration: config.voting_period.after(block),
+
+            objection_threshold: config.objection_threshold,
+            escalation_module: config.escalation_module.clone(),
+            close_on_execution_failure: config.close_proposals_on_execution_failure,
+
+            total_power,
+            votes: Votes::zero(),
+        }
+    }
+
+    /// Returns true if objections to the proposal have reached its
+    /// objection threshold.
+    pub fn is_objected(&self) -> bool {
+        does_vote_count_pass(self.votes.no, self.total_power, self.objection_threshold)
+    }
+
+    pub(crate) fn update_status(&mut self, block: &BlockInfo) -> Status {
+        self.last_status = status(block, self);
+        self.last_status
+    }
+
+    pub fn status(&self, block: &BlockInfo) -> Status {
+        status(block, self)
+    }
+
+    // To test that status is updated before responding to queries.
+    #[cfg(test)]
+    pub fn last_status(&self) -> Status {
+        self.last_status
+    }
+
+    pub(crate) fn set_closed(&mut self) {
+        debug_assert_eq!(self.last_status, Status::Rejected);
+
+        self.last_status = Status::Closed;
+    }
+
+    /// Sets the proposal's status to escalated and returns a message
+    /// creating a proposal in the escalation module.
+    pub(crate) fn set_escalated(&mut self, proposal_id: u64) -> StdResult<WasmMsg> {
+        debug_assert_eq!(self.last_status, Status::Objected);
+
+        self.last_status = Status::Escalated { proposal_id };
+
+        Ok(WasmMsg::Execute {
+            contract_addr: self
+                .escalation_module
+                .as_ref()
+                .expect("objected proposals have an escalation module")
+                .to_string(),
+            msg: to_binary(&dao_proposal_single::msg::ExecuteMsg::Propose(
+                dao_voting::proposal::SingleChoiceProposeMsg {
+                    title: self.title.clone(),
+                    description: self.description.clone(),
+                    msgs: self.msgs.clone(),
+                    proposer: None,
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
+                },
+            ))?,
+            funds: vec![],
+        })
+    }
+
+    /// Sets the proposal's status to executed and returns a
+    /// submessage to be executed.
+    pub(crate) fn set_executed(&mut self, dao: Addr) -> StdResult<SubMsg> {
+        debug_assert_eq!(self.last_status, Status::Passed);
+
+        self.last_status = Status::Executed;
+
+        let core_exec = WasmMsg::Execute {
+            contract_addr: dao.into_string(),
+            msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook {
+                msgs: self.msgs.clone(),
+            })?,
+            funds: vec![],
+        };
+        Ok(if self.close_on_execution_failure {
+            let masked_id = mask_proposal_execution_proposal_id(self.id);
+            SubMsg::reply_on_error(core_exec, masked_id)
+        } else {
+            SubMsg::new(core_exec)
+        })
+    }
+
+    pub(crate) fn set_execution_failed(&mut self) {
+        debug_assert_eq!(self.last_status, Status::Executed);
+
+        self.last_status = Status::ExecutionFailed;
+    }
+}
@@ -0,0 +1,19 @@
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use dao_voting::voting::Vote;
+
+use crate::{config::Config, proposal::Proposal};
+
+pub(crate) const DAO: Item<Addr> = Item::new("dao");
+pub(crate) const CONFIG: Item<Config> = Item::new("config");
+
+pub(crate) const PROPOSAL: Map<u64, Proposal> = Map::new("proposals");
+pub(crate) const VOTE: Map<(u64, Addr), Vote> = Map::new("votes");
+
+pub(crate) fn next_proposal_id(storage: &dyn Storage) -> StdResult<u64> {
+    PROPOSAL
+        .keys(storage, None, None, cosmwasm_std::Order::Descending)
+        .next()
+        .transpose()
+        .map(|id| id.unwrap_or(0) + 1)
+}
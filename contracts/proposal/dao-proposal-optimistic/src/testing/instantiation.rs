@@ -0,0 +1,95 @@
+use cosmwasm_std::Decimal;
+use cw_utils::Duration;
+use dao_voting::threshold::PercentageThreshold;
+
+use crate::{config::UncheckedConfig, ContractError};
+
+use super::{is_error, suite::SuiteBuilder};
+
+#[test]
+fn test_instantiation() {
+    let default_config = SuiteBuilder::default().instantiate;
+
+    let suite = SuiteBuilder::default().build();
+    let config = suite.query_config();
+
+    assert_eq!(
+        config.objection_threshold,
+        default_config.objection_threshold
+    );
+    assert_eq!(config.voting_period, default_config.voting_period);
+    assert_eq!(config.escalation_module, None);
+    assert!(config.close_proposals_on_execution_failure);
+}
+
+#[test]
+#[should_panic(expected = "Required threshold cannot be zero")]
+fn test_instantiate_zero_objection_threshold() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        objection_threshold: PercentageThreshold::Percent(Decimal::zero()),
+        voting_period: Duration::Height(10),
+        escalation_module: None,
+        close_proposals_on_execution_failure: true,
+    })
+    .build();
+}
+
+#[test]
+#[should_panic(expected = "Not possible to reach required (passing) threshold")]
+fn test_instantiate_unreachable_objection_threshold() {
+    SuiteBuilder::with_config(UncheckedConfig {
+        objection_threshold: PercentageThreshold::Percent(Decimal::percent(101)),
+        voting_period: Duration::Height(10),
+        escalation_module: None,
+        close_proposals_on_execution_failure: true,
+    })
+    .build();
+}
+
+#[test]
+fn test_set_config() {
+    let mut suite = SuiteBuilder::default().with_escalation().build();
+    let config = suite.query_config();
+    assert_eq!(config.escalation_module, Some(suite.single.clone()));
+
+    // Only the DAO may update the config.
+    let err = suite.set_config(
+        suite.sender(),
+        UncheckedConfig {
+            objection_threshold: PercentageThreshold::Majority {},
+            voting_period: Duration::Height(10),
+            escalation_module: None,
+            close_proposals_on_execution_failure: true,
+        },
+    );
+    is_error!(err, &ContractError::NotDao {}.to_string());
+
+    // The escalation module must be a proposal module of the DAO.
+    let err = suite.set_config(
+        suite.core.clone(),
+        UncheckedConfig {
+            objection_threshold: PercentageThreshold::Majority {},
+            voting_period: Duration::Height(10),
+            escalation_module: Some(suite.optimistic.to_string()),
+            close_proposals_on_execution_failure: true,
+        },
+    );
+    assert!(err.is_err());
+
+    suite
+        .set_config(
+            suite.core.clone(),
+            UncheckedConfig {
+                objection_threshold: PercentageThreshold::Majority {},
+                voting_period: Duration::Height(10),
+                escalation_module: None,
+                close_proposals_on_execution_failure: false,
+            },
+        )
+        .unwrap();
+    let config = suite.query_config();
+    assert_eq!(config.objection_threshold, PercentageThreshold::Majority {});
+    assert_eq!(config.voting_period, Duration::Height(10));
+    assert_eq!(config.escalation_module, None);
+    assert!(!config.close_proposals_on_execution_failure);
+}
@@ -0,0 +1,167 @@
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env},
+    Reply, SubMsgResult,
+};
+use dao_voting::voting::Vote;
+
+use crate::{
+    contract::reply, proposal::Status, testing::suite::unimportant_message, ContractError,
+};
+
+use super::{is_error, suite::SuiteBuilder};
+
+#[test]
+fn test_make_proposal() {
+    let mut suite = SuiteBuilder::default().build();
+    let id = suite
+        .propose(suite.sender(), vec![unimportant_message()])
+        .unwrap();
+    let proposal = suite.query_proposal(id).proposal;
+
+    assert_eq!(proposal.id, id);
+    assert_eq!(proposal.proposer, suite.sender());
+    assert_eq!(proposal.msgs, vec![unimportant_message()]);
+    assert_eq!(proposal.escalation_module, None);
+    assert_eq!(proposal.last_status(), Status::Open);
+
+    let proposals = suite.query_list_proposals(None, None);
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].proposal, proposal);
+}
+
+#[test]
+fn test_no_propose_zero_voting_power() {
+    let mut suite = SuiteBuilder::default().build();
+    let err = suite.propose("someone", vec![]);
+    is_error!(err, &ContractError::ZeroVotingPower {}.to_string());
+}
+
+// nobody objects and the proposal passes with zero turnout.
+#[test]
+fn test_passes_without_objection() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 10), ("blue", 90)])
+        .build();
+    let id = suite.propose("ekez", vec![]).unwrap();
+
+    // yes and abstain votes are not objections.
+    suite.vote("blue", id, Vote::Yes).unwrap();
+    suite.vote("ekez", id, Vote::Abstain).unwrap();
+    assert_eq!(suite.query_vote(id, "blue"), Some(Vote::Yes));
+
+    let err = suite.execute("ekez", id);
+    is_error!(err, &ContractError::Unexecutable {}.to_string());
+
+    suite.a_day_passes();
+    suite.a_day_passes();
+    assert_eq!(suite.query_status(id), Status::Open);
+    suite.a_day_passes();
+    assert_eq!(suite.query_status(id), Status::Passed);
+
+    let err = suite.vote("blue", id, Vote::No);
+    is_error!(err, &ContractError::Voted {}.to_string());
+
+    suite.execute("ekez", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::Executed);
+}
+
+#[test]
+fn test_objection_rejects() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 5), ("blue", 5), ("violet", 90)])
+        .build();
+    let id = suite.propose("violet", vec![]).unwrap();
+
+    // 5% objecting is less than the 10% objection threshold.
+    suite.vote("ekez", id, Vote::No).unwrap();
+    assert_eq!(suite.query_status(id), Status::Open);
+
+    let err = suite.close("ekez", id);
+    is_error!(err, &ContractError::Unclosable {}.to_string());
+
+    suite.vote("blue", id, Vote::No).unwrap();
+    assert_eq!(suite.query_status(id), Status::Rejected);
+
+    let err = suite.vote("violet", id, Vote::Yes);
+    is_error!(err, &ContractError::NotOpen {}.to_string());
+    let err = suite.escalate("violet", id);
+    is_error!(err, &ContractError::Unescalatable {}.to_string());
+
+    // the proposal stays rejected after expiration.
+    suite.a_day_passes();
+    suite.a_day_passes();
+    suite.a_day_passes();
+    let err = suite.execute("violet", id);
+    is_error!(err, &ContractError::Unexecutable {}.to_string());
+
+    suite.close("ekez", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::Closed);
+}
+
+#[test]
+fn test_objection_escalates() {
+    let mut suite = SuiteBuilder::default()
+        .with_voters(&[("ekez", 10), ("violet", 90)])
+        .with_escalation()
+        .build();
+    let id = suite
+        .propose("violet", vec![unimportant_message()])
+        .unwrap();
+    assert_eq!(
+        suite.query_proposal(id).proposal.escalation_module,
+        Some(suite.single.clone())
+    );
+
+    suite.vote("ekez", id, Vote::No).unwrap();
+    assert_eq!(suite.query_status(id), Status::Objected);
+
+    let err = suite.close("ekez", id);
+    is_error!(err, &ContractError::Unclosable {}.to_string());
+
+    suite.escalate("ekez", id).unwrap();
+    assert_eq!(suite.query_status(id), Status::Escalated { proposal_id: 1 });
+
+    let escalated = suite.query_single_proposal(1).proposal;
+    assert_eq!(escalated.title, "title");
+    assert_eq!(escalated.description, "description");
+    assert_eq!(escalated.msgs, vec![unimportant_message()]);
+    assert_eq!(escalated.proposer, suite.optimistic);
+    assert_eq!(escalated.status, dao_voting::status::Status::Open);
+
+    let err = suite.escalate("ekez", id);
+    is_error!(err, &ContractError::Unescalatable {}.to_string());
+}
+
+#[test]
+fn test_execution_failure_closes() {
+    let mut suite = SuiteBuilder::default().build();
+    // the DAO has no tokens to send so execution will fail.
+    let id = suite
+        .propose(suite.sender(), vec![unimportant_message()])
+        .unwrap();
+    suite.a_day_passes();
+    suite.a_day_passes();
+    suite.a_day_passes();
+
+    suite.execute(suite.sender(), id).unwrap();
+    assert_eq!(suite.query_status(id), Status::ExecutionFailed);
+}
+
+#[test]
+fn test_unexpected_reply() {
+    let mut deps = mock_dependencies();
+    let id = dao_voting::reply::mask_proposal_hook_index(0);
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id,
+            result: SubMsgResult::Err("error".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InvalidReplyId { id }.to_string()
+    );
+}
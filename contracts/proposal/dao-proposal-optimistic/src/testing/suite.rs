@@ -0,0 +1,379 @@
+use cosmwasm_std::{coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal};
+use cw_multi_test::{next_block, App, Executor};
+use cw_utils::Duration;
+use dao_interface::{voting::InfoResponse, Admin, ModuleInstantiateInfo};
+use dao_testing::contracts::{
+    cw4_group_contract, dao_core_contract, dao_voting_cw4_contract, proposal_optimistic_contract,
+    proposal_single_contract,
+};
+use dao_voting::{
+    pre_propose::PreProposeInfo,
+    threshold::{PercentageThreshold, Threshold},
+    voting::Vote,
+};
+
+use crate::{
+    config::{Config, UncheckedConfig},
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    proposal::{ProposalResponse, Status},
+};
+
+pub(crate) struct Suite {
+    app: App,
+    sender: Addr,
+    pub optimistic: Addr,
+    pub single: Addr,
+    pub core: Addr,
+}
+
+pub(crate) struct SuiteBuilder {
+    pub instantiate: InstantiateMsg,
+    with_escalation: bool,
+    with_voters: Vec<(String, u64)>,
+}
+
+impl Default for SuiteBuilder {
+    fn default() -> Self {
+        Self {
+            instantiate: UncheckedConfig {
+                objection_threshold: PercentageThreshold::Percent(Decimal::percent(10)),
+                voting_period: Duration::Time(60 * 60 * 24 * 3),
+                escalation_module: None,
+                close_proposals_on_execution_failure: true,
+            },
+            with_escalation: false,
+            with_voters: vec![("sender".to_string(), 10)],
+        }
+    }
+}
+
+impl SuiteBuilder {
+    #[allow(clippy::field_reassign_with_default)]
+    pub fn with_config(instantiate: UncheckedConfig) -> Self {
+        let mut b = Self::default();
+        b.instantiate = instantiate;
+        b
+    }
+
+    /// Configures the optimistic module to escalate objected
+    /// proposals to the DAO's `dao-proposal-single` module.
+    pub fn with_escalation(mut self) -> Self {
+        self.with_escalation = true;
+        self
+    }
+
+    pub fn with_voters(mut self, voters: &[(&str, u64)]) -> Self {
+        self.with_voters = voters.iter().map(|(a, p)| (a.to_string(), *p)).collect();
+        self
+    }
+
+    pub fn build(self) -> Suite {
+        let initial_members: Vec<_> = self
+            .with_voters
+            .into_iter()
+            .map(|(addr, weight)| cw4::Member { addr, weight })
+            .collect();
+        let sender = Addr::unchecked(&initial_members[0].addr);
+
+        let mut app = App::default();
+        let optimistic_id = app.store_code(proposal_optimistic_contract());
+        let single_id = app.store_code(proposal_single_contract());
+        let core_id = app.store_code(dao_core_contract());
+        let cw4_id = app.store_code(cw4_group_contract());
+        let cw4_voting_id = app.store_code(dao_voting_cw4_contract());
+
+        let core_instantiate = dao_core::msg::InstantiateMsg {
+            admin: None,
+            name: "core module".to_string(),
+            description: "core module".to_string(),
+            image_url: None,
+            automatically_add_cw20s: false,
+            automatically_add_cw721s: false,
+            voting_module_instantiate_info: ModuleInstantiateInfo {
+                code_id: cw4_voting_id,
+                msg: to_binary(&dao_voting_cw4::msg::InstantiateMsg {
+                    cw4_group_code_id: cw4_id,
+                    initial_members,
+                })
+                .unwrap(),
+                admin: Some(Admin::CoreModule {}),
+                label: "voting module".to_string(),
+            },
+            proposal_modules_instantiate_info: vec![
+                ModuleInstantiateInfo {
+                    code_id: optimistic_id,
+                    msg: to_binary(&self.instantiate).unwrap(),
+                    admin: Some(Admin::CoreModule {}),
+                    label: "optimistic module".to_string(),
+                },
+                ModuleInstantiateInfo {
+                    code_id: single_id,
+                    msg: to_binary(&dao_proposal_single::msg::InstantiateMsg {
+                        threshold: Threshold::AbsolutePercentage {
+                            percentage: PercentageThreshold::Majority {},
+                        },
+                        max_voting_period: Duration::Time(60 * 60 * 24 * 7),
+                        min_voting_period: None,
+                        only_members_execute: false,
+                        allow_revoting: false,
+                        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+                        close_proposal_on_execution_failure: true,
+                        anti_sniping: None,
+                        threshold_rules: vec![],
+                        commit_reveal: None,
+                        delegation_registry: None,
+                        partial_execution: false,
+                        power_transform: None,
+                        review_period: None,
+                        voting_period_choice: None,
+                    })
+                    .unwrap(),
+                    admin: Some(Admin::CoreModule {}),
+                    label: "single module".to_string(),
+                },
+            ],
+            initial_items: None,
+            dao_uri: None,
+        };
+        let core = app
+            .instantiate_contract(
+                core_id,
+                sender.clone(),
+                &core_instantiate,
+                &[],
+                "core module".to_string(),
+                None,
+            )
+            .unwrap();
+        let modules: Vec<dao_core::state::ProposalModule> = app
+            .wrap()
+            .query_wasm_smart(
+                &core,
+                &dao_core::msg::QueryMsg::ProposalModules {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let optimistic = modules[0].address.clone();
+        let single = modules[1].address.clone();
+
+        app.update_block(next_block);
+
+        let mut suite = Suite {
+            app,
+            sender,
+            optimistic,
+            single,
+            core,
+        };
+
+        if self.with_escalation {
+            suite
+                .set_config(
+                    suite.core.clone(),
+                    UncheckedConfig {
+                        escalation_module: Some(suite.single.to_string()),
+                        ..self.instantiate
+                    },
+                )
+                .unwrap();
+        }
+
+        let next_id = suite.query_next_proposal_id();
+        assert_eq!(next_id, 1);
+
+        let dao = suite.query_dao();
+        assert_eq!(dao, suite.core);
+        let info = suite.query_info();
+        assert_eq!(info.info.version, CONTRACT_VERSION);
+        assert_eq!(info.info.contract, CONTRACT_NAME);
+
+        suite
+    }
+}
+
+impl Suite {
+    pub fn a_day_passes(&mut self) {
+        self.app
+            .update_block(|b| b.time = b.time.plus_seconds(60 * 60 * 24))
+    }
+
+    pub fn sender(&self) -> Addr {
+        self.sender.clone()
+    }
+}
+
+// query
+impl Suite {
+    pub fn query_config(&self) -> Config {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Config {})
+            .unwrap()
+    }
+
+    pub fn query_proposal(&self, id: u64) -> ProposalResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Proposal { id })
+            .unwrap()
+    }
+
+    pub fn query_status(&self, id: u64) -> Status {
+        self.query_proposal(id).proposal.last_status()
+    }
+
+    pub fn query_list_proposals(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<ProposalResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.optimistic,
+                &QueryMsg::ListProposals { start_after, limit },
+            )
+            .unwrap()
+    }
+
+    pub fn query_vote(&self, proposal_id: u64, voter: &str) -> Option<Vote> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.optimistic,
+                &QueryMsg::Vote {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_single_proposal(&self, id: u64) -> dao_proposal_single::query::ProposalResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.single,
+                &dao_proposal_single::msg::QueryMsg::Proposal { proposal_id: id },
+            )
+            .unwrap()
+    }
+
+    pub fn query_next_proposal_id(&self) -> u64 {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::NextProposalId {})
+            .unwrap()
+    }
+
+    pub fn query_dao(&self) -> Addr {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Dao {})
+            .unwrap()
+    }
+
+    pub fn query_info(&self) -> InfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.optimistic, &QueryMsg::Info {})
+            .unwrap()
+    }
+}
+
+// execute
+impl Suite {
+    pub fn propose<S: Into<String>>(
+        &mut self,
+        sender: S,
+        msgs: Vec<CosmosMsg>,
+    ) -> anyhow::Result<u64> {
+        let id = self.query_next_proposal_id();
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.optimistic.clone(),
+            &ExecuteMsg::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+            },
+            &[],
+        )?;
+        Ok(id)
+    }
+
+    pub fn vote<S: Into<String>>(
+        &mut self,
+        sender: S,
+        proposal_id: u64,
+        vote: Vote,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Vote { proposal_id, vote },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn execute<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Execute { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn close<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Close { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn escalate<S: Into<String>>(&mut self, sender: S, proposal_id: u64) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::Escalate { proposal_id },
+                &[],
+            )
+            .map(|_| ())
+    }
+
+    pub fn set_config<S: Into<String>>(
+        &mut self,
+        sender: S,
+        config: UncheckedConfig,
+    ) -> anyhow::Result<()> {
+        self.app
+            .execute_contract(
+                Addr::unchecked(sender),
+                self.optimistic.clone(),
+                &ExecuteMsg::SetConfig(config),
+                &[],
+            )
+            .map(|_| ())
+    }
+}
+
+pub fn unimportant_message() -> CosmosMsg {
+    BankMsg::Send {
+        to_address: "someone".to_string(),
+        amount: coins(10, "something"),
+    }
+    .into()
+}
@@ -0,0 +1,89 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary};
+use cw_utils::Duration;
+use dao_voting::voting::Vote;
+use sha2::{Digest, Sha256};
+
+use crate::ContractError;
+
+/// Configuration for secret ballots. When set, proposals are voted on
+/// in two phases. During the commit phase, which lasts for the
+/// proposal's voting period, voters submit a hash of their vote. Once
+/// the commit phase ends, voters have `reveal_period` to reveal the
+/// vote they committed to. Only revealed votes are counted.
+#[cw_serde]
+#[derive(Copy)]
+pub struct CommitRevealConfig {
+    /// The amount of time after the commit phase ends that voters
+    /// have to reveal their votes.
+    pub reveal_period: Duration,
+}
+
+impl CommitRevealConfig {
+    /// Validates that the reveal period is non-zero and has the same
+    /// units (height or time) as the max voting period it follows.
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), ContractError> {
+        match (self.reveal_period, max_voting_period) {
+            (Duration::Height(0), _) | (Duration::Time(0), _) => {
+                Err(ContractError::ZeroRevealPeriod {})
+            }
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+                Ok(())
+            }
+            _ => Err(ContractError::RevealPeriodUnitsConflict {}),
+        }
+    }
+}
+
+/// Computes the commitment VOTER must submit to later reveal VOTE on
+/// proposal PROPOSAL_ID. This is the SHA-256 hash of the string
+/// `"{proposal_id}:{voter}:{vote}:{salt}"`, where `vote` is one of
+/// `yes`, `no`, or `abstain`. The proposal ID and voter are included
+/// so that a commitment can not be copied by another voter or onto
+/// another proposal. SALT should be a random string kept secret
+/// until the vote is revealed.
+pub fn commitment(proposal_id: u64, voter: &Addr, vote: Vote, salt: &str) -> Binary {
+    let preimage = format!("{proposal_id}:{voter}:{vote}:{salt}");
+    Binary::from(Sha256::digest(preimage.as_bytes()).as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment() {
+        let voter = Addr::unchecked("ekez");
+        let c = commitment(1, &voter, Vote::Yes, "salt");
+
+        assert_eq!(c.len(), 32);
+        assert_eq!(c, commitment(1, &voter, Vote::Yes, "salt"));
+        assert_ne!(c, commitment(1, &voter, Vote::No, "salt"));
+        assert_ne!(c, commitment(1, &voter, Vote::Yes, "pepper"));
+        assert_ne!(c, commitment(2, &voter, Vote::Yes, "salt"));
+        assert_ne!(
+            c,
+            commitment(1, &Addr::unchecked("blue"), Vote::Yes, "salt")
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = CommitRevealConfig {
+            reveal_period: Duration::Height(10),
+        };
+        config.validate(&Duration::Height(100)).unwrap();
+        assert!(matches!(
+            config.validate(&Duration::Time(100)),
+            Err(ContractError::RevealPeriodUnitsConflict {})
+        ));
+
+        let config = CommitRevealConfig {
+            reveal_period: Duration::Time(0),
+        };
+        assert!(matches!(
+            config.validate(&Duration::Time(100)),
+            Err(ContractError::ZeroRevealPeriod {})
+        ));
+    }
+}
@@ -1,31 +1,40 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, BlockInfo, CosmosMsg, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Reply, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_hooks::Hooks;
 use cw_proposal_single_v1 as v1;
 use cw_storage_plus::Bound;
-use cw_utils::{parse_reply_instantiate_data, Duration};
+use cw_utils::{parse_reply_instantiate_data, Duration, Expiration};
 use dao_interface::voting::IsActiveResponse;
 use dao_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use dao_vote_hooks::new_vote_hooks;
+use dao_voting::anti_sniping::{block_at_expiration, AntiSnipingConfig};
+use dao_voting::power_transform::{transform_voting_power, PowerTransform};
 use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::prerequisite::{is_failed, CheckedPrerequisite, Prerequisite};
 use dao_voting::proposal::{
     SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE,
 };
+use dao_voting::recurrence::Recurrence;
 use dao_voting::reply::{
-    failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id, TaggedReplyId,
+    failed_pre_propose_module_hook_id, mask_instalment_message_index,
+    mask_proposal_execution_proposal_id, mask_proposal_message_index, TaggedReplyId,
 };
 use dao_voting::status::Status;
 use dao_voting::threshold::Threshold;
 use dao_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
+use dao_voting::voting_period::{proposal_voting_period, VotingPeriodChoice};
 
+use crate::commit_reveal::{commitment, CommitRevealConfig};
+use crate::delegation::{cast_delegated_power, revoke_delegated_power};
 use crate::msg::MigrateMsg;
-use crate::proposal::{next_proposal_id, SingleChoiceProposal};
+use crate::proposal::{next_proposal_id, MessageResult, SingleChoiceProposal};
 use crate::state::{Config, CREATION_POLICY};
+use crate::threshold_rules::{find_threshold_rule, validate_threshold_rules, ThresholdRule};
 
 use crate::v1_state::{
     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
@@ -33,14 +42,29 @@ use crate::v1_state::{
 use crate::{
     error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    proposal::advance_proposal_id,
-    query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    proposal::{advance_proposal_id, record_turnout},
+    query::{
+        InstalmentListResponse, ProposalResponse, ProposalVersionListResponse, VoteInfo,
+        VoteListResponse, VoteResponse, VoterBallotInfo, VoterBallotListResponse,
+    },
+    query::{ProposalListResponse, ProposalStatusListResponse},
+    state::{
+        proposals, Ballot, CreationSnapshot, Instalment, ProposalVersion, Schedule, BALLOTS,
+        CONFIG, CREATION_SNAPSHOTS, EXPIRATION_HEIGHT, EXPIRATION_TIME, INSTALMENTS,
+        PROPOSAL_COUNT, PROPOSAL_HOOKS, PROPOSAL_VERSIONS, SCHEDULES, TURNOUT, VOTER_BALLOTS,
+        VOTE_HOOKS,
+    },
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The maximum number of expired proposals of each expiration kind
+/// whose turnout is recorded when a proposal is created.
+pub(crate) const MAX_TURNOUTS_RECORDED: usize = 10;
+/// The maximum number of stored proposals `ListProposalsByStatus`
+/// examines for each stored status it lists from, unless a higher
+/// limit is requested.
+pub(crate) const MAX_PROPOSALS_SCANNED: usize = 100;
 
 /// Message type used for firing hooks to this module's pre-propose
 /// module, if one is installed.
@@ -61,6 +85,30 @@ pub fn instantiate(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
+    if let Some(anti_sniping) = msg.anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+    if let Some(commit_reveal) = msg.commit_reveal {
+        if msg.anti_sniping.is_some() {
+            return Err(ContractError::CommitRevealAntiSniping {});
+        }
+        commit_reveal.validate(&max_voting_period)?;
+    }
+    validate_threshold_rules(deps.api, &msg.threshold_rules, &max_voting_period)?;
+    if let Some(power_transform) = msg.power_transform {
+        power_transform.validate()?;
+    }
+    if let Some(review_period) = msg.review_period {
+        validate_review_period(&review_period, &max_voting_period)?;
+    }
+    if let Some(choice) = &msg.voting_period_choice {
+        validate_voting_period_choice(
+            choice,
+            &msg.threshold,
+            min_voting_period.as_ref(),
+            &max_voting_period,
+        )?;
+    }
 
     let (initial_policy, pre_propose_messages) = msg
         .pre_propose_info
@@ -74,6 +122,17 @@ pub fn instantiate(
         dao: dao.clone(),
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        anti_sniping: msg.anti_sniping,
+        threshold_rules: msg.threshold_rules,
+        commit_reveal: msg.commit_reveal,
+        delegation_registry: msg
+            .delegation_registry
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        partial_execution: msg.partial_execution,
+        power_transform: msg.power_transform,
+        review_period: msg.review_period,
+        voting_period_choice: msg.voting_period_choice,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -101,18 +160,56 @@ pub fn execute(
             description,
             msgs,
             proposer,
-        }) => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+            prerequisites,
+            execute_at,
+            recurrence,
+            voting_period,
+        }) => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            msgs,
+            proposer,
+            prerequisites,
+            execute_at,
+            recurrence,
+            voting_period,
+        ),
         ExecuteMsg::Vote {
             proposal_id,
             vote,
             rationale,
         } => execute_vote(deps, env, info, proposal_id, vote, rationale),
+        ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment,
+        } => execute_commit_vote(deps, env, info, proposal_id, commitment),
+        ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt,
+            rationale,
+        } => execute_reveal_vote(deps, env, info, proposal_id, vote, salt, rationale),
+        ExecuteMsg::Amend {
+            proposal_id,
+            title,
+            description,
+            msgs,
+        } => execute_amend(deps, env, info, proposal_id, title, description, msgs),
         ExecuteMsg::UpdateRationale {
             proposal_id,
             rationale,
         } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::ExecuteInstalment { proposal_id } => {
+            execute_execute_instalment(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::CancelSchedule { proposal_id } => {
+            execute_cancel_schedule(deps, info, proposal_id)
+        }
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -121,6 +218,14 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            threshold_rules,
+            commit_reveal,
+            delegation_registry,
+            partial_execution,
+            power_transform,
+            review_period,
+            voting_period_choice,
         } => execute_update_config(
             deps,
             info,
@@ -131,6 +236,14 @@ pub fn execute(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            threshold_rules,
+            commit_reveal,
+            delegation_registry,
+            partial_execution,
+            power_transform,
+            review_period,
+            voting_period_choice,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -148,6 +261,7 @@ pub fn execute(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -156,6 +270,10 @@ pub fn execute_propose(
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
     proposer: Option<String>,
+    prerequisites: Vec<Prerequisite>,
+    execute_at: Option<Expiration>,
+    recurrence: Option<Recurrence>,
+    voting_period: Option<Duration>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
@@ -194,9 +312,41 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    if execute_at == Some(Expiration::Never {}) {
+        return Err(ContractError::InvalidExecuteAt {});
+    }
+    if let Some(recurrence) = recurrence {
+        recurrence.validate()?;
+    }
+
+    let prerequisites = prerequisites
+        .into_iter()
+        .map(|prerequisite| prerequisite.into_checked(deps.api))
+        .collect::<StdResult<Vec<_>>>()?;
+    // Querying the status of each prerequisite checks that it exists.
+    for prerequisite in &prerequisites {
+        prerequisite_status(deps.as_ref(), &env, prerequisite)?;
+    }
+
+    record_expired_turnouts(deps.storage, &env.block)?;
+    let turnout = TURNOUT.may_load(deps.storage)?;
+    let (threshold, max_voting_period, rule) =
+        threshold_and_voting_period(&config, turnout, &msgs, voting_period)?;
+    // Proposals with a review period may be amended before voting
+    // on them begins.
+    let review_expiration = config.review_period.map(|review| review.after(&env.block));
+    let voting_start = match review_expiration {
+        Some(review_expiration) => block_at_expiration(&env.block, &review_expiration),
+        None => env.block.clone(),
+    };
+    let (commit_expiration, expiration) =
+        voting_expirations(&config, max_voting_period, &voting_start)?;
 
     let total_power = get_total_power(deps.as_ref(), &config.dao, Some(env.block.height))?;
+    let total_power = match config.power_transform {
+        Some(power_transform) => power_transform.total_power(total_power),
+        None => total_power,
+    };
 
     let proposal = {
         // Limit mutability to this block.
@@ -205,14 +355,26 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period: config.min_voting_period.map(|min| min.after(&voting_start)),
             expiration,
-            threshold: config.threshold,
+            threshold,
             total_power,
             msgs,
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
+            anti_sniping: config.anti_sniping,
+            extensions: 0,
+            commit_expiration,
+            prerequisites,
+            execute_at,
+            msg_results: vec![],
+            power_transform: config.power_transform,
+            review_expiration,
+            version: 0,
+            recurrence,
+            voting_period,
+            turnout_recorded: false,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -243,16 +405,23 @@ pub fn execute_propose(
         });
     }
 
-    PROPOSALS.save(deps.storage, id, &proposal)?;
+    proposals().save(deps.storage, id, &proposal)?;
+    if review_expiration.is_some() {
+        CREATION_SNAPSHOTS.save(deps.storage, id, &CreationSnapshot { config, turnout })?;
+    }
 
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
 
-    Ok(Response::default()
+    let response = Response::default()
         .add_submessages(hooks)
         .add_attribute("action", "propose")
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", id.to_string())
-        .add_attribute("status", proposal.status.to_string()))
+        .add_attribute("status", proposal.status.to_string());
+    Ok(match rule {
+        Some(index) => response.add_attribute("threshold_rule", index.to_string()),
+        None => response,
+    })
 }
 
 pub fn execute_execute(
@@ -261,12 +430,14 @@ pub fn execute_execute(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS
+    let mut prop = proposals()
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
     let config = CONFIG.load(deps.storage)?;
-    if config.only_members_execute {
+    // Scheduled proposals may be executed by anyone once their
+    // execution time is reached.
+    if config.only_members_execute && prop.execute_at.is_none() {
         let power = get_voting_power(
             deps.as_ref(),
             info.sender.clone(),
@@ -286,13 +457,74 @@ pub fn execute_execute(
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
+    if let Some(execute_at) = prop.execute_at {
+        if !execute_at.is_expired(&env.block) {
+            return Err(ContractError::ExecuteAtNotReached {
+                id: proposal_id,
+                execute_at,
+            });
+        }
+    }
+    for prerequisite in &prop.prerequisites {
+        if prerequisite_status(deps.as_ref(), &env, prerequisite)? != Status::Executed {
+            return Err(ContractError::PrerequisiteNotExecuted {
+                proposal_module: prerequisite.proposal_module.to_string(),
+                proposal_id: prerequisite.proposal_id,
+            });
+        }
+    }
 
     prop.status = Status::Executed;
+    if config.partial_execution {
+        // Messages are assumed to succeed. Failures are recorded
+        // when their replies are handled.
+        prop.msg_results = vec![MessageResult::Succeeded; prop.msgs.len()];
+    }
+
+    record_turnout(deps.storage, &env.block, &mut prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    // The first instalment of a recurring proposal is executed along
+    // with the proposal. The rest are executed by the crank.
+    if let Some(recurrence) = prop.recurrence {
+        SCHEDULES.save(
+            deps.storage,
+            proposal_id,
+            &Schedule {
+                interval: recurrence.interval,
+                count: recurrence.count,
+                executed: 1,
+                next: recurrence.interval.after(&env.block),
+                cancelled: false,
+            },
+        )?;
+        record_instalment(deps.storage, &env.block, proposal_id, 0, vec![])?;
+    }
 
     let response = {
-        if !prop.msgs.is_empty() {
+        if config.partial_execution {
+            // Each message is executed by the DAO in its own
+            // submessage so that the failure of one does not revert
+            // the others.
+            let submessages = prop
+                .msgs
+                .into_iter()
+                .enumerate()
+                .map(|(index, msg)| {
+                    Ok(SubMsg::reply_on_error(
+                        WasmMsg::Execute {
+                            contract_addr: config.dao.to_string(),
+                            msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook {
+                                msgs: vec![msg],
+                            })?,
+                            funds: vec![],
+                        },
+                        mask_proposal_message_index(proposal_id, index as u64),
+                    ))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            Response::default().add_submessages(submessages)
+        } else if !prop.msgs.is_empty() {
             let execute_message = WasmMsg::Execute {
                 contract_addr: config.dao.to_string(),
                 msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook {
@@ -351,8 +583,131 @@ pub fn execute_execute(
         .add_attribute("dao", config.dao))
 }
 
-pub fn execute_vote(
+pub fn execute_execute_instalment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut schedule = SCHEDULES
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSchedule { id: proposal_id })?;
+    if schedule.cancelled {
+        return Err(ContractError::ScheduleCancelled { id: proposal_id });
+    }
+    if schedule.executed >= schedule.count {
+        return Err(ContractError::ScheduleComplete { id: proposal_id });
+    }
+    if !schedule.next.is_expired(&env.block) {
+        return Err(ContractError::InstalmentNotDue {
+            id: proposal_id,
+            next: schedule.next,
+        });
+    }
+
+    let prop = proposals().load(deps.storage, proposal_id)?;
+    if prop.status != Status::Executed {
+        return Err(ContractError::NotExecuted { id: proposal_id });
+    }
+
+    // The next instalment is due an interval after this one was due,
+    // not after it was cranked, so late cranks do not delay the
+    // schedule.
+    let index = schedule.executed;
+    schedule.executed += 1;
+    schedule.next = (schedule.next + schedule.interval)?;
+    SCHEDULES.save(deps.storage, proposal_id, &schedule)?;
+
+    // Instalments are executed like the proposal, except that a
+    // failing instalment is always recorded rather than reverting
+    // the crank, so that it does not hold up the rest of the
+    // schedule.
+    let config = CONFIG.load(deps.storage)?;
+    let execute = |msgs: Vec<CosmosMsg>| -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_binary(&dao_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        })
+    };
+    let (msg_results, submessages) = if config.partial_execution {
+        let submessages = prop
+            .msgs
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| {
+                Ok(SubMsg::reply_on_error(
+                    execute(vec![msg.clone()])?,
+                    mask_instalment_message_index(proposal_id, i as u64),
+                ))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        (vec![MessageResult::Succeeded; prop.msgs.len()], submessages)
+    } else if prop.msgs.is_empty() {
+        (vec![], vec![])
+    } else {
+        let submessage = SubMsg::reply_on_error(
+            execute(prop.msgs)?,
+            mask_instalment_message_index(proposal_id, 0),
+        );
+        (vec![], vec![submessage])
+    };
+    record_instalment(deps.storage, &env.block, proposal_id, index, msg_results)?;
+
+    let response = Response::default().add_submessages(submessages);
+
+    Ok(response
+        .add_attribute("action", "execute_instalment")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("instalment", index.to_string()))
+}
+
+pub fn execute_cancel_schedule(
     deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    SCHEDULES.update(deps.storage, proposal_id, |schedule| match schedule {
+        Some(mut schedule) => {
+            schedule.cancelled = true;
+            Ok(schedule)
+        }
+        None => Err(ContractError::NoSchedule { id: proposal_id }),
+    })?;
+
+    Ok(Response::default()
+        .add_attribute("action", "cancel_schedule")
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+fn record_instalment(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+    index: u64,
+    msg_results: Vec<MessageResult>,
+) -> StdResult<()> {
+    INSTALMENTS.save(
+        storage,
+        (proposal_id, index),
+        &Instalment {
+            index,
+            height: block.height,
+            time: block.time,
+            msg_results,
+            error: None,
+        },
+    )
+}
+
+pub fn execute_vote(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
@@ -360,7 +715,7 @@ pub fn execute_vote(
     rationale: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
+    let mut prop = proposals()
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
@@ -374,36 +729,71 @@ pub fn execute_vote(
     if prop.expiration.is_expired(&env.block) {
         return Err(ContractError::Expired { id: proposal_id });
     }
+    if prop.commit_expiration.is_some() {
+        return Err(ContractError::CommitRevealRequired { id: proposal_id });
+    }
+    if prop.is_in_review(&env.block) {
+        return Err(ContractError::InReviewPeriod { id: proposal_id });
+    }
 
-    let vote_power = get_voting_power(
-        deps.as_ref(),
-        info.sender.clone(),
-        &config.dao,
-        Some(prop.start_height),
-    )?;
-    if vote_power.is_zero() {
+    let vote_power = transform_voting_power(
+        prop.power_transform,
+        get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            &config.dao,
+            Some(prop.start_height),
+        )?,
+        prop.total_power,
+    );
+    // The power of delegators who have not voted is cast with their
+    // delegate's first vote and moves with it if they revote.
+    let delegated_power = match BALLOTS.may_load(deps.storage, (proposal_id, &info.sender))? {
+        Some(ballot) => ballot.delegated_power,
+        None => cast_delegated_power(
+            deps.branch(),
+            &env,
+            &config,
+            proposal_id,
+            &prop,
+            &info.sender,
+        )?,
+    };
+    if vote_power.is_zero() && delegated_power.is_zero() {
         return Err(ContractError::NotRegistered {});
     }
 
+    // The outcome is taken before a revoter's old vote is removed
+    // so that a revote which flips it is seen as doing so.
+    let old_status = prop.status;
+    let was_passing = prop.is_passed_at_expiration(&env.block);
+
     BALLOTS.update(deps.storage, (proposal_id, &info.sender), |bal| match bal {
         Some(current_ballot) => {
             if prop.allow_revoting {
-                if current_ballot.vote == vote {
+                // Ballots on proposals without commit-reveal are
+                // never committed.
+                let current_vote = current_ballot.vote.ok_or(ContractError::NotRevealed {})?;
+                if current_vote == vote {
                     // Don't allow casting the same vote more than
                     // once. This seems liable to be confusing
                     // behavior.
                     Err(ContractError::AlreadyCast {})
                 } else {
                     // Remove the old vote if this is a re-vote.
-                    prop.votes
-                        .remove_vote(current_ballot.vote, current_ballot.power);
+                    prop.votes.remove_vote(
+                        current_vote,
+                        current_ballot.power + current_ballot.delegated_power,
+                    );
                     Ok(Ballot {
                         power: vote_power,
-                        vote,
+                        vote: Some(vote),
                         // Roll over the previous rationale. If
                         // you're changing your vote, you've also
                         // likely changed your thinking.
                         rationale: rationale.clone(),
+                        commitment: None,
+                        delegated_power,
                     })
                 }
             } else {
@@ -412,17 +802,22 @@ pub fn execute_vote(
         }
         None => Ok(Ballot {
             power: vote_power,
-            vote,
+            vote: Some(vote),
             rationale: rationale.clone(),
+            commitment: None,
+            delegated_power,
         }),
     })?;
+    VOTER_BALLOTS.save(deps.storage, (&info.sender, proposal_id), &Empty {})?;
 
-    let old_status = prop.status;
-
-    prop.votes.add_vote(vote, vote_power);
+    // Voting directly overrides the voter's delegate.
+    revoke_delegated_power(deps.storage, proposal_id, &mut prop, &info.sender)?;
+    prop.votes.add_vote(vote, vote_power + delegated_power);
+    let extended = prop.extend_if_outcome_changed(&env.block, was_passing)?;
     prop.update_status(&env.block);
+    record_turnout(deps.storage, &env.block, &mut prop)?;
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
@@ -441,17 +836,247 @@ pub fn execute_vote(
         vote.to_string(),
     )?;
 
-    Ok(Response::default()
+    let response = Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
+        .add_attribute("delegated_power", delegated_power)
+        .add_attribute("rationale", rationale.as_deref().unwrap_or("_none"))
+        .add_attribute("status", prop.status.to_string());
+
+    Ok(if extended {
+        response.add_attribute("extended_expiration", prop.expiration.to_string())
+    } else {
+        response
+    })
+}
+
+pub fn execute_commit_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let commit_expiration = prop
+        .commit_expiration
+        .ok_or(ContractError::NotCommitReveal { id: proposal_id })?;
+    if commit_expiration.is_expired(&env.block) {
+        return Err(ContractError::CommitPhaseOver { id: proposal_id });
+    }
+    if prop.is_in_review(&env.block) {
+        return Err(ContractError::InReviewPeriod { id: proposal_id });
+    }
+
+    let vote_power = transform_voting_power(
+        prop.power_transform,
+        get_voting_power(
+            deps.as_ref(),
+            info.sender.clone(),
+            &config.dao,
+            Some(prop.start_height),
+        )?,
+        prop.total_power,
+    );
+    if vote_power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    // Votes may not be revealed until the commit phase is over, so
+    // any existing ballot is an unrevealed commitment.
+    BALLOTS.update(deps.storage, (proposal_id, &info.sender), |bal| match bal {
+        Some(_) if !prop.allow_revoting => Err(ContractError::AlreadyVoted {}),
+        Some(Ballot {
+            commitment: Some(current),
+            ..
+        }) if current == commitment => Err(ContractError::AlreadyCast {}),
+        _ => Ok(Ballot {
+            power: vote_power,
+            vote: None,
+            rationale: None,
+            commitment: Some(commitment.clone()),
+            delegated_power: Uint128::zero(),
+        }),
+    })?;
+    VOTER_BALLOTS.save(deps.storage, (&info.sender, proposal_id), &Empty {})?;
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        "committed".to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "commit_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("commitment", commitment.to_base64()))
+}
+
+pub fn execute_reveal_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+    salt: String,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let commit_expiration = prop
+        .commit_expiration
+        .ok_or(ContractError::NotCommitReveal { id: proposal_id })?;
+    if !commit_expiration.is_expired(&env.block) || prop.expiration.is_expired(&env.block) {
+        return Err(ContractError::NotRevealPhase { id: proposal_id });
+    }
+
+    let mut ballot = BALLOTS
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .ok_or_else(|| ContractError::NoSuchVote {
+            id: proposal_id,
+            voter: info.sender.to_string(),
+        })?;
+    if ballot.vote.is_some() {
+        return Err(ContractError::AlreadyRevealed {});
+    }
+    if ballot.commitment != Some(commitment(proposal_id, &info.sender, vote, &salt)) {
+        return Err(ContractError::InvalidReveal {});
+    }
+    // All commitments are made before any vote is revealed, so
+    // delegators who committed to a vote are never counted here.
+    let config = CONFIG.load(deps.storage)?;
+    ballot.delegated_power = cast_delegated_power(
+        deps.branch(),
+        &env,
+        &config,
+        proposal_id,
+        &prop,
+        &info.sender,
+    )?;
+    ballot.vote = Some(vote);
+    ballot.rationale = rationale.clone();
+    BALLOTS.save(deps.storage, (proposal_id, &info.sender), &ballot)?;
+
+    let old_status = prop.status;
+    prop.votes
+        .add_vote(vote, ballot.power + ballot.delegated_power);
+    prop.update_status(&env.block);
+    record_turnout(deps.storage, &env.block, &mut prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        vote.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_submessages(vote_hooks)
+        .add_attribute("action", "reveal_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("position", vote.to_string())
+        .add_attribute("delegated_power", ballot.delegated_power)
         .add_attribute("rationale", rationale.as_deref().unwrap_or("_none"))
         .add_attribute("status", prop.status.to_string()))
 }
 
+pub fn execute_amend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let mut prop = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.proposer != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let review_expiration = match prop.review_expiration {
+        Some(review_expiration) if !review_expiration.is_expired(&env.block) => review_expiration,
+        _ => return Err(ContractError::NotInReviewPeriod { id: proposal_id }),
+    };
+    let CreationSnapshot { config, turnout } =
+        CREATION_SNAPSHOTS.load(deps.storage, proposal_id)?;
+
+    PROPOSAL_VERSIONS.save(
+        deps.storage,
+        (proposal_id, prop.version),
+        &ProposalVersion {
+            version: prop.version,
+            title: prop.title,
+            description: prop.description,
+            msgs: prop.msgs,
+        },
+    )?;
+    prop.version += 1;
+    prop.title = title;
+    prop.description = description;
+    prop.msgs = msgs;
+
+    // The new messages may match a different threshold rule than
+    // the old ones, so the threshold and voting period are selected
+    // again with the config and turnout the proposal was created
+    // with. As no votes have been cast this is safe to do.
+    let (threshold, max_voting_period, rule) =
+        threshold_and_voting_period(&config, turnout, &prop.msgs, prop.voting_period)?;
+    prop.threshold = threshold;
+    (prop.commit_expiration, prop.expiration) = voting_expirations(
+        &config,
+        max_voting_period,
+        &block_at_expiration(&env.block, &review_expiration),
+    )?;
+
+    let proposal_size = cosmwasm_std::to_vec(&prop)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+
+    proposals().save(deps.storage, proposal_id, &prop)?;
+
+    let response = Response::default()
+        .add_attribute("action", "amend")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("version", prop.version.to_string());
+    Ok(match rule {
+        Some(index) => response.add_attribute("threshold_rule", index.to_string()),
+        None => response,
+    })
+}
+
 pub fn execute_update_rationale(
     deps: DepsMut,
     info: MessageInfo,
@@ -488,19 +1113,37 @@ pub fn execute_close(
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut prop = proposals().load(deps.storage, proposal_id)?;
 
     // Update status to ensure that proposals which were open and have
     // expired are moved to "rejected."
     prop.update_status(&env.block);
-    if prop.status != Status::Rejected {
+    let closable = match prop.status {
+        Status::Rejected => true,
+        // Proposals that can never be executed because one of their
+        // prerequisites failed may be closed without waiting for
+        // them to be rejected.
+        Status::Open | Status::Passed => {
+            let mut failed = false;
+            for prerequisite in &prop.prerequisites {
+                if is_failed(prerequisite_status(deps.as_ref(), &env, prerequisite)?) {
+                    failed = true;
+                    break;
+                }
+            }
+            failed
+        }
+        _ => false,
+    };
+    if !closable {
         return Err(ContractError::WrongCloseStatus {});
     }
 
     let old_status = prop.status;
 
     prop.status = Status::Closed;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    record_turnout(deps.storage, &env.block, &mut prop)?;
+    proposals().save(deps.storage, proposal_id, &prop)?;
 
     let hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
@@ -539,6 +1182,128 @@ pub fn execute_close(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+/// Returns the end of the commit phase, if the module uses
+/// commit-reveal voting, and the expiration of a proposal whose voting
+/// begins at START and lasts for MAX_VOTING_PERIOD.
+fn voting_expirations(
+    config: &Config,
+    max_voting_period: Duration,
+    start: &BlockInfo,
+) -> StdResult<(Option<Expiration>, Expiration)> {
+    // Proposals using commit-reveal voting are committed to for the
+    // voting period and then revealed. They expire once the reveal
+    // period ends.
+    Ok(match config.commit_reveal {
+        Some(commit_reveal) => {
+            let commit_expiration = max_voting_period.after(start);
+            (
+                Some(commit_expiration),
+                (commit_expiration + commit_reveal.reveal_period)?,
+            )
+        }
+        None => (None, max_voting_period.after(start)),
+    })
+}
+
+/// Returns the threshold and voting period of a proposal containing
+/// MSGS whose proposer chose VOTING_PERIOD, along with the index of
+/// the threshold rule matching it, if any. Adaptive quorums are fixed
+/// with TURNOUT.
+///
+/// Proposals matched by a threshold rule use that rule's threshold
+/// and voting period, which may not be chosen. Other proposals use
+/// the module's threshold, with the quorum set by the module's
+/// quorum schedule for their voting period if there is one.
+fn threshold_and_voting_period(
+    config: &Config,
+    turnout: Option<Decimal>,
+    msgs: &[CosmosMsg<Empty>],
+    voting_period: Option<Duration>,
+) -> Result<(Threshold, Duration, Option<usize>), ContractError> {
+    match find_threshold_rule(&config.threshold_rules, msgs) {
+        Some((index, rule)) => {
+            if voting_period.is_some() {
+                return Err(ContractError::ThresholdRuleVotingPeriod { rule: index });
+            }
+            Ok((
+                rule.threshold.fix_quorum(turnout),
+                rule.max_voting_period,
+                Some(index),
+            ))
+        }
+        None => {
+            let choice = config.voting_period_choice.as_ref();
+            let voting_period =
+                proposal_voting_period(choice, voting_period, config.max_voting_period)?;
+            let threshold = config.threshold.fix_quorum(turnout);
+            let threshold = match choice.and_then(|choice| choice.quorum(&voting_period)) {
+                // The step is applied to the fixed quorum, so it only
+                // raises an adaptive quorum above its current value.
+                Some(quorum) => threshold.raise_quorum(quorum),
+                None => threshold,
+            };
+            Ok((threshold, voting_period, None))
+        }
+    }
+}
+
+/// Validates CHOICE, and that it only has a quorum schedule if
+/// THRESHOLD has a quorum for the schedule to replace.
+fn validate_voting_period_choice(
+    choice: &VotingPeriodChoice,
+    threshold: &Threshold,
+    min_voting_period: Option<&Duration>,
+    max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    choice.validate(min_voting_period, max_voting_period)?;
+    match threshold {
+        Threshold::ThresholdQuorum { .. } | Threshold::AdaptiveQuorum { .. } => Ok(()),
+        _ if choice.quorum_schedule.is_empty() => Ok(()),
+        _ => Err(ContractError::QuorumScheduleWithoutQuorum {}),
+    }
+}
+
+/// Validates that the review period is non-zero and has the same
+/// units (height or time) as the max voting period it precedes.
+fn validate_review_period(
+    review_period: &Duration,
+    max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    match (review_period, max_voting_period) {
+        (Duration::Height(0), _) | (Duration::Time(0), _) => {
+            Err(ContractError::ZeroReviewPeriod {})
+        }
+        (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+            Ok(())
+        }
+        _ => Err(ContractError::ReviewPeriodUnitsConflict {}),
+    }
+}
+
+/// Returns the current status of a prerequisite proposal. Proposals
+/// in this module are loaded from storage instead of being queried.
+fn prerequisite_status(
+    deps: Deps,
+    env: &Env,
+    prerequisite: &CheckedPrerequisite,
+) -> Result<Status, ContractError> {
+    let no_such_prerequisite = || ContractError::NoSuchPrerequisite {
+        proposal_module: prerequisite.proposal_module.to_string(),
+        proposal_id: prerequisite.proposal_id,
+    };
+    if prerequisite.proposal_module == env.contract.address {
+        let mut prop = proposals()
+            .may_load(deps.storage, prerequisite.proposal_id)?
+            .ok_or_else(no_such_prerequisite)?;
+        prop.update_status(&env.block);
+        Ok(prop.status)
+    } else {
+        prerequisite
+            .query_status(&deps.querier)
+            .map_err(|_| no_such_prerequisite())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
@@ -550,6 +1315,14 @@ pub fn execute_update_config(
     allow_revoting: bool,
     dao: String,
     close_proposal_on_execution_failure: bool,
+    anti_sniping: Option<AntiSnipingConfig>,
+    threshold_rules: Vec<ThresholdRule>,
+    commit_reveal: Option<CommitRevealConfig>,
+    delegation_registry: Option<String>,
+    partial_execution: bool,
+    power_transform: Option<PowerTransform>,
+    review_period: Option<Duration>,
+    voting_period_choice: Option<VotingPeriodChoice>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -562,6 +1335,33 @@ pub fn execute_update_config(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(min_voting_period, max_voting_period)?;
+    if let Some(anti_sniping) = anti_sniping {
+        anti_sniping.validate(&max_voting_period)?;
+    }
+    if let Some(commit_reveal) = commit_reveal {
+        if anti_sniping.is_some() {
+            return Err(ContractError::CommitRevealAntiSniping {});
+        }
+        commit_reveal.validate(&max_voting_period)?;
+    }
+    validate_threshold_rules(deps.api, &threshold_rules, &max_voting_period)?;
+    if let Some(power_transform) = power_transform {
+        power_transform.validate()?;
+    }
+    if let Some(review_period) = review_period {
+        validate_review_period(&review_period, &max_voting_period)?;
+    }
+    if let Some(choice) = &voting_period_choice {
+        validate_voting_period_choice(
+            choice,
+            &threshold,
+            min_voting_period.as_ref(),
+            &max_voting_period,
+        )?;
+    }
+    let delegation_registry = delegation_registry
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
 
     CONFIG.save(
         deps.storage,
@@ -573,6 +1373,14 @@ pub fn execute_update_config(
             allow_revoting,
             dao,
             close_proposal_on_execution_failure,
+            anti_sniping,
+            threshold_rules,
+            commit_reveal,
+            delegation_registry,
+            partial_execution,
+            power_transform,
+            review_period,
+            voting_period_choice,
         },
     )?;
 
@@ -724,11 +1532,39 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ListVotesByVoter {
+            voter,
+            start_after,
+            limit,
+        } => query_list_votes_by_voter(deps, voter, start_after, limit),
+        QueryMsg::ListProposalVersions {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_proposal_versions(deps, proposal_id, start_after, limit),
+        QueryMsg::Schedule { proposal_id } => {
+            to_binary(&SCHEDULES.may_load(deps.storage, proposal_id)?)
+        }
+        QueryMsg::ListInstalments {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_list_instalments(deps, proposal_id, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::ReverseProposals {
             start_before,
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ListProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => query_list_proposals_by_status(deps, env, status, start_after, limit),
+        QueryMsg::ListProposalsByProposer {
+            proposer,
+            start_after,
+            limit,
+        } => query_list_proposals_by_proposer(deps, env, proposer, start_after, limit),
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
@@ -746,7 +1582,7 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let proposal = proposals().load(deps.storage, id)?;
     to_binary(&proposal.into_response(&env.block, id))
 }
 
@@ -763,7 +1599,7 @@ pub fn query_list_proposals(
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
@@ -782,7 +1618,7 @@ pub fn query_reverse_proposals(
 ) -> StdResult<Binary> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let max = start_before.map(Bound::exclusive);
-    let props: Vec<ProposalResponse> = PROPOSALS
+    let props: Vec<ProposalResponse> = proposals()
         .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
@@ -793,6 +1629,101 @@ pub fn query_reverse_proposals(
     to_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_list_proposals_by_status(
+    deps: Deps,
+    env: Env,
+    status: Status,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let scan_limit = limit.max(MAX_PROPOSALS_SCANNED);
+    // Proposal status is only updated in storage when the proposal is
+    // voted on or executed, so a proposal stored as open may have
+    // since passed or been rejected. All other stored statuses are
+    // final.
+    let stored = match status {
+        Status::Open => vec![Status::Open],
+        Status::Passed | Status::Rejected => vec![status, Status::Open],
+        _ => vec![status],
+    };
+    let mut props = vec![];
+    // The highest proposal ID up to which every stored status has
+    // been examined, if the scan of any of them was cut short.
+    let mut scanned_to: Option<u64> = None;
+    for stored in stored {
+        let scanned = proposals()
+            .idx
+            .status
+            .prefix(stored.to_string())
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(scan_limit)
+            .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+        if scanned.len() == scan_limit {
+            let last = scanned[scan_limit - 1].0;
+            scanned_to = Some(scanned_to.map_or(last, |to| to.min(last)));
+        }
+        props.extend(
+            scanned
+                .into_iter()
+                .filter(|(_, prop)| prop.current_status(&env.block) == status),
+        );
+    }
+    props.sort_by_key(|(id, _)| *id);
+    if let Some(to) = scanned_to {
+        props.retain(|(id, _)| *id <= to);
+    }
+
+    let next_start_after = if props.len() > limit {
+        props.truncate(limit);
+        props.last().map(|(id, _)| *id)
+    } else {
+        scanned_to
+    };
+    let proposals = props
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .collect();
+
+    to_binary(&ProposalStatusListResponse {
+        proposals,
+        next_start_after,
+    })
+}
+
+pub fn query_list_proposals_by_proposer(
+    deps: Deps,
+    env: Env,
+    proposer: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let proposer = deps.api.addr_validate(&proposer)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = proposals()
+        .idx
+        .proposer
+        .prefix(proposer)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id))
+        .collect();
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
     let proposal_count = PROPOSAL_COUNT.load(deps.storage)?;
     to_binary(&proposal_count)
@@ -810,6 +1741,8 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
         vote: ballot.vote,
         power: ballot.power,
         rationale: ballot.rationale,
+        commitment: ballot.commitment,
+        delegated_power: ballot.delegated_power,
     });
     to_binary(&VoteResponse { vote })
 }
@@ -837,6 +1770,8 @@ pub fn query_list_votes(
                 vote: ballot.vote,
                 power: ballot.power,
                 rationale: ballot.rationale,
+                commitment: ballot.commitment,
+                delegated_power: ballot.delegated_power,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
@@ -844,6 +1779,82 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_list_votes_by_voter(
+    deps: Deps,
+    voter: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let ballots = VOTER_BALLOTS
+        .prefix(&voter)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|proposal_id| {
+            let proposal_id = proposal_id?;
+            Ok(VoterBallotInfo {
+                proposal_id,
+                ballot: BALLOTS.load(deps.storage, (proposal_id, &voter))?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoterBallotListResponse { ballots })
+}
+
+pub fn query_list_proposal_versions(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let versions = PROPOSAL_VERSIONS
+        .prefix(proposal_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|item| item.map(|(_, version)| version))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalVersionListResponse { versions })
+}
+
+pub fn query_list_instalments(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let instalments = INSTALMENTS
+        .prefix(proposal_id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|item| item.map(|(_, instalment)| instalment))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&InstalmentListResponse { instalments })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&dao_interface::voting::InfoResponse { info })
@@ -880,6 +1891,14 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                     allow_revoting: current_config.allow_revoting,
                     dao: current_config.dao.clone(),
                     close_proposal_on_execution_failure,
+                    anti_sniping: None,
+                    threshold_rules: vec![],
+                    commit_reveal: None,
+                    delegation_registry: None,
+                    partial_execution: false,
+                    power_transform: None,
+                    review_period: None,
+                    voting_period_choice: None,
                 },
             )?;
 
@@ -924,12 +1943,25 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                         status: v1_status_to_v2(prop.status),
                         votes: v1_votes_to_v2(prop.votes),
                         allow_revoting: prop.allow_revoting,
+                        anti_sniping: None,
+                        extensions: 0,
+                        commit_expiration: None,
+                        prerequisites: vec![],
+                        execute_at: None,
+                        recurrence: None,
+                        voting_period: None,
+                        turnout_recorded: false,
+                        msg_results: vec![],
+                        power_transform: None,
+                        review_expiration: None,
+                        version: 0,
                     };
 
-                    PROPOSALS
+                    proposals()
                         .save(deps.storage, id, &migrated_proposal)
                         .map_err(|e| e.into())
                 })?;
+            index_voter_ballots(deps.storage)?;
 
             Ok(Response::default()
                 .add_attribute("action", "migrate")
@@ -937,10 +1969,64 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
                 .add_submessages(pre_propose_messages))
         }
 
-        MigrateMsg::FromCompatible {} => Ok(Response::default()
-            .add_attribute("action", "migrate")
-            .add_attribute("from", "compatible")),
+        MigrateMsg::FromCompatible {} => {
+            // Re-save proposals so that they are added to the status,
+            // turnout, and proposer indexes.
+            let current_proposals = proposals()
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+            for (id, prop) in current_proposals {
+                proposals().save(deps.storage, id, &prop)?;
+            }
+            index_voter_ballots(deps.storage)?;
+
+            Ok(Response::default()
+                .add_attribute("action", "migrate")
+                .add_attribute("from", "compatible"))
+        }
+    }
+}
+
+/// Records the turnout of proposals whose voting period has ended
+/// without their turnout having been recorded, such as rejected
+/// proposals that are never closed, so that they are counted before
+/// the turnout is next used.
+fn record_expired_turnouts(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    let mut expired = vec![];
+    for (kind, now) in [
+        (EXPIRATION_HEIGHT, block.height),
+        (EXPIRATION_TIME, block.time.nanos()),
+    ] {
+        let unrecorded = proposals()
+            .idx
+            .turnout
+            .sub_prefix((false as u8, kind))
+            .range(
+                storage,
+                None,
+                Some(Bound::inclusive((now, u64::MAX))),
+                Order::Ascending,
+            )
+            .take(MAX_TURNOUTS_RECORDED)
+            .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+        expired.extend(unrecorded);
+    }
+    for (id, mut prop) in expired {
+        record_turnout(storage, block, &mut prop)?;
+        proposals().save(storage, id, &prop)?;
     }
+    Ok(())
+}
+
+/// Adds every ballot in `BALLOTS` to the `VOTER_BALLOTS` index.
+fn index_voter_ballots(storage: &mut dyn Storage) -> StdResult<()> {
+    let keys = BALLOTS
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Addr)>>>()?;
+    for (proposal_id, voter) in keys {
+        VOTER_BALLOTS.save(storage, (&voter, proposal_id), &Empty {})?;
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -948,7 +2034,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+            proposals().update(deps.storage, proposal_id, |prop| match prop {
                 Some(mut prop) => {
                     prop.status = Status::ExecutionFailed;
 
@@ -956,9 +2042,51 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
                 }
                 None => Err(ContractError::NoSuchProposal { id: proposal_id }),
             })?;
+            // A recurring proposal whose first instalment fails is
+            // not scheduled.
+            SCHEDULES.remove(deps.storage, proposal_id);
+            INSTALMENTS.remove(deps.storage, (proposal_id, 0));
 
             Ok(Response::new().add_attribute("proposal_execution_failed", proposal_id.to_string()))
         }
+        TaggedReplyId::FailedProposalMessage(proposal_id, index) => {
+            let error = msg.result.into_result().err().unwrap_or_default();
+            proposals().update(deps.storage, proposal_id, |prop| match prop {
+                Some(mut prop) => {
+                    prop.msg_results[index as usize] = MessageResult::Failed { error };
+                    Ok(prop)
+                }
+                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("proposal_message_failed", format!("{proposal_id}:{index}")))
+        }
+        TaggedReplyId::FailedInstalment(proposal_id, index) => {
+            let error = msg.result.into_result().err().unwrap_or_default();
+            let config = CONFIG.load(deps.storage)?;
+            let mut schedule = SCHEDULES.load(deps.storage, proposal_id)?;
+            // The schedule was advanced past the failed instalment
+            // before it was executed.
+            let index_in_schedule = schedule.executed - 1;
+            let mut instalment =
+                INSTALMENTS.load(deps.storage, (proposal_id, index_in_schedule))?;
+            if config.partial_execution {
+                instalment.msg_results[index as usize] = MessageResult::Failed { error };
+            } else {
+                instalment.error = Some(error);
+            }
+            INSTALMENTS.save(deps.storage, (proposal_id, index_in_schedule), &instalment)?;
+            if !config.partial_execution && config.close_proposal_on_execution_failure {
+                schedule.cancelled = true;
+                SCHEDULES.save(deps.storage, proposal_id, &schedule)?;
+            }
+
+            Ok(Response::new().add_attribute(
+                "instalment_failed",
+                format!("{proposal_id}:{index_in_schedule}"),
+            ))
+        }
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
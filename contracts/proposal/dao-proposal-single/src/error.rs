@@ -2,7 +2,7 @@ use std::u64;
 
 use cosmwasm_std::StdError;
 use cw_hooks::HookError;
-use cw_utils::ParseReplyError;
+use cw_utils::{Expiration, ParseReplyError};
 use dao_voting::reply::error::TagError;
 use thiserror::Error;
 
@@ -56,7 +56,7 @@ pub enum ContractError {
     #[error("proposal is closed")]
     Closed {},
 
-    #[error("only rejected proposals may be closed")]
+    #[error("only rejected proposals, or proposals with a rejected or closed prerequisite, may be closed")]
     WrongCloseStatus {},
 
     #[error("the DAO is currently inactive, you cannot create proposals")]
@@ -89,4 +89,93 @@ pub enum ContractError {
 
     #[error("can not migrate. current version is up to date")]
     AlreadyMigrated {},
+
+    #[error("threshold rule voting periods must have the same units as, and be at least as long as, max_voting_period")]
+    InvalidThresholdRuleVotingPeriod {},
+
+    #[error("threshold rule ({rule}) is never used as rule ({by}) precedes it and matches every proposal it does")]
+    ShadowedThresholdRule { rule: usize, by: usize },
+
+    #[error("reveal period must be non-zero")]
+    ZeroRevealPeriod {},
+
+    #[error("reveal period must have the same units as max_voting_period (height or time)")]
+    RevealPeriodUnitsConflict {},
+
+    #[error("anti-sniping can not be used with commit-reveal voting")]
+    CommitRevealAntiSniping {},
+
+    #[error("proposal ({id}) uses commit-reveal voting. commit to a vote and reveal it instead")]
+    CommitRevealRequired { id: u64 },
+
+    #[error("proposal ({id}) does not use commit-reveal voting")]
+    NotCommitReveal { id: u64 },
+
+    #[error("the commit phase of proposal ({id}) has ended")]
+    CommitPhaseOver { id: u64 },
+
+    #[error("proposal ({id}) is not in its reveal phase")]
+    NotRevealPhase { id: u64 },
+
+    #[error("revealed vote and salt do not match the committed hash")]
+    InvalidReveal {},
+
+    #[error("vote has already been revealed")]
+    AlreadyRevealed {},
+
+    #[error("vote has not been revealed")]
+    NotRevealed {},
+
+    #[error("prerequisite proposal ({proposal_id}) does not exist in proposal module ({proposal_module})")]
+    NoSuchPrerequisite {
+        proposal_module: String,
+        proposal_id: u64,
+    },
+
+    #[error("prerequisite proposal ({proposal_id}) in proposal module ({proposal_module}) has not been executed")]
+    PrerequisiteNotExecuted {
+        proposal_module: String,
+        proposal_id: u64,
+    },
+
+    #[error("execute_at may not be never")]
+    InvalidExecuteAt {},
+
+    #[error("proposal ({id}) may not be executed until {execute_at}")]
+    ExecuteAtNotReached { id: u64, execute_at: Expiration },
+
+    #[error("review period must be non-zero")]
+    ZeroReviewPeriod {},
+
+    #[error("review period must have the same units as max_voting_period (height or time)")]
+    ReviewPeriodUnitsConflict {},
+
+    #[error("proposal ({id}) is in its review period and may not be voted on yet")]
+    InReviewPeriod { id: u64 },
+
+    #[error("proposal ({id}) is not in its review period and may not be amended")]
+    NotInReviewPeriod { id: u64 },
+
+    #[error("proposal ({id}) has no schedule. only executed recurring proposals have schedules")]
+    NoSchedule { id: u64 },
+
+    #[error("proposal ({id}) has not been executed")]
+    NotExecuted { id: u64 },
+
+    #[error("the schedule of proposal ({id}) has been cancelled")]
+    ScheduleCancelled { id: u64 },
+
+    #[error("all instalments of proposal ({id}) have been executed")]
+    ScheduleComplete { id: u64 },
+
+    #[error("the next instalment of proposal ({id}) may not be executed until {next}")]
+    InstalmentNotDue { id: u64, next: Expiration },
+
+    #[error(
+        "proposals matching threshold rule ({rule}) use its voting period, which may not be chosen"
+    )]
+    ThresholdRuleVotingPeriod { rule: usize },
+
+    #[error("a quorum schedule requires a threshold with a quorum")]
+    QuorumScheduleWithoutQuorum {},
 }
@@ -1,6 +1,8 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod commit_reveal;
 pub mod contract;
+mod delegation;
 mod error;
 pub mod msg;
 pub mod proposal;
@@ -10,6 +12,7 @@ pub mod query;
 mod testing;
 
 pub mod state;
+pub mod threshold_rules;
 mod v1_state;
 
 pub use crate::error::ContractError;
@@ -1,9 +1,13 @@
+use crate::commit_reveal::CommitRevealConfig;
+use crate::threshold_rules::ThresholdRule;
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, CosmosMsg, Empty};
 use cw_utils::Duration;
 use dao_macros::proposal_module_query;
 use dao_voting::{
-    pre_propose::PreProposeInfo, proposal::SingleChoiceProposeMsg, threshold::Threshold,
-    voting::Vote,
+    anti_sniping::AntiSnipingConfig, power_transform::PowerTransform, pre_propose::PreProposeInfo,
+    proposal::SingleChoiceProposeMsg, status::Status, threshold::Threshold, voting::Vote,
+    voting_period::VotingPeriodChoice,
 };
 
 #[cw_serde]
@@ -38,6 +42,45 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, votes cast in the final window of a proposal's voting
+    /// period that change its outcome (passing to failing or vice
+    /// versa) will extend the proposal's expiration.
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// Rules assigning stricter thresholds and longer voting periods
+    /// to proposals based on the messages they contain. When a
+    /// proposal is created the first rule matching any of its
+    /// messages is applied. Proposals matching no rule use
+    /// `threshold` and `max_voting_period`.
+    pub threshold_rules: Vec<ThresholdRule>,
+    /// If set, proposals use secret ballots. Voters commit to a hash
+    /// of their vote during the voting period and reveal it during
+    /// the reveal period that follows. Only revealed votes are
+    /// counted. May not be combined with `anti_sniping`.
+    pub commit_reveal: Option<CommitRevealConfig>,
+    /// If set, the votes of delegates in this
+    /// `dao-delegation-registry` are counted on behalf of their
+    /// delegators who do not vote themselves.
+    pub delegation_registry: Option<String>,
+    /// If set to true each message of a proposal is executed on its
+    /// own when the proposal is executed, and the failure of one
+    /// message does not prevent the others from executing. The
+    /// result of executing each message is recorded in the
+    /// proposal. When set, `close_proposal_on_execution_failure`
+    /// has no effect.
+    pub partial_execution: bool,
+    /// If set, voters' voting power is transformed before it is
+    /// tallied, dampening the influence of large holders.
+    pub power_transform: Option<PowerTransform>,
+    /// If set, proposals may be amended by their proposer for this
+    /// amount of time after they are created. No votes are accepted
+    /// during the review period, and the voting period begins once
+    /// it ends.
+    pub review_period: Option<Duration>,
+    /// If set, proposers may choose the voting period of proposals
+    /// that match no threshold rule, between `min` and
+    /// `max_voting_period`. Proposals with short voting periods may
+    /// be required to reach a higher quorum.
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 #[cw_serde]
@@ -56,6 +99,60 @@ pub enum ExecuteMsg {
         /// the vote.
         rationale: Option<String>,
     },
+    /// Commits to a vote on a commit-reveal proposal without
+    /// revealing it. The commitment is computed by
+    /// `commit_reveal::commitment`. May only be called during the
+    /// proposal's commit phase. If revoting is allowed, an
+    /// unrevealed commitment may be replaced.
+    CommitVote {
+        /// The ID of the proposal to commit to a vote on.
+        proposal_id: u64,
+        /// The hash of the sender's vote and a secret salt.
+        commitment: Binary,
+    },
+    /// Reveals a vote previously committed to on a commit-reveal
+    /// proposal, counting it. May only be called during the
+    /// proposal's reveal phase.
+    RevealVote {
+        /// The ID of the proposal to reveal a vote on.
+        proposal_id: u64,
+        /// The vote that was committed to.
+        vote: Vote,
+        /// The salt used when computing the commitment.
+        salt: String,
+        /// An optional rationale for why this vote was cast.
+        rationale: Option<String>,
+    },
+    /// Replaces the title, description, and messages of a proposal
+    /// during its review period. Only the proposal's proposer may
+    /// amend it. The threshold and voting period of the proposal are
+    /// recomputed for its new messages, using the module's config
+    /// and turnout from when the proposal was created.
+    Amend {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        /// The new title of the proposal.
+        title: String,
+        /// The new description of the proposal.
+        description: String,
+        /// The new messages of the proposal.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Executes the next instalment of an executed recurring
+    /// proposal. May be called by any address once the instalment is
+    /// due. Failures of the instalment are recorded on it rather than
+    /// failing this message.
+    ExecuteInstalment {
+        /// The ID of the recurring proposal.
+        proposal_id: u64,
+    },
+    /// Cancels the schedule of an executed recurring proposal so that
+    /// no further instalments are executed. Only the DAO may call
+    /// this method.
+    CancelSchedule {
+        /// The ID of the recurring proposal.
+        proposal_id: u64,
+    },
     /// Updates the sender's rationale for their vote on the specified
     /// proposal. Errors if no vote vote has been cast.
     UpdateRationale {
@@ -110,6 +207,40 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// If set, votes cast in the final window of a proposal's
+        /// voting period that change its outcome will extend the
+        /// proposal's expiration. This will only apply to proposals
+        /// created after the config update.
+        anti_sniping: Option<AntiSnipingConfig>,
+        /// Rules assigning stricter thresholds and longer voting
+        /// periods to proposals based on the messages they
+        /// contain. This will only apply to proposals created after
+        /// the config update.
+        threshold_rules: Vec<ThresholdRule>,
+        /// If set, proposals use secret ballots. This will only
+        /// apply to proposals created after the config update.
+        commit_reveal: Option<CommitRevealConfig>,
+        /// If set, the votes of delegates in this delegation
+        /// registry are counted on behalf of their delegators. This
+        /// applies to votes cast after the config update.
+        delegation_registry: Option<String>,
+        /// If set to true each message of a proposal is executed on
+        /// its own and the failure of one message does not prevent
+        /// the others from executing. Applies to all proposals
+        /// executed after the config update.
+        partial_execution: bool,
+        /// If set, voters' voting power is transformed before it is
+        /// tallied. This will only apply to proposals created after
+        /// the config update.
+        power_transform: Option<PowerTransform>,
+        /// If set, proposals may be amended by their proposer for
+        /// this amount of time before voting begins. This will only
+        /// apply to proposals created after the config update.
+        review_period: Option<Duration>,
+        /// If set, proposers may choose the voting period of
+        /// proposals that match no threshold rule. This will only
+        /// apply to proposals created after the config update.
+        voting_period_choice: Option<VotingPeriodChoice>,
     },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
@@ -167,6 +298,36 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists proposals with STATUS in ascending order of proposal
+    /// ID. Status is computed as of the current block, so a proposal
+    /// whose voting period has ended will be listed as passed or
+    /// rejected even if no message has updated it since. Proposals
+    /// are listed from a bounded scan of storage, so keep paging with
+    /// the response's `next_start_after` until it is None.
+    #[returns(crate::query::ProposalStatusListResponse)]
+    ListProposalsByStatus {
+        /// The status of proposals to list.
+        status: Status,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists proposals created by PROPOSER in ascending order of
+    /// proposal ID.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposalsByProposer {
+        /// The address of the proposer.
+        proposer: String,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
     /// Returns a voters position on a propsal.
     #[returns(crate::query::VoteResponse)]
     GetVote { proposal_id: u64, voter: String },
@@ -183,6 +344,50 @@ pub enum QueryMsg {
         /// query. If no limit is specified a max of 30 are returned.
         limit: Option<u64>,
     },
+    /// Lists the ballots cast by VOTER in ascending order of
+    /// proposal ID.
+    #[returns(crate::query::VoterBallotListResponse)]
+    ListVotesByVoter {
+        /// The address of the voter.
+        voter: String,
+        /// The proposal ID to start listing ballots after.
+        start_after: Option<u64>,
+        /// The maximum number of ballots to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the versions of a proposal that have been replaced by
+    /// amendments in ascending order of version. The current version
+    /// of a proposal is returned by the `Proposal` query.
+    #[returns(crate::query::ProposalVersionListResponse)]
+    ListProposalVersions {
+        /// The ID of the proposal to list the versions of.
+        proposal_id: u64,
+        /// The version to start listing versions after.
+        start_after: Option<u64>,
+        /// The maximum number of versions to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the schedule of an executed recurring proposal, or
+    /// None if the proposal is not recurring or has not been executed.
+    #[returns(::std::option::Option<crate::state::Schedule>)]
+    Schedule { proposal_id: u64 },
+    /// Lists the executed instalments of a recurring proposal in
+    /// ascending order of index.
+    #[returns(crate::query::InstalmentListResponse)]
+    ListInstalments {
+        /// The ID of the recurring proposal.
+        proposal_id: u64,
+        /// The index to start listing instalments after.
+        start_after: Option<u64>,
+        /// The maximum number of instalments to return in response to
+        /// this query. If no limit is specified a max of 30 are
+        /// returned.
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this module.
     #[returns(::std::primitive::u64)]
     ProposalCount {},
@@ -220,5 +425,8 @@ pub enum MigrateMsg {
         /// option allows for instantiating a prepropose module which will handle deposit verification and return logic.
         pre_propose_info: PreProposeInfo,
     },
+    /// Migrates from a compatible version of this contract. This
+    /// rebuilds the status, proposer, and voter indexes over existing
+    /// proposals and ballots and is safe to run more than once.
     FromCompatible {},
 }
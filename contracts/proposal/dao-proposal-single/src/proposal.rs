@@ -1,10 +1,14 @@
 use crate::query::ProposalResponse;
-use crate::state::PROPOSAL_COUNT;
+use crate::state::{PROPOSAL_COUNT, TURNOUT};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
+use dao_voting::anti_sniping::{block_at_expiration, AntiSnipingConfig};
+use dao_voting::power_transform::PowerTransform;
+use dao_voting::prerequisite::CheckedPrerequisite;
+use dao_voting::recurrence::Recurrence;
 use dao_voting::status::Status;
-use dao_voting::threshold::{PercentageThreshold, Threshold};
+use dao_voting::threshold::{update_turnout, PercentageThreshold, Threshold};
 use dao_voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
 
 #[cw_serde]
@@ -34,6 +38,68 @@ pub struct SingleChoiceProposal {
     pub status: Status,
     pub votes: Votes,
     pub allow_revoting: bool,
+    /// The anti-sniping configuration of the module at the time of
+    /// this proposal's creation, if any.
+    #[serde(default)]
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// The number of times this proposal's expiration has been
+    /// extended by outcome changing votes cast shortly before it
+    /// expired.
+    #[serde(default)]
+    pub extensions: u64,
+    /// If this proposal uses commit-reveal voting, the time at which
+    /// its commit phase ends and its reveal phase begins. The reveal
+    /// phase ends at `expiration`.
+    #[serde(default)]
+    pub commit_expiration: Option<Expiration>,
+    /// Proposals that must be executed before this proposal may be
+    /// executed.
+    #[serde(default)]
+    pub prerequisites: Vec<CheckedPrerequisite>,
+    /// If set, the time before which this proposal may not be
+    /// executed.
+    #[serde(default)]
+    pub execute_at: Option<Expiration>,
+    /// If this proposal was executed with partial execution enabled,
+    /// the result of executing each of its messages in order.
+    #[serde(default)]
+    pub msg_results: Vec<MessageResult>,
+    /// The power transform of the module at the time of this
+    /// proposal's creation, if any. `total_power` has already been
+    /// transformed.
+    #[serde(default)]
+    pub power_transform: Option<PowerTransform>,
+    /// If set, the time at which this proposal's review period
+    /// ends. Until then, its proposer may amend it and no votes are
+    /// accepted.
+    #[serde(default)]
+    pub review_expiration: Option<Expiration>,
+    /// The number of times this proposal has been amended. Replaced
+    /// versions may be queried with `ListProposalVersions`.
+    #[serde(default)]
+    pub version: u64,
+    /// If set, this proposal's messages are executed repeatedly on
+    /// this schedule once it is executed.
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    /// The voting period chosen by this proposal's proposer, if
+    /// any. Proposals whose proposer did not choose one are voted on
+    /// for the module's max voting period.
+    #[serde(default)]
+    pub voting_period: Option<Duration>,
+    /// True once this proposal's turnout has been counted in the
+    /// module's moving average of turnout, which happens when its
+    /// outcome is first decided.
+    #[serde(default)]
+    pub turnout_recorded: bool,
+}
+
+/// The result of executing one message of a proposal executed with
+/// partial execution enabled.
+#[cw_serde]
+pub enum MessageResult {
+    Succeeded,
+    Failed { error: String },
 }
 
 pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
@@ -46,7 +112,44 @@ pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
     Ok(id)
 }
 
+/// Updates the moving average of turnout with the turnout of PROP if
+/// its outcome has been decided as of BLOCK and its turnout has not
+/// already been recorded. PROP must be saved afterwards.
+pub fn record_turnout(
+    store: &mut dyn Storage,
+    block: &BlockInfo,
+    prop: &mut SingleChoiceProposal,
+) -> StdResult<()> {
+    if prop.turnout_recorded || prop.current_status(block) == Status::Open {
+        return Ok(());
+    }
+    prop.turnout_recorded = true;
+    if prop.total_power.is_zero() || !prop.bounds_votes() {
+        return Ok(());
+    }
+    let turnout = Decimal::from_ratio(prop.votes.total(), prop.total_power);
+    let average = update_turnout(TURNOUT.may_load(store)?, turnout);
+    TURNOUT.save(store, &average)
+}
+
 impl SingleChoiceProposal {
+    /// Returns true if this proposal is in its review period, during
+    /// which it may be amended and not voted on.
+    pub fn is_in_review(&self, block: &BlockInfo) -> bool {
+        self.review_expiration.map_or(false, |review_expiration| {
+            !review_expiration.is_expired(block)
+        })
+    }
+
+    /// Returns true if this proposal's votes can not exceed its total
+    /// power, so that the outcome may be known before it expires.
+    pub fn bounds_votes(&self) -> bool {
+        match self.power_transform {
+            Some(transform) => transform.bounds_votes(),
+            None => true,
+        }
+    }
+
     /// Consumes the proposal and returns a version which may be used
     /// in a query response. Why is this necessary? Proposal
     /// statuses are only updated on vote, execute, and close
@@ -82,9 +185,10 @@ impl SingleChoiceProposal {
     /// expiration if no future sequence of possible votes can cause
     /// it to fail).
     pub fn is_passed(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed, or the votes still to be cast are
+        // not bounded by the total power, nothing is known until the
+        // proposal has expired.
+        if (self.allow_revoting || !self.bounds_votes()) && !self.expiration.is_expired(block) {
             return false;
         }
         // If the min voting period is set and not expired the
@@ -100,7 +204,7 @@ impl SingleChoiceProposal {
 
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.total_power.saturating_sub(self.votes.abstain);
                 does_vote_count_pass(self.votes.yes, options, percentage)
             }
             Threshold::ThresholdQuorum { threshold, quorum } => {
@@ -116,20 +220,60 @@ impl SingleChoiceProposal {
                     let options = self.votes.total() - self.votes.abstain;
                     does_vote_count_pass(self.votes.yes, options, threshold)
                 } else {
-                    let options = self.total_power - self.votes.abstain;
+                    let options = self.total_power.saturating_sub(self.votes.abstain);
                     does_vote_count_pass(self.votes.yes, options, threshold)
                 }
             }
             Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
+            // Adaptive quorums are fixed when proposals are created,
+            // so this is only reached by proposals constructed
+            // elsewhere.
+            Threshold::AdaptiveQuorum { .. } => SingleChoiceProposal {
+                threshold: self.threshold.fix_quorum(None),
+                ..self.clone()
+            }
+            .is_passed(block),
         }
     }
 
+    /// Returns true if this proposal would pass were voting to close
+    /// with the current tally.
+    pub fn is_passed_at_expiration(&self, block: &BlockInfo) -> bool {
+        self.is_passed(&block_at_expiration(block, &self.expiration))
+    }
+
+    /// Extends this proposal's expiration if anti-sniping is enabled,
+    /// BLOCK is inside of the extension window, and the proposal's
+    /// outcome at expiration differs from WAS_PASSING. Returns true
+    /// if the expiration was extended.
+    pub fn extend_if_outcome_changed(
+        &mut self,
+        block: &BlockInfo,
+        was_passing: bool,
+    ) -> StdResult<bool> {
+        let config = match self.anti_sniping {
+            Some(config) => config,
+            None => return Ok(false),
+        };
+        if self.status != Status::Open
+            || self.extensions >= config.max_extensions
+            || !config.is_in_window(block, &self.expiration)
+            || self.is_passed_at_expiration(block) == was_passing
+        {
+            return Ok(false);
+        }
+        self.expiration = (self.expiration + config.extension)?;
+        self.extensions += 1;
+        Ok(true)
+    }
+
     /// As above for the passed check, used to check if a proposal is
     /// already rejected.
     pub fn is_rejected(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed, or the votes still to be cast are
+        // not bounded by the total power, and the proposal is not
+        // expired no information is known.
+        if (self.allow_revoting || !self.bounds_votes()) && !self.expiration.is_expired(block) {
             return false;
         }
 
@@ -137,7 +281,7 @@ impl SingleChoiceProposal {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
             } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.total_power.saturating_sub(self.votes.abstain);
 
                 // If there is a 100% passing threshold..
                 if percentage_needed == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -199,7 +343,7 @@ impl SingleChoiceProposal {
                     (true, false) | (false, false) => {
                         // => consider all possible votes and see if
                         //    no votes meet threshold.
-                        let options = self.total_power - self.votes.abstain;
+                        let options = self.total_power.saturating_sub(self.votes.abstain);
 
                         // If there is a 100% passing threshold..
                         if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -231,9 +375,14 @@ impl SingleChoiceProposal {
             Threshold::AbsoluteCount { threshold } => {
                 // If all the outstanding votes voting yes would not
                 // cause this proposal to pass then it is rejected.
-                let outstanding_votes = self.total_power - self.votes.total();
+                let outstanding_votes = self.total_power.saturating_sub(self.votes.total());
                 self.votes.yes + outstanding_votes < threshold
             }
+            Threshold::AdaptiveQuorum { .. } => SingleChoiceProposal {
+                threshold: self.threshold.fix_quorum(None),
+                ..self.clone()
+            }
+            .is_rejected(block),
         }
     }
 }
@@ -245,6 +394,7 @@ mod test {
         testing::{mock_dependencies, mock_env},
         Decimal,
     };
+    use cw_utils::Duration;
 
     fn setup_prop(
         threshold: Threshold,
@@ -277,6 +427,18 @@ mod test {
             threshold,
             total_power,
             votes,
+            anti_sniping: None,
+            extensions: 0,
+            msg_results: vec![],
+            power_transform: None,
+            review_expiration: None,
+            version: 0,
+            commit_expiration: None,
+            prerequisites: vec![],
+            execute_at: None,
+            recurrence: None,
+            voting_period: None,
+            turnout_recorded: false,
         };
         (prop, block)
     }
@@ -1091,6 +1253,131 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_extend_if_outcome_changed() {
+        let threshold = Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+        };
+        let (mut prop, block) = setup_prop(
+            threshold,
+            Votes {
+                yes: Uint128::new(2),
+                no: Uint128::new(1),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(15),
+            false,
+            true,
+            false,
+        );
+        let original_expiration = prop.expiration;
+
+        // Without anti-sniping nothing happens.
+        assert!(!prop.extend_if_outcome_changed(&block, false).unwrap());
+
+        prop.anti_sniping = Some(AntiSnipingConfig {
+            window: Duration::Height(200),
+            extension: Duration::Height(10),
+            max_extensions: 1,
+        });
+
+        // Outcome is unchanged, no extension.
+        assert!(prop.is_passed_at_expiration(&block));
+        assert!(!prop.extend_if_outcome_changed(&block, true).unwrap());
+        assert_eq!(prop.expiration, original_expiration);
+
+        // Outcome has changed, expiration is extended.
+        assert!(prop.extend_if_outcome_changed(&block, false).unwrap());
+        assert_eq!(
+            prop.expiration,
+            (original_expiration + Duration::Height(10)).unwrap()
+        );
+        assert_eq!(prop.extensions, 1);
+
+        // Max extensions reached.
+        assert!(!prop.extend_if_outcome_changed(&block, false).unwrap());
+        assert_eq!(prop.extensions, 1);
+    }
+
+    #[test]
+    fn test_extend_outside_of_window() {
+        let threshold = Threshold::AbsoluteCount {
+            threshold: Uint128::new(2),
+        };
+        let (mut prop, block) = setup_prop(
+            threshold,
+            Votes {
+                yes: Uint128::new(2),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(15),
+            false,
+            true,
+            false,
+        );
+        prop.anti_sniping = Some(AntiSnipingConfig {
+            window: Duration::Height(50),
+            extension: Duration::Height(10),
+            max_extensions: 1,
+        });
+
+        // Expiration is 100 blocks away, so this vote is outside of
+        // the window.
+        assert!(!prop.extend_if_outcome_changed(&block, false).unwrap());
+        assert_eq!(prop.extensions, 0);
+    }
+
+    #[test]
+    fn test_square_root_completes_at_expiration() {
+        // 9 voters with 100 power each have a square root total power
+        // of 30, but may cast 90 votes between them.
+        let (mut prop, block) = setup_prop(
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            Votes {
+                yes: Uint128::new(20),
+                no: Uint128::new(30),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(30),
+            false,
+            true,
+            false,
+        );
+        prop.power_transform = Some(PowerTransform::SquareRoot {});
+        assert!(!prop.is_passed(&block));
+        assert!(!prop.is_rejected(&block));
+
+        prop.votes.yes = Uint128::new(60);
+        assert!(!prop.is_passed(&block));
+        prop.expiration = Expiration::AtHeight(block.height);
+        assert!(prop.is_passed(&block));
+
+        // Turnout is not recorded for such proposals, as it may be
+        // over 100%.
+        let storage = &mut mock_dependencies().storage;
+        record_turnout(storage, &block, &mut prop).unwrap();
+        assert_eq!(TURNOUT.may_load(storage).unwrap(), None);
+        assert!(prop.turnout_recorded);
+        prop.turnout_recorded = false;
+        prop.power_transform = Some(PowerTransform::Cap {
+            percent: Decimal::percent(10),
+        });
+        prop.votes.yes = Uint128::new(15);
+        prop.votes.no = Uint128::zero();
+        record_turnout(storage, &block, &mut prop).unwrap();
+        let turnout = TURNOUT.may_load(storage).unwrap();
+        assert!(turnout.is_some());
+
+        // Turnout is only recorded once.
+        prop.votes.yes = Uint128::new(30);
+        record_turnout(storage, &block, &mut prop).unwrap();
+        assert_eq!(TURNOUT.may_load(storage).unwrap(), turnout);
+    }
+
     #[test]
     fn test_proposal_ids_advance() {
         // do they advance, lets find out!
@@ -1,6 +1,9 @@
-use crate::proposal::SingleChoiceProposal;
+use crate::{
+    proposal::SingleChoiceProposal,
+    state::{Ballot, Instalment, ProposalVersion},
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Uint128};
 use dao_voting::voting::Vote;
 
 /// Information about a proposal returned by proposal queries.
@@ -16,12 +19,19 @@ pub struct ProposalResponse {
 pub struct VoteInfo {
     /// The address that voted.
     pub voter: Addr,
-    /// Position on the vote.
-    pub vote: Vote,
+    /// Position on the vote. None if the vote has been committed
+    /// to but not yet revealed.
+    pub vote: Option<Vote>,
     /// The voting power behind the vote.
     pub power: Uint128,
     /// Address-specified rationale for the vote.
     pub rationale: Option<String>,
+    /// The hash committed to if the vote was cast on a commit-reveal
+    /// proposal.
+    pub commitment: Option<Binary>,
+    /// The voting power of the voter's delegators cast along with
+    /// their vote.
+    pub delegated_power: Uint128,
 }
 
 /// Information about a vote.
@@ -43,3 +53,43 @@ pub struct VoteListResponse {
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// A page of proposals returned by `ListProposalsByStatus`.
+#[cw_serde]
+pub struct ProposalStatusListResponse {
+    pub proposals: Vec<ProposalResponse>,
+    /// The proposal ID to pass as `start_after` to continue listing,
+    /// or None if no proposals remain. As each query examines a
+    /// bounded number of stored proposals, a page may hold fewer
+    /// than `limit` proposals, or none, while this is set.
+    pub next_start_after: Option<u64>,
+}
+
+/// A ballot cast by a voter, returned by `ListVotesByVoter`.
+#[cw_serde]
+pub struct VoterBallotInfo {
+    /// The proposal the ballot was cast on.
+    pub proposal_id: u64,
+    /// The ballot, including its rationale.
+    pub ballot: Ballot,
+}
+
+/// The versions of a proposal replaced by amendments, returned by
+/// `ListProposalVersions`.
+#[cw_serde]
+pub struct ProposalVersionListResponse {
+    pub versions: Vec<ProposalVersion>,
+}
+
+/// The executed instalments of a recurring proposal, returned by
+/// `ListInstalments`.
+#[cw_serde]
+pub struct InstalmentListResponse {
+    pub instalments: Vec<Instalment>,
+}
+
+/// Information about the ballots cast by a voter.
+#[cw_serde]
+pub struct VoterBallotListResponse {
+    pub ballots: Vec<VoterBallotInfo>,
+}
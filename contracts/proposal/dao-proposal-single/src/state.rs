@@ -1,26 +1,56 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, Empty, Timestamp, Uint128};
 use cw_hooks::Hooks;
-use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
-use dao_voting::{pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::{Duration, Expiration};
+use dao_voting::{
+    anti_sniping::AntiSnipingConfig, power_transform::PowerTransform,
+    pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote,
+    voting_period::VotingPeriodChoice,
+};
 
-use crate::proposal::SingleChoiceProposal;
+use crate::{
+    commit_reveal::CommitRevealConfig,
+    proposal::{MessageResult, SingleChoiceProposal},
+    threshold_rules::ThresholdRule,
+};
 
 /// A vote cast for a proposal.
 #[cw_serde]
 pub struct Ballot {
     /// The amount of voting power behind the vote.
     pub power: Uint128,
-    /// The position.
-    pub vote: Vote,
+    /// The position. None if the ballot is a commitment to a vote
+    /// that has not yet been revealed.
+    pub vote: Option<Vote>,
 
     /// An optional rationale for why this vote was cast. If the key
     /// is missing (i.e. the ballot was cast in a v1 proposal module),
     /// we deserialize into None (i.e. Option::default()).
     #[serde(default)]
     pub rationale: Option<String>,
+    /// The hash committed to if the ballot was cast on a
+    /// commit-reveal proposal. Ballots cast on other proposals, or
+    /// by older versions of this module, deserialize into None.
+    #[serde(default)]
+    pub commitment: Option<Binary>,
+    /// The voting power of the voter's delegators cast along with
+    /// this ballot. Ballots saved by older versions of this module
+    /// deserialize into zero.
+    #[serde(default)]
+    pub delegated_power: Uint128,
+}
+
+/// A record of a delegator's voting power having been cast by their
+/// delegate.
+#[cw_serde]
+pub struct DelegatedBallot {
+    /// The delegate whose vote the power was cast with.
+    pub delegate: Addr,
+    /// The delegator's voting power.
+    pub power: Uint128,
 }
+
 /// The governance module's configuration.
 #[cw_serde]
 pub struct Config {
@@ -55,6 +85,49 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set, votes cast shortly before a proposal's expiration
+    /// that change its outcome will extend its voting period. If
+    /// the key is missing (i.e. the config was saved by an older
+    /// version of this module), we deserialize into None.
+    #[serde(default)]
+    pub anti_sniping: Option<AntiSnipingConfig>,
+    /// Rules assigning stricter thresholds and longer voting periods
+    /// to proposals based on the messages they contain. The first
+    /// matching rule is applied when a proposal is created. If the
+    /// key is missing, we deserialize into an empty list.
+    #[serde(default)]
+    pub threshold_rules: Vec<ThresholdRule>,
+    /// If set, votes are committed to as hashes during the voting
+    /// period and revealed afterwards so that voters can not see
+    /// how others have voted before casting their own vote. If the
+    /// key is missing, we deserialize into None.
+    #[serde(default)]
+    pub commit_reveal: Option<CommitRevealConfig>,
+    /// If set, the votes of delegates in this delegation registry
+    /// are counted on behalf of their delegators who do not vote
+    /// themselves. If the key is missing, we deserialize into None.
+    #[serde(default)]
+    pub delegation_registry: Option<Addr>,
+    /// If set to true each message of a proposal is executed on its
+    /// own and proposals are executed even if some of their messages
+    /// fail. If the key is missing, we deserialize into false.
+    #[serde(default)]
+    pub partial_execution: bool,
+    /// If set, the transformation applied to voters' voting power
+    /// before it is tallied. If the key is missing, we deserialize
+    /// into None.
+    #[serde(default)]
+    pub power_transform: Option<PowerTransform>,
+    /// If set, the amount of time after a proposal is created during
+    /// which its proposer may amend it and no votes are accepted. If
+    /// the key is missing, we deserialize into None.
+    #[serde(default)]
+    pub review_period: Option<Duration>,
+    /// If set, the bounds within which proposers may choose the
+    /// voting period of their proposals. If the key is missing, we
+    /// deserialize into None.
+    #[serde(default)]
+    pub voting_period_choice: Option<VotingPeriodChoice>,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -62,8 +135,78 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config_v2");
 /// The number of proposals that have been created.
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
-pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
+/// The exponential moving average of turnout on proposals whose
+/// outcome has been decided, used to set the quorum of proposals with
+/// an adaptive quorum threshold.
+pub const TURNOUT: Item<Decimal> = Item::new("turnout");
+
+pub struct ProposalIndexes<'a> {
+    /// Proposals indexed by their stored status. As statuses are
+    /// only updated when a proposal is voted on, executed, or
+    /// closed, proposals stored as open may have since passed or
+    /// been rejected.
+    pub status: MultiIndex<'a, String, SingleChoiceProposal, u64>,
+    /// Proposals indexed by whether their turnout has been recorded
+    /// and their expiration (see `expiration_key`), so that expired
+    /// proposals whose turnout has not been recorded may be found
+    /// without loading every proposal.
+    pub turnout: MultiIndex<'a, (u8, u8, u64), SingleChoiceProposal, u64>,
+    pub proposer: MultiIndex<'a, Addr, SingleChoiceProposal, u64>,
+}
+
+impl<'a> IndexList<SingleChoiceProposal> for ProposalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SingleChoiceProposal>> + '_> {
+        let v: Vec<&dyn Index<SingleChoiceProposal>> =
+            vec![&self.status, &self.turnout, &self.proposer];
+        Box::new(v.into_iter())
+    }
+}
+
+pub(crate) const EXPIRATION_HEIGHT: u8 = 0;
+pub(crate) const EXPIRATION_TIME: u8 = 1;
+pub(crate) const EXPIRATION_NEVER: u8 = 2;
+
+/// Orders expirations of the same kind by the height or time (in
+/// nanoseconds) at which they expire.
+pub(crate) fn expiration_key(expiration: &Expiration) -> (u8, u64) {
+    match expiration {
+        Expiration::AtHeight(height) => (EXPIRATION_HEIGHT, *height),
+        Expiration::AtTime(time) => (EXPIRATION_TIME, time.nanos()),
+        Expiration::Never {} => (EXPIRATION_NEVER, 0),
+    }
+}
+
+pub fn proposals<'a>() -> IndexedMap<'a, u64, SingleChoiceProposal, ProposalIndexes<'a>> {
+    let indexes = ProposalIndexes {
+        status: MultiIndex::new(
+            |_pk: &[u8], d: &SingleChoiceProposal| d.status.to_string(),
+            "proposals_v2",
+            "proposals_v2__status",
+        ),
+        turnout: MultiIndex::new(
+            |_pk: &[u8], d: &SingleChoiceProposal| {
+                let (kind, at) = expiration_key(&d.expiration);
+                (d.turnout_recorded as u8, kind, at)
+            },
+            "proposals_v2",
+            "proposals_v2__turnout",
+        ),
+        proposer: MultiIndex::new(
+            |_pk: &[u8], d: &SingleChoiceProposal| d.proposer.clone(),
+            "proposals_v2",
+            "proposals_v2__proposer",
+        ),
+    };
+    IndexedMap::new("proposals_v2", indexes)
+}
+
 pub const BALLOTS: Map<(u64, &Addr), Ballot> = Map::new("ballots");
+/// Index over `BALLOTS` by voter so that the proposals an address has
+/// voted on may be listed without scanning every proposal.
+pub const VOTER_BALLOTS: Map<(&Addr, u64), Empty> = Map::new("voter_ballots");
+/// Maps (proposal_id, delegator) to the delegate who cast the
+/// delegator's voting power on the proposal.
+pub const DELEGATED_BALLOTS: Map<(u64, &Addr), DelegatedBallot> = Map::new("delegated_ballots");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
@@ -71,3 +214,78 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+
+/// A version of a proposal that has been replaced by an amendment.
+#[cw_serde]
+pub struct ProposalVersion {
+    /// The version number. A proposal's first version is zero.
+    pub version: u64,
+    pub title: String,
+    pub description: String,
+    pub msgs: Vec<CosmosMsg<Empty>>,
+}
+
+/// Maps (proposal_id, version) to each version of a proposal that has
+/// been replaced by an amendment.
+pub const PROPOSAL_VERSIONS: Map<(u64, u64), ProposalVersion> = Map::new("proposal_versions");
+
+/// The module's config and turnout when a proposal with a review
+/// period was created. Amendments to the proposal select its
+/// threshold and voting period from these, so that config updates
+/// and proposals completing during its review period do not change
+/// them.
+#[cw_serde]
+pub struct CreationSnapshot {
+    pub config: Config,
+    pub turnout: Option<Decimal>,
+}
+
+/// Maps proposal IDs to the creation snapshots of proposals with a
+/// review period.
+pub const CREATION_SNAPSHOTS: Map<u64, CreationSnapshot> = Map::new("creation_snapshots");
+
+/// The execution schedule of a passed recurring proposal, created
+/// when the proposal is executed.
+#[cw_serde]
+pub struct Schedule {
+    /// The amount of time between instalments.
+    pub interval: Duration,
+    /// The total number of instalments.
+    pub count: u64,
+    /// The number of instalments that have been executed.
+    pub executed: u64,
+    /// The time at which the next instalment may be executed.
+    pub next: Expiration,
+    /// True if the schedule has been cancelled by the DAO, or by the
+    /// failure of an instalment if `close_proposal_on_execution_failure`
+    /// is set. No further instalments will be executed.
+    pub cancelled: bool,
+}
+
+/// An executed instalment of a recurring proposal.
+#[cw_serde]
+pub struct Instalment {
+    /// The index of the instalment. The instalment executed along
+    /// with the proposal has index zero.
+    pub index: u64,
+    /// The block height at which the instalment was executed.
+    pub height: u64,
+    /// The time at which the instalment was executed.
+    pub time: Timestamp,
+    /// The results of the instalment's messages if `partial_execution`
+    /// is set. Empty for the first instalment, whose results are
+    /// recorded on the proposal.
+    #[serde(default)]
+    pub msg_results: Vec<MessageResult>,
+    /// The error the instalment failed with, if `partial_execution`
+    /// is not set and its messages failed.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Maps proposal IDs to the schedules of executed recurring
+/// proposals.
+pub const SCHEDULES: Map<u64, Schedule> = Map::new("schedules");
+/// Maps (proposal_id, index) to each executed instalment of a
+/// recurring proposal.
+pub const INSTALMENTS: Map<(u64, u64), Instalment> = Map::new("instalments");
@@ -177,6 +177,14 @@ pub fn test_executed_prop_state_remains_after_vote_swing() {
             false,
         ),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -273,6 +281,14 @@ pub fn test_passed_prop_state_remains_after_vote_swing() {
             false,
         ),
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
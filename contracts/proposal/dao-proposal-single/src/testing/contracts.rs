@@ -21,6 +21,15 @@ pub(crate) fn cw4_group_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub(crate) fn delegation_registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_delegation_registry::contract::execute,
+        dao_delegation_registry::contract::instantiate,
+        dao_delegation_registry::contract::query,
+    );
+    Box::new(contract)
+}
+
 pub(crate) fn cw721_base_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         cw721_base::entry::execute,
@@ -127,6 +127,14 @@ where
         only_members_execute: false,
         allow_revoting: false,
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
         pre_propose_info,
     };
 
@@ -193,6 +201,10 @@ where
                 title: "A simple text proposal".to_string(),
                 description: "This is a simple text proposal".to_string(),
                 msgs: vec![],
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             },
         },
         &funds,
@@ -235,8 +247,10 @@ where
                 let expected = VoteResponse {
                     vote: Some(VoteInfo {
                         rationale: None,
+                        commitment: None,
+                        delegated_power: Uint128::zero(),
                         voter: Addr::unchecked(&voter),
-                        vote: position,
+                        vote: Some(position),
                         power: match deposit_config.deposit_info {
                             Some(CheckedDepositInfo {
                                 amount,
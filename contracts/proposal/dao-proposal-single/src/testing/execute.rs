@@ -9,6 +9,7 @@ use dao_voting::{
 };
 
 use crate::{
+    commit_reveal::commitment,
     msg::{ExecuteMsg, QueryMsg},
     query::ProposalResponse,
     testing::queries::{query_creation_policy, query_next_proposal_id},
@@ -78,6 +79,10 @@ pub(crate) fn make_proposal(
                     description: "description".to_string(),
                     msgs: msgs.clone(),
                     proposer: None,
+                    prerequisites: vec![],
+                    execute_at: None,
+                    recurrence: None,
+                    voting_period: None,
                 }),
                 &[],
             )
@@ -91,6 +96,10 @@ pub(crate) fn make_proposal(
                         title: "title".to_string(),
                         description: "description".to_string(),
                         msgs: msgs.clone(),
+                        prerequisites: vec![],
+                        execute_at: None,
+                        recurrence: None,
+                        voting_period: None,
                     },
                 },
                 &funds,
@@ -194,6 +203,50 @@ pub(crate) fn vote_on_proposal_with_rationale(
     .unwrap();
 }
 
+pub(crate) fn commit_vote(
+    app: &mut App,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    vote: Vote,
+    salt: &str,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment: commitment(proposal_id, &Addr::unchecked(sender), vote, salt),
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
+pub(crate) fn reveal_vote(
+    app: &mut App,
+    proposal_single: &Addr,
+    sender: &str,
+    proposal_id: u64,
+    vote: Vote,
+    salt: &str,
+) -> Result<(), ContractError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        proposal_single.clone(),
+        &ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt: salt.to_string(),
+            rationale: None,
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|e| e.downcast().unwrap())
+}
+
 pub(crate) fn update_rationale(
     app: &mut App,
     proposal_single: &Addr,
@@ -4,11 +4,14 @@ use dao_core::state::{ProposalModule, ProposalModuleStatus};
 
 use cw_hooks::HooksResponse;
 use dao_pre_propose_single as cppbps;
-use dao_voting::pre_propose::ProposalCreationPolicy;
+use dao_voting::{pre_propose::ProposalCreationPolicy, status::Status};
 
 use crate::{
     msg::QueryMsg,
-    query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
+    query::{
+        ProposalListResponse, ProposalResponse, ProposalStatusListResponse, VoteListResponse,
+        VoteResponse, VoterBallotListResponse,
+    },
     state::Config,
 };
 
@@ -72,6 +75,25 @@ pub(crate) fn query_list_votes(
         .unwrap()
 }
 
+pub(crate) fn query_list_votes_by_voter(
+    app: &App,
+    proposal_single: &Addr,
+    voter: &str,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> VoterBallotListResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &QueryMsg::ListVotesByVoter {
+                voter: voter.to_string(),
+                start_after,
+                limit,
+            },
+        )
+        .unwrap()
+}
+
 pub(crate) fn query_vote(
     app: &App,
     proposal_module: &Addr,
@@ -118,6 +140,44 @@ pub(crate) fn query_list_proposals_reverse(
         .unwrap()
 }
 
+pub(crate) fn query_list_proposals_by_status(
+    app: &App,
+    proposal_single: &Addr,
+    status: Status,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> ProposalStatusListResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &QueryMsg::ListProposalsByStatus {
+                status,
+                start_after,
+                limit,
+            },
+        )
+        .unwrap()
+}
+
+pub(crate) fn query_list_proposals_by_proposer(
+    app: &App,
+    proposal_single: &Addr,
+    proposer: &str,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> ProposalListResponse {
+    app.wrap()
+        .query_wasm_smart(
+            proposal_single,
+            &QueryMsg::ListProposalsByProposer {
+                proposer: proposer.to_string(),
+                start_after,
+                limit,
+            },
+        )
+        .unwrap()
+}
+
 pub(crate) fn query_pre_proposal_single_config(app: &App, pre_propose: &Addr) -> cppbps::Config {
     app.wrap()
         .query_wasm_smart(pre_propose, &cppbps::QueryMsg::Config {})
@@ -9,13 +9,18 @@ use cw20::Cw20Coin;
 use cw_denom::CheckedDenom;
 use cw_hooks::{HookError, HooksResponse};
 use cw_multi_test::{next_block, App, Executor};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 use dao_interface::{voting::InfoResponse, Admin, ModuleInstantiateInfo};
 use dao_testing::{ShouldExecute, TestSingleChoiceVote};
 use dao_voting::{
+    anti_sniping::AntiSnipingConfig,
     deposit::{CheckedDepositInfo, UncheckedDepositInfo},
+    error::VotingError,
+    power_transform::PowerTransform,
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
+    prerequisite::Prerequisite,
     proposal::{SingleChoiceProposeMsg as ProposeMsg, MAX_PROPOSAL_SIZE},
+    recurrence::Recurrence,
     reply::{
         failed_pre_propose_module_hook_id, mask_proposal_execution_proposal_id,
         mask_proposal_hook_index, mask_vote_hook_index,
@@ -23,28 +28,33 @@ use dao_voting::{
     status::Status,
     threshold::{PercentageThreshold, Threshold},
     voting::{Vote, Votes},
+    voting_period::{QuorumStep, VotingPeriodChoice},
 };
 use dao_voting_cw20_staked::msg::ActiveThreshold;
 
 use crate::{
-    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
+    commit_reveal::{commitment, CommitRevealConfig},
+    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION, MAX_PROPOSALS_SCANNED},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    proposal::SingleChoiceProposal,
-    query::{ProposalResponse, VoteInfo},
-    state::Config,
+    proposal::{MessageResult, SingleChoiceProposal},
+    query::{
+        InstalmentListResponse, ProposalResponse, ProposalStatusListResponse,
+        ProposalVersionListResponse, VoteInfo,
+    },
+    state::{Config, Instalment, ProposalVersion, Schedule},
     testing::{
         contracts::{
             cw20_base_contract, cw20_stake_contract, cw20_staked_balances_voting_contract,
-            cw_core_contract, pre_propose_single_contract, proposal_single_contract,
-            v1_proposal_single_contract,
+            cw_core_contract, delegation_registry_contract, pre_propose_single_contract,
+            proposal_single_contract, v1_proposal_single_contract,
         },
         execute::{
             add_proposal_hook, add_proposal_hook_should_fail, add_vote_hook,
-            add_vote_hook_should_fail, close_proposal, close_proposal_should_fail,
+            add_vote_hook_should_fail, close_proposal, close_proposal_should_fail, commit_vote,
             execute_proposal, execute_proposal_should_fail, instantiate_cw20_base_default,
             make_proposal, mint_cw20s, mint_natives, remove_proposal_hook,
             remove_proposal_hook_should_fail, remove_vote_hook, remove_vote_hook_should_fail,
-            update_rationale, vote_on_proposal, vote_on_proposal_should_fail,
+            reveal_vote, update_rationale, vote_on_proposal, vote_on_proposal_should_fail,
         },
         instantiate::{
             get_default_non_token_dao_proposal_module_instantiate,
@@ -55,12 +65,14 @@ use crate::{
         queries::{
             query_balance_cw20, query_balance_native, query_creation_policy, query_dao_token,
             query_deposit_config_and_pre_propose_module, query_list_proposals,
-            query_list_proposals_reverse, query_list_votes, query_pre_proposal_single_config,
-            query_pre_proposal_single_deposit_info, query_proposal, query_proposal_config,
-            query_proposal_hooks, query_single_proposal_module, query_vote_hooks,
-            query_voting_module,
+            query_list_proposals_by_proposer, query_list_proposals_by_status,
+            query_list_proposals_reverse, query_list_votes, query_list_votes_by_voter,
+            query_pre_proposal_single_config, query_pre_proposal_single_deposit_info,
+            query_proposal, query_proposal_config, query_proposal_hooks,
+            query_single_proposal_module, query_vote_hooks, query_voting_module,
         },
     },
+    threshold_rules::{MsgMatcher, ThresholdRule},
     ContractError,
 };
 
@@ -125,6 +137,18 @@ fn test_simple_propose_staked_balances() {
             threshold: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        anti_sniping: None,
+        extensions: 0,
+        msg_results: vec![],
+        power_transform: None,
+        review_expiration: None,
+        version: 0,
+        commit_expiration: None,
+        prerequisites: vec![],
+        execute_at: None,
+        recurrence: None,
+        voting_period: None,
+        turnout_recorded: false,
         total_power: Uint128::new(100_000_000),
         msgs: vec![],
         status: Status::Open,
@@ -174,6 +198,18 @@ fn test_simple_proposal_cw4_voting() {
             quorum: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        anti_sniping: None,
+        extensions: 0,
+        msg_results: vec![],
+        power_transform: None,
+        review_expiration: None,
+        version: 0,
+        commit_expiration: None,
+        prerequisites: vec![],
+        execute_at: None,
+        recurrence: None,
+        voting_period: None,
+        turnout_recorded: false,
         total_power: Uint128::new(1),
         msgs: vec![],
         status: Status::Open,
@@ -278,6 +314,18 @@ fn test_instantiate_with_non_voting_module_cw20_deposit() {
             quorum: PercentageThreshold::Majority {},
         },
         allow_revoting: false,
+        anti_sniping: None,
+        extensions: 0,
+        msg_results: vec![],
+        power_transform: None,
+        review_expiration: None,
+        version: 0,
+        commit_expiration: None,
+        prerequisites: vec![],
+        execute_at: None,
+        recurrence: None,
+        voting_period: None,
+        turnout_recorded: false,
         total_power: Uint128::new(1),
         msgs: vec![],
         status: Status::Open,
@@ -586,6 +634,14 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                commit_reveal: None,
+                delegation_registry: None,
+                partial_execution: false,
+                power_transform: None,
+                review_period: None,
+                voting_period_choice: None,
             })
             .unwrap(),
             funds: vec![],
@@ -614,6 +670,14 @@ fn test_update_config() {
             allow_revoting: false,
             dao: core_addr.clone(),
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     );
 
@@ -632,6 +696,14 @@ fn test_update_config() {
                 allow_revoting: false,
                 dao: core_addr.to_string(),
                 close_proposal_on_execution_failure: false,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                commit_reveal: None,
+                delegation_registry: None,
+                partial_execution: false,
+                power_transform: None,
+                review_period: None,
+                voting_period_choice: None,
             },
             &[],
         )
@@ -709,6 +781,18 @@ fn test_anyone_may_propose_and_proposal_listing() {
                     threshold: PercentageThreshold::Majority {},
                 },
                 allow_revoting: false,
+                anti_sniping: None,
+                extensions: 0,
+                msg_results: vec![],
+                power_transform: None,
+                review_expiration: None,
+                version: 0,
+                commit_expiration: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+                turnout_recorded: true,
                 total_power: Uint128::new(100_000_000),
                 msgs: vec![],
                 status: Status::Executed,
@@ -722,6 +806,297 @@ fn test_anyone_may_propose_and_proposal_listing() {
     )
 }
 
+#[test]
+fn test_list_proposals_by_status_and_proposer() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let ids = |res: ProposalStatusListResponse| -> Vec<u64> {
+        res.proposals.into_iter().map(|p| p.id).collect()
+    };
+
+    // Proposal 1 is executed.
+    let executed = make_proposal(&mut app, &proposal_module, "aaaaaa", vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        executed,
+        Vote::Yes,
+    );
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, executed);
+    // Proposal 2 is rejected by a vote.
+    let rejected = make_proposal(&mut app, &proposal_module, "bbbbbb", vec![]);
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, rejected, Vote::No);
+    // Proposal 3 is rejected when it expires without reaching quorum,
+    // but remains open in storage as nothing updates it.
+    let expired = make_proposal(&mut app, &proposal_module, "aaaaaa", vec![]);
+
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+
+    // Proposal 4 is open.
+    let open = make_proposal(&mut app, &proposal_module, "bbbbbb", vec![]);
+
+    let by_status = |app: &App, status, start_after, limit| {
+        ids(query_list_proposals_by_status(
+            app,
+            &proposal_module,
+            status,
+            start_after,
+            limit,
+        ))
+    };
+    assert_eq!(by_status(&app, Status::Open, None, None), vec![open]);
+    assert_eq!(
+        by_status(&app, Status::Rejected, None, None),
+        vec![rejected, expired]
+    );
+    assert_eq!(
+        by_status(&app, Status::Executed, None, None),
+        vec![executed]
+    );
+    assert!(by_status(&app, Status::Passed, None, None).is_empty());
+    assert!(by_status(&app, Status::Closed, None, None).is_empty());
+
+    // Pagination works across stored and computed statuses.
+    assert_eq!(
+        by_status(&app, Status::Rejected, None, Some(1)),
+        vec![rejected]
+    );
+    assert_eq!(
+        query_list_proposals_by_status(&app, &proposal_module, Status::Rejected, None, Some(1))
+            .next_start_after,
+        Some(rejected)
+    );
+    assert_eq!(
+        query_list_proposals_by_status(&app, &proposal_module, Status::Rejected, None, None)
+            .next_start_after,
+        None
+    );
+    assert_eq!(
+        by_status(&app, Status::Rejected, Some(rejected), None),
+        vec![expired]
+    );
+
+    // Closing the expired proposal moves it out of the rejected list.
+    close_proposal(&mut app, &proposal_module, CREATOR_ADDR, expired);
+    assert_eq!(
+        by_status(&app, Status::Rejected, None, None),
+        vec![rejected]
+    );
+    assert_eq!(by_status(&app, Status::Closed, None, None), vec![expired]);
+
+    let by_proposer = |app: &App, proposer, start_after, limit| {
+        query_list_proposals_by_proposer(app, &proposal_module, proposer, start_after, limit)
+            .proposals
+            .into_iter()
+            .map(|p| p.id)
+            .collect::<Vec<u64>>()
+    };
+    assert_eq!(
+        by_proposer(&app, "aaaaaa", None, None),
+        vec![executed, expired]
+    );
+    assert_eq!(
+        by_proposer(&app, "bbbbbb", None, None),
+        vec![rejected, open]
+    );
+    assert_eq!(
+        by_proposer(&app, "bbbbbb", Some(rejected), None),
+        vec![open]
+    );
+    assert_eq!(by_proposer(&app, "aaaaaa", None, Some(1)), vec![executed]);
+    assert!(by_proposer(&app, "cccccc", None, None).is_empty());
+}
+
+#[test]
+fn test_list_proposals_by_status_at_expiry() {
+    let mut app = App::default();
+    let instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "quorum".to_string(),
+                amount: Uint128::new(15),
+            },
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(85),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 30_000_000);
+
+    let ids = |res: ProposalStatusListResponse| -> Vec<u64> {
+        res.proposals.into_iter().map(|p| p.id).collect()
+    };
+    let by_status = |app: &App, status, start_after| {
+        ids(query_list_proposals_by_status(
+            app,
+            &proposal_module,
+            status,
+            start_after,
+            None,
+        ))
+    };
+
+    // Proposal 1 reaches quorum but not a majority of all voting
+    // power, so it passes when it expires.
+    let passing = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "quorum", passing, Vote::Yes);
+    // Proposal 2 receives no votes, so it is rejected when it
+    // expires.
+    let failing = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    app.update_block(|b| b.time = b.time.plus_seconds(1));
+    // Proposal 3 expires a second after the others.
+    let later = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    assert_eq!(
+        by_status(&app, Status::Open, None),
+        vec![passing, failing, later]
+    );
+    assert_eq!(
+        by_status(&app, Status::Open, Some(passing)),
+        vec![failing, later]
+    );
+    assert!(by_status(&app, Status::Passed, None).is_empty());
+
+    // Expiry moves the first two proposals out of the open list,
+    // though neither has been updated in storage.
+    app.update_block(|b| b.time = b.time.plus_seconds(604799));
+    assert_eq!(by_status(&app, Status::Open, None), vec![later]);
+    assert_eq!(by_status(&app, Status::Passed, None), vec![passing]);
+    assert_eq!(by_status(&app, Status::Rejected, None), vec![failing]);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, passing)
+            .proposal
+            .status,
+        Status::Passed
+    );
+
+    app.update_block(|b| b.time = b.time.plus_seconds(1));
+    assert!(by_status(&app, Status::Open, None).is_empty());
+    assert_eq!(
+        by_status(&app, Status::Rejected, None),
+        vec![failing, later]
+    );
+
+    // Executing the passed proposal moves it out of the passed list.
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, passing);
+    assert!(by_status(&app, Status::Passed, None).is_empty());
+    assert_eq!(by_status(&app, Status::Executed, None), vec![passing]);
+}
+
+#[test]
+fn test_list_proposals_by_status_scan_limit() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    // More proposals than a single query examines are rejected when
+    // they expire, but remain open in storage.
+    for _ in 0..MAX_PROPOSALS_SCANNED + 1 {
+        make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    }
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let open = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // The first page examines only the first expired proposals, so it
+    // is empty but says where to continue from.
+    let page = query_list_proposals_by_status(&app, &proposal_module, Status::Open, None, None);
+    assert!(page.proposals.is_empty());
+    assert_eq!(page.next_start_after, Some(MAX_PROPOSALS_SCANNED as u64));
+
+    let page = query_list_proposals_by_status(
+        &app,
+        &proposal_module,
+        Status::Open,
+        page.next_start_after,
+        None,
+    );
+    assert_eq!(
+        page.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+        vec![open]
+    );
+    assert_eq!(page.next_start_after, None);
+
+    // Listing rejected proposals stops at the scan limit too.
+    let limit = Some(MAX_PROPOSALS_SCANNED as u64);
+    let page =
+        query_list_proposals_by_status(&app, &proposal_module, Status::Rejected, None, limit);
+    assert_eq!(page.proposals.len(), MAX_PROPOSALS_SCANNED);
+    assert_eq!(page.next_start_after, Some(MAX_PROPOSALS_SCANNED as u64));
+    let page = query_list_proposals_by_status(
+        &app,
+        &proposal_module,
+        Status::Rejected,
+        page.next_start_after,
+        limit,
+    );
+    assert_eq!(
+        page.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+        vec![MAX_PROPOSALS_SCANNED as u64 + 1]
+    );
+    assert_eq!(page.next_start_after, None);
+}
+
+#[test]
+fn test_list_votes_by_voter() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    for _ in 0..3 {
+        make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    }
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, 1, Vote::Yes);
+    vote_on_proposal_with_rationale(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        3,
+        Vote::No,
+        Some("not now".to_string()),
+    );
+
+    let ballots = query_list_votes_by_voter(&app, &proposal_module, CREATOR_ADDR, None, None);
+    assert_eq!(
+        ballots
+            .ballots
+            .iter()
+            .map(|b| (b.proposal_id, b.ballot.vote, b.ballot.rationale.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (1, Some(Vote::Yes), None),
+            (3, Some(Vote::No), Some("not now".to_string()))
+        ]
+    );
+    assert_eq!(ballots.ballots[0].ballot.power, Uint128::new(100_000_000));
+
+    let ballots = query_list_votes_by_voter(&app, &proposal_module, CREATOR_ADDR, Some(1), None);
+    assert_eq!(ballots.ballots.len(), 1);
+    assert_eq!(ballots.ballots[0].proposal_id, 3);
+
+    let ballots = query_list_votes_by_voter(&app, &proposal_module, CREATOR_ADDR, None, Some(1));
+    assert_eq!(ballots.ballots.len(), 1);
+    assert_eq!(ballots.ballots[0].proposal_id, 1);
+
+    let ballots = query_list_votes_by_voter(&app, &proposal_module, "nonvoter", None, None);
+    assert!(ballots.ballots.is_empty());
+}
+
 #[test]
 fn test_proposal_hook_registration() {
     let CommonTest {
@@ -877,6 +1252,10 @@ fn test_active_threshold_absolute() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             }),
             &[],
         )
@@ -898,59 +1277,2103 @@ fn test_active_threshold_absolute() {
     // active threshold.
     make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
 
-    // Unstake some tokens to make it inactive again.
-    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
-        amount: Uint128::new(50),
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
-        .unwrap();
-    app.update_block(next_block);
+    // Unstake some tokens to make it inactive again.
+    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
+        amount: Uint128::new(50),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InactiveDao {}));
+}
+
+#[test]
+fn test_active_threshold_percent() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        instantiate,
+        None,
+        Some(ActiveThreshold::Percentage {
+            percent: Decimal::percent(20),
+        }),
+    );
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let voting_module = query_voting_module(&app, &core_addr);
+
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &dao_voting_cw20_staked::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InactiveDao {}));
+
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(20_000_000),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), gov_token, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Proposal creation now works as tokens have been staked to reach
+    // active threshold.
+    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // Unstake some tokens to make it inactive again.
+    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
+        amount: Uint128::new(1), // Only one is needed as we're right
+                                 // on the edge. :)
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+            }),
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InactiveDao {}));
+}
+
+#[test]
+#[should_panic(
+    expected = "min_voting_period and max_voting_period must have the same units (height or time)"
+)]
+fn test_min_duration_unit_missmatch() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.min_voting_period = Some(Duration::Height(10));
+    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+}
+
+#[test]
+#[should_panic(expected = "Min voting period must be less than or equal to max voting period")]
+fn test_min_duration_larger_than_proposal_duration() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.min_voting_period = Some(Duration::Time(604801));
+    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+}
+
+#[test]
+fn test_min_voting_period_no_early_pass() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.min_voting_period = Some(Duration::Height(10));
+    instantiate.max_voting_period = Duration::Height(100);
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Open);
+
+    app.update_block(|mut block| block.height += 10);
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Passed);
+}
+
+// Setting the min duration the same as the proposal duration just
+// means that proposals cant close early.
+#[test]
+fn test_min_duration_same_as_proposal_duration() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.min_voting_period = Some(Duration::Height(100));
+    instantiate.max_voting_period = Duration::Height(100);
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "whale".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, "ekez", 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+
+    // Whale votes yes. Normally the proposal would just pass and ekez
+    // would be out of luck.
+    vote_on_proposal(&mut app, &proposal_module, "whale", proposal_id, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "ekez", proposal_id, Vote::No);
+
+    app.update_block(|mut b| b.height += 100);
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_revoting_playthrough() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+
+    // Vote and change our minds a couple times.
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Open);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+    );
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Open);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Open);
+
+    // Can't cast the same vote more than once.
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+
+    // Expire the proposal allowing the votes to be tallied.
+    app.update_block(|mut b| b.time = b.time.plus_seconds(604800));
+    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal_response.proposal.status, Status::Passed);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
+
+    // Can't vote once the proposal is passed.
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    assert!(matches!(err, ContractError::Expired { .. }));
+}
+
+/// Tests that a vote in the final seconds of a proposal's voting period
+/// that changes its outcome extends the proposal's expiration, up to the
+/// maximum number of extensions.
+#[test]
+fn test_anti_sniping_extension() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Time(100),
+        extension: Duration::Time(100),
+        max_extensions: 1,
+    });
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "carl".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let expiration = app.block_info().time.plus_seconds(604800);
+
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+
+    // Voting in the window without changing the outcome does not
+    // extend the proposal.
+    app.update_block(|b| b.time = b.time.plus_seconds(604750));
+    vote_on_proposal(&mut app, &proposal_module, "bob", proposal_id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.extensions, 0);
+    assert_eq!(proposal.expiration, Expiration::AtTime(expiration));
+
+    // Changing the outcome in the window extends the proposal.
+    vote_on_proposal(&mut app, &proposal_module, "carl", proposal_id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.extensions, 1);
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(expiration.plus_seconds(100))
+    );
+
+    // Once the maximum number of extensions has been reached the
+    // proposal is no longer extended.
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    vote_on_proposal(&mut app, &proposal_module, "carl", proposal_id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(proposal.extensions, 1);
+
+    app.update_block(|b| b.time = b.time.plus_seconds(50));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+/// Tests that a revote in the window which flips the outcome extends
+/// the proposal, as the outcome before the revote includes the
+/// revoter's old vote.
+#[test]
+fn test_anti_sniping_revote() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Time(100),
+        extension: Duration::Time(100),
+        max_extensions: 1,
+    });
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(35),
+            },
+        ]),
+    );
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let expiration = app.block_info().time.plus_seconds(604800);
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    vote_on_proposal(&mut app, &proposal_module, "bob", proposal_id, Vote::No);
+
+    app.update_block(|b| b.time = b.time.plus_seconds(604799));
+    vote_on_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.extensions, 1);
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(expiration.plus_seconds(100))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Anti-sniping window and extension must have the same units")]
+fn test_anti_sniping_units_conflict() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Height(10),
+        extension: Duration::Height(10),
+        max_extensions: 1,
+    });
+    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+}
+
+/// Tests that votes on commit-reveal proposals are hidden until the
+/// reveal phase and that only revealed votes are counted.
+#[test]
+fn test_commit_reveal_voting() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.commit_reveal = Some(CommitRevealConfig {
+        reveal_period: Duration::Time(100),
+    });
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::new(35),
+            },
+            Cw20Coin {
+                address: "carl".to_string(),
+                amount: Uint128::new(25),
+            },
+        ]),
+    );
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let commit_expiration = app.block_info().time.plus_seconds(604800);
+
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(
+        proposal.commit_expiration,
+        Some(Expiration::AtTime(commit_expiration))
+    );
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(commit_expiration.plus_seconds(100))
+    );
+
+    let err = vote_on_proposal_should_fail(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+    );
+    assert!(matches!(err, ContractError::CommitRevealRequired { .. }));
+
+    commit_vote(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap();
+    commit_vote(
+        &mut app,
+        &proposal_module,
+        "bob",
+        proposal_id,
+        Vote::No,
+        "pepper",
+    )
+    .unwrap();
+    let err = commit_vote(
+        &mut app,
+        &proposal_module,
+        "bob",
+        proposal_id,
+        Vote::Yes,
+        "pepper",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+
+    // Committed votes are listed without their position and are not
+    // counted.
+    let votes = query_list_votes(&app, &proposal_module, proposal_id, None, None).votes;
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes[0].voter, Addr::unchecked("bob"));
+    assert_eq!(votes[0].vote, None);
+    assert_eq!(
+        votes[0].commitment,
+        Some(commitment(
+            proposal_id,
+            &Addr::unchecked("bob"),
+            Vote::No,
+            "pepper"
+        ))
+    );
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.votes, Votes::zero());
+
+    let err = reveal_vote(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotRevealPhase { .. }));
+
+    // Reveal phase.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let err = commit_vote(
+        &mut app,
+        &proposal_module,
+        "carl",
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::CommitPhaseOver { .. }));
+    let err = reveal_vote(
+        &mut app,
+        &proposal_module,
+        "carl",
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoSuchVote { .. }));
+
+    let err = reveal_vote(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::No,
+        "salt",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReveal {}));
+
+    reveal_vote(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap();
+    let err = reveal_vote(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        proposal_id,
+        Vote::Yes,
+        "salt",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::AlreadyRevealed {}));
+
+    let vote = query_vote(&app, &proposal_module, CREATOR_ADDR, proposal_id)
+        .vote
+        .unwrap();
+    assert_eq!(vote.vote, Some(Vote::Yes));
+    assert_eq!(vote.power, Uint128::new(40));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.votes.yes, Uint128::new(40));
+    assert_eq!(proposal.status, Status::Open);
+
+    // bob never reveals, so only the creator's vote is counted.
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    let err = reveal_vote(
+        &mut app,
+        &proposal_module,
+        "bob",
+        proposal_id,
+        Vote::No,
+        "pepper",
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotRevealPhase { .. }));
+
+    let proposal = query_proposal(&app, &proposal_module, proposal_id).proposal;
+    assert_eq!(proposal.votes.no, Uint128::zero());
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+#[should_panic(expected = "anti-sniping can not be used with commit-reveal voting")]
+fn test_commit_reveal_anti_sniping_conflict() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.anti_sniping = Some(AntiSnipingConfig {
+        window: Duration::Time(10),
+        extension: Duration::Time(10),
+        max_extensions: 1,
+    });
+    instantiate.commit_reveal = Some(CommitRevealConfig {
+        reveal_period: Duration::Time(100),
+    });
+    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+}
+
+/// Tests that proposals containing messages matched by a threshold
+/// rule are created with that rule's threshold and voting period.
+#[test]
+fn test_delegated_voting() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.allow_revoting = true;
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(40),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let registry_id = app.store_code(delegation_registry_contract());
+    let registry = app
+        .instantiate_contract(
+            registry_id,
+            core_addr.clone(),
+            &dao_delegation_registry::msg::InstantiateMsg {},
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap();
+    let config = query_proposal_config(&app, &proposal_module);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.into_string(),
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            anti_sniping: config.anti_sniping,
+            threshold_rules: config.threshold_rules,
+            commit_reveal: config.commit_reveal,
+            delegation_registry: Some(registry.to_string()),
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let delegate = |app: &mut App, delegator: &str| {
+        app.execute_contract(
+            Addr::unchecked(delegator),
+            registry.clone(),
+            &dao_delegation_registry::msg::ExecuteMsg::Delegate {
+                delegate: "zeke".to_string(),
+                proposal_module: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let votes = |app: &App, id: u64| query_proposal(app, &proposal_module, id).proposal.votes;
+
+    delegate(&mut app, "ekez");
+    delegate(&mut app, "blue");
+    app.update_block(next_block);
+    let id = make_proposal(&mut app, &proposal_module, "keze", vec![]);
+    // Delegations made after a proposal is created do not apply to it.
+    delegate(&mut app, "keze");
+
+    // The delegate votes for both of their delegators.
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::Yes);
+    assert_eq!(votes(&app, id).yes, Uint128::new(60));
+    let ballot = query_vote(&app, &proposal_module, "zeke", id).vote.unwrap();
+    assert_eq!(ballot.power, Uint128::new(30));
+    assert_eq!(ballot.delegated_power, Uint128::new(30));
+
+    // A delegator voting overrides their delegate.
+    vote_on_proposal(&mut app, &proposal_module, "ekez", id, Vote::No);
+    assert_eq!(votes(&app, id).yes, Uint128::new(50));
+    assert_eq!(votes(&app, id).no, Uint128::new(10));
+
+    // Delegated power moves with the delegate's vote.
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::No);
+    assert_eq!(votes(&app, id).yes, Uint128::zero());
+    assert_eq!(votes(&app, id).no, Uint128::new(60));
+    vote_on_proposal(&mut app, &proposal_module, "blue", id, Vote::Abstain);
+    assert_eq!(votes(&app, id).no, Uint128::new(40));
+    assert_eq!(votes(&app, id).abstain, Uint128::new(20));
+    let ballot = query_vote(&app, &proposal_module, "zeke", id).vote.unwrap();
+    assert_eq!(ballot.delegated_power, Uint128::zero());
+
+    // Delegators who vote before their delegate are not counted by
+    // them.
+    app.update_block(next_block);
+    let id = make_proposal(&mut app, &proposal_module, "keze", vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "blue", id, Vote::Abstain);
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::Yes);
+    assert_eq!(
+        votes(&app, id),
+        Votes {
+            yes: Uint128::new(30 + 10 + 40),
+            no: Uint128::zero(),
+            abstain: Uint128::new(20),
+        }
+    );
+
+    // Delegators who stop delegating after a proposal is created are
+    // still counted by the delegate they had when it was created.
+    app.update_block(next_block);
+    let id = make_proposal(&mut app, &proposal_module, "keze", vec![]);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        registry.clone(),
+        &dao_delegation_registry::msg::ExecuteMsg::Undelegate {
+            proposal_module: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::Yes);
+    let ballot = query_vote(&app, &proposal_module, "zeke", id).vote.unwrap();
+    assert_eq!(ballot.delegated_power, Uint128::new(10 + 20 + 40));
+    assert_eq!(votes(&app, id).yes, Uint128::new(100));
+}
+
+#[test]
+fn test_prerequisites() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                prerequisites: vec![Prerequisite {
+                    proposal_module: proposal_module.to_string(),
+                    proposal_id,
+                }],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+            }),
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert!(matches!(
+        err,
+        ContractError::NoSuchPrerequisite { proposal_id: 1, .. }
+    ));
+
+    let first = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    propose(&mut app, first).unwrap();
+    let second = first + 1;
+    vote_on_proposal(&mut app, &proposal_module, "ekez", first, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "ekez", second, Vote::Yes);
+
+    // The second proposal may not be executed before the first.
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, "ekez", second);
+    assert!(matches!(
+        err,
+        ContractError::PrerequisiteNotExecuted { proposal_id, .. } if proposal_id == first
+    ));
+    // Nor may it be closed as its prerequisite may still execute.
+    let err = close_proposal_should_fail(&mut app, &proposal_module, "ekez", second);
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    execute_proposal(&mut app, &proposal_module, "ekez", first);
+    execute_proposal(&mut app, &proposal_module, "ekez", second);
+    let proposal = query_proposal(&app, &proposal_module, second);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+
+    // Proposals whose prerequisite is rejected may be closed while
+    // open.
+    let rejected = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    propose(&mut app, rejected).unwrap();
+    let dependent = rejected + 1;
+    vote_on_proposal(&mut app, &proposal_module, "ekez", rejected, Vote::No);
+    close_proposal(&mut app, &proposal_module, "ekez", dependent);
+    let proposal = query_proposal(&app, &proposal_module, dependent);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+}
+
+#[test]
+fn test_execute_at() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, execute_at: Expiration| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: Some(execute_at),
+                recurrence: None,
+                voting_period: None,
+            }),
+            &[],
+        )
+    };
+
+    let err: ContractError = propose(&mut app, Expiration::Never {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExecuteAt {}));
+
+    let execute_at = Expiration::AtHeight(app.block_info().height + 10);
+    propose(&mut app, execute_at).unwrap();
+    vote_on_proposal(&mut app, &proposal_module, "ekez", 1, Vote::Yes);
+
+    let err = execute_proposal_should_fail(&mut app, &proposal_module, "ekez", 1);
+    assert!(matches!(
+        err,
+        ContractError::ExecuteAtNotReached { id: 1, execute_at: e } if e == execute_at
+    ));
+
+    // Once the execution time is reached the proposal may be
+    // executed by anyone, even though only members may execute
+    // unscheduled proposals.
+    app.update_block(|b| b.height += 10);
+    execute_proposal(&mut app, &proposal_module, "crank", 1);
+    let proposal = query_proposal(&app, &proposal_module, 1);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_recurrence() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let propose = |app: &mut App, recurrence: Recurrence| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: "blue".to_string(),
+                    amount: coins(10, "ujuno"),
+                }
+                .into()],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: Some(recurrence),
+                voting_period: None,
+            }),
+            &[],
+        )
+    };
+    let crank = |app: &mut App, id: u64| {
+        app.execute_contract(
+            Addr::unchecked("crank"),
+            proposal_module.clone(),
+            &ExecuteMsg::ExecuteInstalment { proposal_id: id },
+            &[],
+        )
+    };
+    let balance = |app: &App| {
+        app.wrap()
+            .query_balance("blue", "ujuno")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    let err: ContractError = propose(
+        &mut app,
+        Recurrence {
+            interval: Duration::Height(10),
+            count: 1,
+        },
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidRecurrence {})
+    ));
+
+    propose(
+        &mut app,
+        Recurrence {
+            interval: Duration::Height(10),
+            count: 3,
+        },
+    )
+    .unwrap();
+    vote_on_proposal(&mut app, &proposal_module, "ekez", 1, Vote::Yes);
+
+    // Proposals are not scheduled until they are executed.
+    let err: ContractError = crank(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::NoSchedule { id: 1 }));
+
+    // The first instalment is executed along with the proposal.
+    execute_proposal(&mut app, &proposal_module, "ekez", 1);
+    assert_eq!(balance(&app), 10);
+
+    let next = Expiration::AtHeight(app.block_info().height + 10);
+    let err: ContractError = crank(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert!(matches!(
+        err,
+        ContractError::InstalmentNotDue { id: 1, next: n } if n == next
+    ));
+
+    // Late cranks do not delay the instalments after them.
+    app.update_block(|b| b.height += 15);
+    crank(&mut app, 1).unwrap();
+    assert_eq!(balance(&app), 20);
+    app.update_block(|b| b.height += 5);
+    crank(&mut app, 1).unwrap();
+    assert_eq!(balance(&app), 30);
+
+    app.update_block(|b| b.height += 10);
+    let err: ContractError = crank(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::ScheduleComplete { id: 1 }));
+
+    let instalments: InstalmentListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListInstalments {
+                proposal_id: 1,
+                start_after: Some(0),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        instalments
+            .instalments
+            .iter()
+            .map(|i| i.index)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    // The DAO may cancel a schedule, after which no more instalments
+    // are executed.
+    propose(
+        &mut app,
+        Recurrence {
+            interval: Duration::Height(10),
+            count: 12,
+        },
+    )
+    .unwrap();
+    vote_on_proposal(&mut app, &proposal_module, "ekez", 2, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, "ekez", 2);
+    assert_eq!(balance(&app), 40);
+
+    let cancel = ExecuteMsg::CancelSchedule { proposal_id: 2 };
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &cancel,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    app.execute_contract(core_addr, proposal_module.clone(), &cancel, &[])
+        .unwrap();
+
+    app.update_block(|b| b.height += 10);
+    let err: ContractError = crank(&mut app, 2).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::ScheduleCancelled { id: 2 }));
+    let schedule: Option<Schedule> = app
+        .wrap()
+        .query_wasm_smart(&proposal_module, &QueryMsg::Schedule { proposal_id: 2 })
+        .unwrap();
+    assert_eq!(
+        schedule,
+        Some(Schedule {
+            interval: Duration::Height(10),
+            count: 12,
+            executed: 1,
+            next: Expiration::AtHeight(app.block_info().height),
+            cancelled: true,
+        })
+    );
+}
+
+#[test]
+fn test_schedule_cancelled_by_proposal() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(100, "ujuno"));
+
+    let propose = |app: &mut App, msg: CosmosMsg, recurrence: Option<Recurrence>| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![msg],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence,
+                voting_period: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    };
+    propose(
+        &mut app,
+        BankMsg::Send {
+            to_address: "blue".to_string(),
+            amount: coins(10, "ujuno"),
+        }
+        .into(),
+        Some(Recurrence {
+            interval: Duration::Height(10),
+            count: 12,
+        }),
+    );
+    vote_on_proposal(&mut app, &proposal_module, "ekez", 1, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, "ekez", 1);
+
+    propose(
+        &mut app,
+        WasmMsg::Execute {
+            contract_addr: proposal_module.to_string(),
+            msg: to_binary(&ExecuteMsg::CancelSchedule { proposal_id: 1 }).unwrap(),
+            funds: vec![],
+        }
+        .into(),
+        None,
+    );
+    vote_on_proposal(&mut app, &proposal_module, "ekez", 2, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, "ekez", 2);
+
+    app.update_block(|b| b.height += 10);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("crank"),
+            proposal_module.clone(),
+            &ExecuteMsg::ExecuteInstalment { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ScheduleCancelled { id: 1 }));
+    assert_eq!(
+        app.wrap().query_balance("blue", "ujuno").unwrap().amount,
+        Uint128::new(10)
+    );
+}
+
+#[test]
+fn test_instalment_failure() {
+    // Each module pays 10 ujuno and 1 uatom per instalment and holds
+    // enough ujuno for three instalments but uatom for only one.
+    let setup = |partial_execution: bool, close_proposal_on_execution_failure: bool| {
+        let mut app = App::default();
+        let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+        instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+        instantiate.partial_execution = partial_execution;
+        instantiate.close_proposal_on_execution_failure = close_proposal_on_execution_failure;
+        let core_addr = instantiate_with_cw4_groups_governance(
+            &mut app,
+            instantiate,
+            Some(vec![Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            }]),
+        );
+        let proposal_module = query_single_proposal_module(&app, &core_addr);
+        mint_natives(&mut app, core_addr.as_str(), coins(30, "ujuno"));
+        mint_natives(&mut app, core_addr.as_str(), coins(1, "uatom"));
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![
+                    BankMsg::Send {
+                        to_address: "blue".to_string(),
+                        amount: coins(10, "ujuno"),
+                    }
+                    .into(),
+                    BankMsg::Send {
+                        to_address: "blue".to_string(),
+                        amount: coins(1, "uatom"),
+                    }
+                    .into(),
+                ],
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: Some(Recurrence {
+                    interval: Duration::Height(10),
+                    count: 3,
+                }),
+                voting_period: None,
+            }),
+            &[],
+        )
+        .unwrap();
+        vote_on_proposal(&mut app, &proposal_module, "ekez", 1, Vote::Yes);
+        execute_proposal(&mut app, &proposal_module, "ekez", 1);
+        (app, proposal_module)
+    };
+    let crank = |app: &mut App, proposal_module: &Addr| {
+        app.update_block(|b| b.height += 10);
+        app.execute_contract(
+            Addr::unchecked("crank"),
+            proposal_module.clone(),
+            &ExecuteMsg::ExecuteInstalment { proposal_id: 1 },
+            &[],
+        )
+    };
+    let instalment = |app: &App, proposal_module: &Addr, index: u64| {
+        let instalments: InstalmentListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                proposal_module,
+                &QueryMsg::ListInstalments {
+                    proposal_id: 1,
+                    start_after: index.checked_sub(1),
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+        instalments.instalments.into_iter().next().unwrap()
+    };
+    let schedule = |app: &App, proposal_module: &Addr| {
+        let schedule: Option<Schedule> = app
+            .wrap()
+            .query_wasm_smart(proposal_module, &QueryMsg::Schedule { proposal_id: 1 })
+            .unwrap();
+        schedule.unwrap()
+    };
+    let balance = |app: &App| {
+        app.wrap()
+            .query_balance("blue", "ujuno")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    // A failing instalment is recorded and, as failed proposals are
+    // closed, cancels the schedule.
+    let (mut app, proposal_module) = setup(false, true);
+    crank(&mut app, &proposal_module).unwrap();
+    assert_eq!(balance(&app), 10);
+    let Instalment {
+        index,
+        msg_results,
+        error,
+        ..
+    } = instalment(&app, &proposal_module, 1);
+    assert_eq!(index, 1);
+    assert!(msg_results.is_empty());
+    assert!(error.is_some());
+    let schedule_after = schedule(&app, &proposal_module);
+    assert_eq!(schedule_after.executed, 2);
+    assert!(schedule_after.cancelled);
+    let err: ContractError = crank(&mut app, &proposal_module)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ScheduleCancelled { id: 1 }));
+
+    // Otherwise the schedule continues.
+    let (mut app, proposal_module) = setup(false, false);
+    crank(&mut app, &proposal_module).unwrap();
+    assert!(instalment(&app, &proposal_module, 1).error.is_some());
+    crank(&mut app, &proposal_module).unwrap();
+    assert!(instalment(&app, &proposal_module, 2).error.is_some());
+    assert_eq!(balance(&app), 10);
+    let schedule_after = schedule(&app, &proposal_module);
+    assert_eq!(schedule_after.executed, 3);
+    assert!(!schedule_after.cancelled);
+
+    // With partial execution each message's failure is recorded and
+    // the messages that succeed are executed.
+    let (mut app, proposal_module) = setup(true, true);
+    crank(&mut app, &proposal_module).unwrap();
+    crank(&mut app, &proposal_module).unwrap();
+    assert_eq!(balance(&app), 30);
+    for index in [1, 2] {
+        let Instalment {
+            msg_results, error, ..
+        } = instalment(&app, &proposal_module, index);
+        assert_eq!(msg_results[0], MessageResult::Succeeded);
+        assert!(matches!(msg_results[1], MessageResult::Failed { .. }));
+        assert_eq!(error, None);
+    }
+    assert!(!schedule(&app, &proposal_module).cancelled);
+}
+
+#[test]
+fn test_partial_execution() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.partial_execution = true;
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    mint_natives(&mut app, core_addr.as_str(), coins(20, "ujuno"));
+
+    let send = |to: &str, amount| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+    // The DAO can not afford the second message.
+    let id = make_proposal(
+        &mut app,
+        &proposal_module,
+        "ekez",
+        vec![send("ekez", 10), send("blue", 100), send("zeke", 5)],
+    );
+    vote_on_proposal(&mut app, &proposal_module, "ekez", id, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, "ekez", id);
+
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(proposal.proposal.msg_results.len(), 3);
+    assert_eq!(proposal.proposal.msg_results[0], MessageResult::Succeeded);
+    assert!(matches!(
+        proposal.proposal.msg_results[1],
+        MessageResult::Failed { .. }
+    ));
+    assert_eq!(proposal.proposal.msg_results[2], MessageResult::Succeeded);
+
+    assert_eq!(
+        query_balance_native(&app, "ekez", "ujuno"),
+        Uint128::new(10)
+    );
+    assert_eq!(query_balance_native(&app, "blue", "ujuno"), Uint128::zero());
+    assert_eq!(query_balance_native(&app, "zeke", "ujuno"), Uint128::new(5));
+}
+
+#[test]
+fn test_power_transform() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.power_transform = Some(PowerTransform::Cap {
+        percent: Decimal::percent(20),
+    });
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "whale".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(5),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(5),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let id = make_proposal(&mut app, &proposal_module, "whale", vec![]);
+    // Without the cap the whale's vote would meet quorum and pass
+    // the proposal.
+    vote_on_proposal(&mut app, &proposal_module, "whale", id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(20));
+    assert_eq!(proposal.proposal.status, Status::Open);
+    let ballot = query_vote(&app, &proposal_module, "whale", id);
+    assert_eq!(ballot.vote.unwrap().power, Uint128::new(20));
+
+    // Voters below the cap are unaffected.
+    vote_on_proposal(&mut app, &proposal_module, "blue", id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.votes.no, Uint128::new(5));
+
+    // Changing the transform does not change open proposals.
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: proposal.proposal.threshold,
+            max_voting_period: Duration::Time(604800),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: Some(PowerTransform::SquareRoot {}),
+            review_period: None,
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::No);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.votes.no, Uint128::new(10));
+
+    let id = make_proposal(&mut app, &proposal_module, "whale", vec![]);
+    vote_on_proposal(&mut app, &proposal_module, "whale", id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.total_power, Uint128::new(10));
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(9));
+    // The square roots of the other voters' power sum to more than
+    // the one remaining, so the proposal can not pass early.
+    assert_eq!(proposal.proposal.status, Status::Open);
+    vote_on_proposal(&mut app, &proposal_module, "blue", id, Vote::No);
+    vote_on_proposal(&mut app, &proposal_module, "zeke", id, Vote::No);
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.votes.total(), Uint128::new(13));
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // Invalid caps are rejected.
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            proposal_module.clone(),
+            &ExecuteMsg::UpdateConfig {
+                threshold: proposal.proposal.threshold,
+                max_voting_period: Duration::Time(604800),
+                min_voting_period: None,
+                only_members_execute: true,
+                allow_revoting: false,
+                dao: core_addr.to_string(),
+                close_proposal_on_execution_failure: true,
+                anti_sniping: None,
+                threshold_rules: vec![],
+                commit_reveal: None,
+                delegation_registry: None,
+                partial_execution: false,
+                power_transform: Some(PowerTransform::Cap {
+                    percent: Decimal::zero(),
+                }),
+                review_period: None,
+                voting_period_choice: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::InvalidPowerTransformCap {})
+    ));
+}
+
+#[test]
+fn test_review_period() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.review_period = Some(Duration::Time(100));
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    let created = app.block_info().time;
+    assert_eq!(
+        proposal.review_expiration,
+        Some(Expiration::AtTime(created.plus_seconds(100)))
+    );
+    // The voting period begins once the review period ends.
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(created.plus_seconds(100 + 604800))
+    );
+
+    let err = vote_on_proposal_should_fail(&mut app, &proposal_module, "ekez", id, Vote::Yes);
+    assert!(matches!(err, ContractError::InReviewPeriod { .. }));
+
+    let amend = |app: &mut App, sender: &str, title: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_module.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: title.to_string(),
+                description: "amended".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(10, "ujuno"),
+                }
+                .into()],
+            },
+            &[],
+        )
+    };
+    let err: ContractError = amend(&mut app, "blue", "hijacked")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    amend(&mut app, "ekez", "first").unwrap();
+    amend(&mut app, "ekez", "second").unwrap();
+
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.version, 2);
+    assert_eq!(proposal.title, "second");
+    assert_eq!(proposal.msgs.len(), 1);
+    let versions: ProposalVersionListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &proposal_module,
+            &QueryMsg::ListProposalVersions {
+                proposal_id: id,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        versions.versions,
+        vec![
+            ProposalVersion {
+                version: 0,
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+            },
+            ProposalVersion {
+                version: 1,
+                title: "first".to_string(),
+                description: "amended".to_string(),
+                msgs: proposal.msgs.clone(),
+            },
+        ]
+    );
+
+    let list_versions = |app: &App, start_after: Option<u64>, limit: Option<u64>| {
+        let versions: ProposalVersionListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proposal_module,
+                &QueryMsg::ListProposalVersions {
+                    proposal_id: id,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        versions
+            .versions
+            .into_iter()
+            .map(|v| v.version)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(list_versions(&app, None, Some(1)), vec![0]);
+    assert_eq!(list_versions(&app, Some(0), Some(1)), vec![1]);
+    assert_eq!(list_versions(&app, Some(1), None), Vec::<u64>::new());
+
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    let err: ContractError = amend(&mut app, "ekez", "third")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotInReviewPeriod { .. }));
+    vote_on_proposal(&mut app, &proposal_module, "ekez", id, Vote::Yes);
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_amend_pre_proposed() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.review_period = Some(Duration::Time(100));
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let (_, pre_propose) = query_deposit_config_and_pre_propose_module(&app, &proposal_module);
+
+    // The pre-propose module takes a deposit for the proposal.
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
+    let id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.proposer, Addr::unchecked(CREATOR_ADDR));
+    assert!(proposal.review_expiration.is_some());
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, CREATOR_ADDR),
+        Uint128::zero()
+    );
+
+    let amend = |app: &mut App, sender: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            proposal_module.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: "amended".to_string(),
+                description: "amended".to_string(),
+                msgs: vec![],
+            },
+            &[],
+        )
+    };
+
+    // The proposer recorded on the proposal may amend it, but the
+    // pre-propose module that created it may not.
+    let err: ContractError = amend(&mut app, pre_propose.as_str())
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    amend(&mut app, CREATOR_ADDR).unwrap();
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.version, 1);
+    assert_eq!(proposal.title, "amended");
+
+    // The deposit is refunded when the amended proposal passes.
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    vote_on_proposal(&mut app, &proposal_module, CREATOR_ADDR, id, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, id);
+    assert_eq!(
+        query_balance_cw20(&app, &gov_token, CREATOR_ADDR),
+        Uint128::new(10_000_000)
+    );
+}
+
+#[test]
+fn test_amend_uses_creation_config() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.review_period = Some(Duration::Time(100));
+    instantiate.threshold = Threshold::AdaptiveQuorum {
+        threshold: PercentageThreshold::Majority {},
+        min_quorum: Decimal::percent(10),
+        max_quorum: Decimal::percent(100),
+    };
+    let strict_threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Percent(Decimal::percent(75)),
+    };
+    instantiate.threshold_rules = vec![ThresholdRule {
+        matcher: MsgMatcher::BankSend {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(100),
+        },
+        threshold: strict_threshold.clone(),
+        max_voting_period: Duration::Time(604800 * 2),
+    }];
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(70),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let created_threshold = Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::percent(100)),
+    };
+    let amend = |app: &mut App, id: u64, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Amend {
+                proposal_id: id,
+                title: "amended".to_string(),
+                description: "amended".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(amount, "ujuno"),
+                }
+                .into()],
+            },
+            &[],
+        )
+        .unwrap();
+    };
+
+    let completed = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    app.update_block(|b| b.time = b.time.plus_seconds(100));
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let created = app.block_info().time;
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id)
+            .proposal
+            .threshold,
+        created_threshold
+    );
+
+    // A proposal completing during the review period changes the
+    // turnout new proposals' quorums are fixed with, but not the
+    // quorum of the proposal under review.
+    vote_on_proposal(&mut app, &proposal_module, "blue", completed, Vote::No);
+    close_proposal(&mut app, &proposal_module, "ekez", completed);
+    let new = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, new)
+            .proposal
+            .threshold,
+        Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(70)),
+        }
+    );
+    amend(&mut app, id, 10);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id)
+            .proposal
+            .threshold,
+        created_threshold
+    );
+
+    // Nor does a config update.
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(100),
+            },
+            max_voting_period: Duration::Time(604800),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: Some(Duration::Time(100)),
+            voting_period_choice: None,
+        },
+        &[],
+    )
+    .unwrap();
+    amend(&mut app, id, 10);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id)
+            .proposal
+            .threshold,
+        created_threshold
+    );
+
+    // The threshold rule is selected from the rules the proposal was
+    // created with.
+    amend(&mut app, id, 101);
+    let proposal = query_proposal(&app, &proposal_module, id).proposal;
+    assert_eq!(proposal.threshold, strict_threshold);
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(created.plus_seconds(100 + 604800 * 2))
+    );
+
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    assert_eq!(
+        query_proposal(&app, &proposal_module, id)
+            .proposal
+            .threshold,
+        Threshold::AbsoluteCount {
+            threshold: Uint128::new(100)
+        }
+    );
+}
+
+#[test]
+fn test_adaptive_quorum() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.threshold = Threshold::AdaptiveQuorum {
+        threshold: PercentageThreshold::Majority {},
+        min_quorum: Decimal::percent(10),
+        max_quorum: Decimal::percent(60),
+    };
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "blue".to_string(),
+                amount: Uint128::new(70),
+            },
+        ]),
+    );
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+    let fixed = |quorum| Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::percent(quorum)),
+    };
+
+    // Without any turnout history the max quorum is used.
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.threshold, fixed(60));
+    vote_on_proposal(&mut app, &proposal_module, "ekez", id, Vote::Yes);
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    close_proposal(&mut app, &proposal_module, "ekez", id);
+
+    // 30% turned out for the first proposal.
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.threshold, fixed(30));
+    vote_on_proposal(&mut app, &proposal_module, "ekez", id, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "blue", id, Vote::Yes);
+    execute_proposal(&mut app, &proposal_module, "ekez", id);
+
+    // 0.8 * 30% + 0.2 * 100%
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.threshold, fixed(44));
+
+    // Proposals are counted once their outcome is decided, even if
+    // they are never closed or executed. The last proposal is
+    // rejected when it expires without votes.
+    app.update_block(|b| b.time = b.time.plus_seconds(604800));
+    let quorum = |permyriad| Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::from_ratio(permyriad, 10_000u128)),
+    };
+    // 0.8 * 44% + 0.2 * 0%
+    let passed = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, passed);
+    assert_eq!(proposal.proposal.threshold, quorum(3520u128));
+    vote_on_proposal(&mut app, &proposal_module, "ekez", passed, Vote::Yes);
+    vote_on_proposal(&mut app, &proposal_module, "blue", passed, Vote::Yes);
+
+    // 0.8 * 35.2% + 0.2 * 100%
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.threshold, quorum(4816u128));
+
+    // Executing the passed proposal does not count it again.
+    execute_proposal(&mut app, &proposal_module, "ekez", passed);
+    let id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
+    let proposal = query_proposal(&app, &proposal_module, id);
+    assert_eq!(proposal.proposal.threshold, quorum(4816u128));
+}
+
+#[test]
+fn test_threshold_rules() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let strict_threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Percent(Decimal::percent(75)),
+    };
+    instantiate.threshold_rules = vec![ThresholdRule {
+        matcher: MsgMatcher::BankSend {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(100),
+        },
+        threshold: strict_threshold.clone(),
+        max_voting_period: Duration::Time(604800 * 2),
+    }];
+    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+    let gov_token = query_dao_token(&app, &core_addr);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 20_000_000);
+    let send = |amount| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+
+    let small = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![send(100)]);
+    let large = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![send(101)]);
+    let now = app.block_info().time;
+
+    let small = query_proposal(&app, &proposal_module, small).proposal;
+    assert_eq!(
+        small.threshold,
+        Threshold::ThresholdQuorum {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            threshold: PercentageThreshold::Majority {},
+        }
+    );
+    assert_eq!(
+        small.expiration,
+        Expiration::AtTime(now.plus_seconds(604800))
+    );
+
+    let large = query_proposal(&app, &proposal_module, large).proposal;
+    assert_eq!(large.threshold, strict_threshold);
+    assert_eq!(
+        large.expiration,
+        Expiration::AtTime(now.plus_seconds(604800 * 2))
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "threshold rule voting periods must have the same units as, and be at least as long as, max_voting_period"
+)]
+fn test_threshold_rule_shorter_voting_period() {
+    let mut app = App::default();
+    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.threshold_rules = vec![ThresholdRule {
+        matcher: MsgMatcher::WasmMigrate {
+            contract_addr: None,
+        },
+        threshold: instantiate.threshold.clone(),
+        max_voting_period: Duration::Time(60),
+    }];
+    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
+}
+
+#[test]
+fn test_voting_period_choice() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    instantiate.threshold = Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+    };
+    instantiate.voting_period_choice = Some(VotingPeriodChoice {
+        min: Duration::Time(86400),
+        quorum_schedule: vec![QuorumStep {
+            max_voting_period: Duration::Time(86400 * 2),
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+        }],
+    });
+    instantiate.threshold_rules = vec![ThresholdRule {
+        matcher: MsgMatcher::BankSend {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(100),
+        },
+        threshold: instantiate.threshold.clone(),
+        max_voting_period: Duration::Time(604800 * 2),
+    }];
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
+
+    let propose = |app: &mut App, msgs: Vec<CosmosMsg>, voting_period: Option<Duration>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose(ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs,
+                proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period,
+            }),
+            &[],
+        )
+    };
+    let now = app.block_info().time;
+
+    // Urgent proposals close sooner and require a higher quorum.
+    propose(&mut app, vec![], Some(Duration::Time(86400))).unwrap();
+    let proposal = query_proposal(&app, &proposal_module, 1).proposal;
+    assert_eq!(proposal.voting_period, Some(Duration::Time(86400)));
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(now.plus_seconds(86400))
+    );
+    assert_eq!(
+        proposal.threshold,
+        Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+        }
+    );
+
+    // Periods not covered by the schedule use the module's quorum.
+    propose(&mut app, vec![], Some(Duration::Time(86400 * 3))).unwrap();
+    let proposal = query_proposal(&app, &proposal_module, 2).proposal;
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(now.plus_seconds(86400 * 3))
+    );
+    assert_eq!(
+        proposal.threshold,
+        Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+        }
+    );
+
+    // Proposers that do not choose get the max voting period.
+    propose(&mut app, vec![], None).unwrap();
+    let proposal = query_proposal(&app, &proposal_module, 3).proposal;
+    assert_eq!(proposal.voting_period, None);
+    assert_eq!(
+        proposal.expiration,
+        Expiration::AtTime(now.plus_seconds(604800))
+    );
+
+    for period in [Duration::Time(86399), Duration::Time(604801)] {
+        let err: ContractError = propose(&mut app, vec![], Some(period))
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(
+            err,
+            ContractError::VotingError(VotingError::InvalidVotingPeriod {})
+        ));
+    }
 
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            proposal_module.clone(),
-            &ExecuteMsg::Propose(ProposeMsg {
-                title: "title".to_string(),
-                description: "description".to_string(),
-                msgs: vec![],
-                proposer: None,
-            }),
-            &[],
-        )
+    // Proposals matching a threshold rule use the rule's period.
+    let send: CosmosMsg = BankMsg::Send {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(101, "ujuno"),
+    }
+    .into();
+    let err: ContractError = propose(&mut app, vec![send], Some(Duration::Time(86400)))
         .unwrap_err()
         .downcast()
         .unwrap();
-    assert!(matches!(err, ContractError::InactiveDao {}));
+    assert!(matches!(
+        err,
+        ContractError::ThresholdRuleVotingPeriod { rule: 0 }
+    ));
 }
 
 #[test]
-fn test_active_threshold_percent() {
+fn test_quorum_schedule_keeps_adaptive_quorum() {
     let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
     instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
-    let core_addr = instantiate_with_staking_active_threshold(
-        &mut app,
-        instantiate,
-        None,
-        Some(ActiveThreshold::Percentage {
-            percent: Decimal::percent(20),
-        }),
-    );
-    let gov_token = query_dao_token(&app, &core_addr);
+    // With no turnout recorded yet the adaptive quorum is its max.
+    instantiate.threshold = Threshold::AdaptiveQuorum {
+        threshold: PercentageThreshold::Majority {},
+        min_quorum: Decimal::percent(20),
+        max_quorum: Decimal::percent(40),
+    };
+    instantiate.voting_period_choice = Some(VotingPeriodChoice {
+        min: Duration::Time(86400),
+        quorum_schedule: vec![
+            QuorumStep {
+                max_voting_period: Duration::Time(86400),
+                quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            },
+            QuorumStep {
+                max_voting_period: Duration::Time(86400 * 3),
+                quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+            },
+        ],
+    });
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
     let proposal_module = query_single_proposal_module(&app, &core_addr);
-    let voting_module = query_voting_module(&app, &core_addr);
-
-    let staking_contract: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module,
-            &dao_voting_cw20_staked::msg::QueryMsg::StakingContract {},
-        )
-        .unwrap();
 
-    let err: ContractError = app
-        .execute_contract(
+    let quorum_for = |app: &mut App, voting_period: Duration| {
+        app.execute_contract(
             Addr::unchecked(CREATOR_ADDR),
             proposal_module.clone(),
             &ExecuteMsg::Propose(ProposeMsg {
@@ -958,207 +3381,88 @@ fn test_active_threshold_percent() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: Some(voting_period),
             }),
             &[],
         )
-        .unwrap_err()
-        .downcast()
         .unwrap();
-    assert!(matches!(err, ContractError::InactiveDao {}));
-
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(20_000_000),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+        let count: u64 = app
+            .wrap()
+            .query_wasm_smart(&proposal_module, &QueryMsg::ProposalCount {})
+            .unwrap();
+        match query_proposal(app, &proposal_module, count)
+            .proposal
+            .threshold
+        {
+            Threshold::ThresholdQuorum { quorum, .. } => quorum,
+            threshold => panic!("unexpected threshold {threshold:?}"),
+        }
     };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), gov_token, &msg, &[])
-        .unwrap();
-    app.update_block(next_block);
 
-    // Proposal creation now works as tokens have been staked to reach
-    // active threshold.
-    make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    // Steps above the adaptive quorum raise it, and steps below it
+    // leave it unchanged.
+    assert_eq!(
+        quorum_for(&mut app, Duration::Time(86400)),
+        PercentageThreshold::Percent(Decimal::percent(50))
+    );
+    assert_eq!(
+        quorum_for(&mut app, Duration::Time(86400 * 2)),
+        PercentageThreshold::Percent(Decimal::percent(40))
+    );
+}
 
-    // Unstake some tokens to make it inactive again.
-    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
-        amount: Uint128::new(1), // Only one is needed as we're right
-                                 // on the edge. :)
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
-        .unwrap();
-    app.update_block(next_block);
+#[test]
+fn test_voting_period_not_choosable() {
+    let mut app = App::default();
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.pre_propose_info = PreProposeInfo::AnyoneMayPropose {};
+    let core_addr = instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
+    let proposal_module = query_single_proposal_module(&app, &core_addr);
 
     let err: ContractError = app
         .execute_contract(
             Addr::unchecked(CREATOR_ADDR),
-            proposal_module.clone(),
+            proposal_module,
             &ExecuteMsg::Propose(ProposeMsg {
                 title: "title".to_string(),
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: Some(Duration::Time(86400)),
             }),
             &[],
         )
         .unwrap_err()
         .downcast()
         .unwrap();
-    assert!(matches!(err, ContractError::InactiveDao {}));
-}
-
-#[test]
-#[should_panic(
-    expected = "min_voting_period and max_voting_period must have the same units (height or time)"
-)]
-fn test_min_duration_unit_missmatch() {
-    let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
-    instantiate.min_voting_period = Some(Duration::Height(10));
-    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
-}
-
-#[test]
-#[should_panic(expected = "Min voting period must be less than or equal to max voting period")]
-fn test_min_duration_larger_than_proposal_duration() {
-    let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
-    instantiate.min_voting_period = Some(Duration::Time(604801));
-    instantiate_with_staked_balances_governance(&mut app, instantiate, None);
-}
-
-#[test]
-fn test_min_voting_period_no_early_pass() {
-    let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
-    instantiate.min_voting_period = Some(Duration::Height(10));
-    instantiate.max_voting_period = Duration::Height(100);
-    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
-    let gov_token = query_dao_token(&app, &core_addr);
-    let proposal_module = query_single_proposal_module(&app, &core_addr);
-
-    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
-    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
-    vote_on_proposal(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::Yes,
-    );
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Open);
-
-    app.update_block(|mut block| block.height += 10);
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Passed);
-}
-
-// Setting the min duration the same as the proposal duration just
-// means that proposals cant close early.
-#[test]
-fn test_min_duration_same_as_proposal_duration() {
-    let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
-    instantiate.min_voting_period = Some(Duration::Height(100));
-    instantiate.max_voting_period = Duration::Height(100);
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        instantiate,
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "whale".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
-    );
-    let gov_token = query_dao_token(&app, &core_addr);
-    let proposal_module = query_single_proposal_module(&app, &core_addr);
-
-    mint_cw20s(&mut app, &gov_token, &core_addr, "ekez", 10_000_000);
-    let proposal_id = make_proposal(&mut app, &proposal_module, "ekez", vec![]);
-
-    // Whale votes yes. Normally the proposal would just pass and ekez
-    // would be out of luck.
-    vote_on_proposal(&mut app, &proposal_module, "whale", proposal_id, Vote::Yes);
-    vote_on_proposal(&mut app, &proposal_module, "ekez", proposal_id, Vote::No);
-
-    app.update_block(|mut b| b.height += 100);
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Passed);
+    assert!(matches!(
+        err,
+        ContractError::VotingError(VotingError::VotingPeriodNotChoosable {})
+    ));
 }
 
 #[test]
-fn test_revoting_playthrough() {
+#[should_panic(expected = "a quorum schedule requires a threshold with a quorum")]
+fn test_quorum_schedule_without_quorum() {
     let mut app = App::default();
-    let mut instantiate = get_default_token_dao_proposal_module_instantiate(&mut app);
-    instantiate.allow_revoting = true;
-    let core_addr = instantiate_with_staked_balances_governance(&mut app, instantiate, None);
-    let gov_token = query_dao_token(&app, &core_addr);
-    let proposal_module = query_single_proposal_module(&app, &core_addr);
-
-    mint_cw20s(&mut app, &gov_token, &core_addr, CREATOR_ADDR, 10_000_000);
-    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
-
-    // Vote and change our minds a couple times.
-    vote_on_proposal(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::Yes,
-    );
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Open);
-
-    vote_on_proposal(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::No,
-    );
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Open);
-
-    vote_on_proposal(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::Yes,
-    );
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Open);
-
-    // Can't cast the same vote more than once.
-    let err = vote_on_proposal_should_fail(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::Yes,
-    );
-    assert!(matches!(err, ContractError::AlreadyCast {}));
-
-    // Expire the proposal allowing the votes to be tallied.
-    app.update_block(|mut b| b.time = b.time.plus_seconds(604800));
-    let proposal_response = query_proposal(&app, &proposal_module, proposal_id);
-    assert_eq!(proposal_response.proposal.status, Status::Passed);
-    execute_proposal(&mut app, &proposal_module, CREATOR_ADDR, proposal_id);
-
-    // Can't vote once the proposal is passed.
-    let err = vote_on_proposal_should_fail(
-        &mut app,
-        &proposal_module,
-        CREATOR_ADDR,
-        proposal_id,
-        Vote::Yes,
-    );
-    assert!(matches!(err, ContractError::Expired { .. }));
+    let mut instantiate = get_default_non_token_dao_proposal_module_instantiate(&mut app);
+    instantiate.threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    instantiate.voting_period_choice = Some(VotingPeriodChoice {
+        min: Duration::Time(86400),
+        quorum_schedule: vec![QuorumStep {
+            max_voting_period: Duration::Time(86400),
+            quorum: PercentageThreshold::Majority {},
+        }],
+    });
+    instantiate_with_cw4_groups_governance(&mut app, instantiate, None);
 }
 
 /// Tests that revoting is stored at a per-proposal level. Proposals
@@ -1194,6 +3498,14 @@ fn test_allow_revoting_config_changes() {
             allow_revoting: false,
             dao: core_addr.to_string(),
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         },
         &[],
     )
@@ -1496,6 +3808,14 @@ fn test_proposal_count_initialized_to_zero() {
             allow_revoting: false,
             pre_propose_info,
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         },
         Some(vec![
             Cw20Coin {
@@ -1780,6 +4100,14 @@ fn test_migrate_from_v1() {
             allow_revoting: false,
             dao: core_addr.clone(),
             close_proposal_on_execution_failure: true,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         }
     );
 
@@ -1805,9 +4133,11 @@ fn test_migrate_from_v1() {
         vote.vote.unwrap(),
         VoteInfo {
             voter: Addr::unchecked(CREATOR_ADDR),
-            vote: Vote::No,
+            vote: Some(Vote::No),
             power: Uint128::new(100),
-            rationale: None
+            rationale: None,
+            commitment: None,
+            delegated_power: Uint128::zero(),
         }
     );
 
@@ -1913,6 +4243,14 @@ fn test_execution_failed() {
             dao: config.dao.into_string(),
             // Disable.
             close_proposal_on_execution_failure: false,
+            anti_sniping: None,
+            threshold_rules: vec![],
+            commit_reveal: None,
+            delegation_registry: None,
+            partial_execution: false,
+            power_transform: None,
+            review_period: None,
+            voting_period_choice: None,
         },
         &[],
     )
@@ -1952,13 +4290,13 @@ fn test_execution_failed() {
 #[test]
 fn test_reply_proposal_mock() {
     use crate::contract::reply;
-    use crate::state::PROPOSALS;
+    use crate::state::proposals;
 
     let mut deps = mock_dependencies();
     let env = mock_env();
 
     let m_proposal_id = mask_proposal_execution_proposal_id(1);
-    PROPOSALS
+    proposals()
         .save(
             deps.as_mut().storage,
             1,
@@ -1973,6 +4311,18 @@ fn test_reply_proposal_mock() {
                     percentage: PercentageThreshold::Majority {},
                 },
                 allow_revoting: false,
+                anti_sniping: None,
+                extensions: 0,
+                msg_results: vec![],
+                power_transform: None,
+                review_expiration: None,
+                version: 0,
+                commit_expiration: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
+                turnout_recorded: false,
                 total_power: Uint128::new(100_000_000),
                 msgs: vec![],
                 status: Status::Open,
@@ -1995,7 +4345,7 @@ fn test_reply_proposal_mock() {
         }
     );
 
-    let prop = PROPOSALS.load(deps.as_mut().storage, 1).unwrap();
+    let prop = proposals().load(deps.as_mut().storage, 1).unwrap();
     assert_eq!(prop.status, Status::ExecutionFailed);
 }
 
@@ -2016,6 +4366,10 @@ fn test_proposal_too_large() {
                 description: "a".repeat(MAX_PROPOSAL_SIZE as usize),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             }),
             &[],
         )
@@ -2067,6 +4421,10 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             }),
             &[],
         )
@@ -2092,6 +4450,10 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: None,
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             }),
             &[],
         )
@@ -2122,6 +4484,10 @@ fn test_proposal_creation_permissions() {
                 description: "description".to_string(),
                 msgs: vec![],
                 proposer: Some("ekez".to_string()),
+                prerequisites: vec![],
+                execute_at: None,
+                recurrence: None,
+                voting_period: None,
             }),
             &[],
         )
@@ -2304,32 +4670,42 @@ fn test_query_list_votes() {
         vec![
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("five"),
-                vote: Vote::Yes,
+                vote: Some(Vote::Yes),
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("four"),
-                vote: Vote::Yes,
+                vote: Some(Vote::Yes),
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("one"),
-                vote: Vote::Yes,
+                vote: Some(Vote::Yes),
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("three"),
-                vote: Vote::No,
+                vote: Some(Vote::No),
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("two"),
-                vote: Vote::No,
+                vote: Some(Vote::No),
                 power: Uint128::new(1)
             }
         ]
@@ -2347,14 +4723,18 @@ fn test_query_list_votes() {
         vec![
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("one"),
-                vote: Vote::Yes,
+                vote: Some(Vote::Yes),
                 power: Uint128::new(1)
             },
             VoteInfo {
                 rationale: None,
+                commitment: None,
+                delegated_power: Uint128::zero(),
                 voter: Addr::unchecked("three"),
-                vote: Vote::No,
+                vote: Some(Vote::No),
                 power: Uint128::new(1)
             },
         ]
@@ -0,0 +1,435 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_binary, Api, BankMsg, CosmosMsg, Empty, StdResult, Uint128, WasmMsg};
+use cw_utils::Duration;
+use dao_voting::threshold::Threshold;
+use serde::de::IgnoredAny;
+
+use crate::error::ContractError;
+
+/// Describes a kind of message that a proposal may contain.
+#[cw_serde]
+pub enum MsgMatcher {
+    /// Matches proposals whose `BankMsg::Send`s send more than
+    /// `amount` of `denom` in total.
+    BankSend { denom: String, amount: Uint128 },
+    /// Matches any `WasmMsg::Migrate`. If `contract_addr` is set,
+    /// only migrations of that contract are matched.
+    WasmMigrate { contract_addr: Option<String> },
+    /// Matches any `WasmMsg::Execute`. If `contract_addr` is set,
+    /// only executions on that contract are matched. If `method` is
+    /// set, only executions whose message has `method` as its top
+    /// level key are matched. For example, a matcher with the DAO's
+    /// address and a method of `update_voting_module` matches
+    /// proposals that replace the DAO's voting module.
+    WasmExecute {
+        contract_addr: Option<String>,
+        method: Option<String>,
+    },
+}
+
+/// Assigns a threshold and voting period to proposals containing
+/// messages matched by `matcher`.
+///
+/// A proposal matching more than one rule uses the first of them, so
+/// rules should be listed from strictest to least strict. A rule that
+/// could never be selected as an earlier rule matches every proposal
+/// it does is rejected.
+#[cw_serde]
+pub struct ThresholdRule {
+    /// The messages this rule applies to. The rule applies to a
+    /// proposal if any of its messages match, or for `BankSend`, if
+    /// its messages send more than the amount in total.
+    pub matcher: MsgMatcher,
+    /// The threshold proposals matched by this rule must reach to
+    /// complete.
+    pub threshold: Threshold,
+    /// The maximum amount of time proposals matched by this rule may
+    /// be voted on before expiring. Must be at least as long as the
+    /// module's default `max_voting_period`.
+    pub max_voting_period: Duration,
+}
+
+impl MsgMatcher {
+    /// Returns true if a proposal containing MSGS is matched by this
+    /// matcher. Amounts sent are summed across every message, so
+    /// splitting a transfer does not escape a `BankSend` matcher.
+    pub fn matches(&self, msgs: &[CosmosMsg<Empty>]) -> bool {
+        match self {
+            MsgMatcher::BankSend { denom, amount } => {
+                let sent = msgs
+                    .iter()
+                    .filter_map(|msg| match msg {
+                        CosmosMsg::Bank(BankMsg::Send { amount: coins, .. }) => Some(coins),
+                        _ => None,
+                    })
+                    .flatten()
+                    .filter(|c| c.denom == *denom)
+                    .fold(Uint128::zero(), |sent, c| sent.saturating_add(c.amount));
+                sent > *amount
+            }
+            _ => msgs.iter().any(|msg| self.matches_msg(msg)),
+        }
+    }
+
+    /// Returns true if MSG is matched by this matcher. Only used for
+    /// matchers that apply to individual messages.
+    fn matches_msg(&self, msg: &CosmosMsg<Empty>) -> bool {
+        match (self, msg) {
+            (
+                MsgMatcher::WasmMigrate {
+                    contract_addr: expected,
+                },
+                CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }),
+            ) => expected.as_ref().map_or(true, |e| e == contract_addr),
+            (
+                MsgMatcher::WasmExecute {
+                    contract_addr: expected,
+                    method,
+                },
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }),
+            ) => {
+                expected.as_ref().map_or(true, |e| e == contract_addr)
+                    && method.as_ref().map_or(true, |method| {
+                        // Execute messages are JSON objects keyed by
+                        // the method they call. Anything else can not
+                        // match a method.
+                        from_binary::<BTreeMap<String, IgnoredAny>>(msg)
+                            .map(|keys| keys.contains_key(method))
+                            .unwrap_or(false)
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if every proposal matched by OTHER is also matched
+    /// by this matcher.
+    fn covers(&self, other: &MsgMatcher) -> bool {
+        let covers_addr = |a: &Option<String>, b: &Option<String>| a.is_none() || a == b;
+        match (self, other) {
+            (
+                MsgMatcher::BankSend { denom, amount },
+                MsgMatcher::BankSend {
+                    denom: other_denom,
+                    amount: other_amount,
+                },
+            ) => denom == other_denom && amount <= other_amount,
+            (
+                MsgMatcher::WasmMigrate { contract_addr },
+                MsgMatcher::WasmMigrate {
+                    contract_addr: other_addr,
+                },
+            ) => covers_addr(contract_addr, other_addr),
+            (
+                MsgMatcher::WasmExecute {
+                    contract_addr,
+                    method,
+                },
+                MsgMatcher::WasmExecute {
+                    contract_addr: other_addr,
+                    method: other_method,
+                },
+            ) => covers_addr(contract_addr, other_addr) && covers_addr(method, other_method),
+            _ => false,
+        }
+    }
+
+    /// Validates any addresses in the matcher.
+    fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        match self {
+            MsgMatcher::BankSend { .. } => (),
+            MsgMatcher::WasmMigrate { contract_addr }
+            | MsgMatcher::WasmExecute { contract_addr, .. } => {
+                if let Some(addr) = contract_addr {
+                    api.addr_validate(addr)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ThresholdRule {
+    /// Validates the rule against the module's default max voting
+    /// period.
+    pub fn validate(
+        &self,
+        api: &dyn Api,
+        default_max_voting_period: &Duration,
+    ) -> Result<(), ContractError> {
+        self.matcher.validate(api)?;
+        self.threshold.validate()?;
+        let valid = match (self.max_voting_period, default_max_voting_period) {
+            (Duration::Height(rule), Duration::Height(default)) => rule >= *default,
+            (Duration::Time(rule), Duration::Time(default)) => rule >= *default,
+            _ => false,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ContractError::InvalidThresholdRuleVotingPeriod {})
+        }
+    }
+}
+
+/// Validates RULES, and that each may be selected for some proposal,
+/// i.e. that no rule is preceded by one that matches every proposal
+/// it does.
+pub fn validate_threshold_rules(
+    api: &dyn Api,
+    rules: &[ThresholdRule],
+    default_max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    for (index, rule) in rules.iter().enumerate() {
+        rule.validate(api, default_max_voting_period)?;
+        if let Some(by) = rules[..index]
+            .iter()
+            .position(|earlier| earlier.matcher.covers(&rule.matcher))
+        {
+            return Err(ContractError::ShadowedThresholdRule { rule: index, by });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first rule in RULES that matches a proposal containing
+/// MSGS along with its index, if any.
+pub fn find_threshold_rule<'a>(
+    rules: &'a [ThresholdRule],
+    msgs: &[CosmosMsg<Empty>],
+) -> Option<(usize, &'a ThresholdRule)> {
+    rules
+        .iter()
+        .enumerate()
+        .find(|(_, rule)| rule.matcher.matches(msgs))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, testing::mock_dependencies, to_binary, Decimal};
+    use dao_voting::threshold::PercentageThreshold;
+
+    use super::*;
+
+    fn execute(contract_addr: &str, msg: &impl serde::Serialize) -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(msg).unwrap(),
+            funds: vec![],
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_bank_send_matcher() {
+        let matcher = MsgMatcher::BankSend {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(100),
+        };
+        let send = |amount, denom| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(amount, denom),
+            }
+            .into()
+        };
+        assert!(matcher.matches(&[send(101, "ujuno")]));
+        assert!(!matcher.matches(&[send(100, "ujuno")]));
+        assert!(!matcher.matches(&[send(101, "uatom")]));
+        assert!(!matcher.matches(&[BankMsg::Burn {
+            amount: coins(101, "ujuno")
+        }
+        .into()]));
+
+        // Amounts are summed across messages and coins.
+        assert!(matcher.matches(&[send(50, "ujuno"), send(51, "ujuno")]));
+        assert!(!matcher.matches(&[send(50, "ujuno"), send(51, "uatom")]));
+        assert!(matcher.matches(&[BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: vec![
+                cosmwasm_std::coin(60, "ujuno"),
+                cosmwasm_std::coin(41, "ujuno")
+            ],
+        }
+        .into()]));
+    }
+
+    #[test]
+    fn test_wasm_matchers() {
+        let update_voting_module_msg = dao_core::msg::ExecuteMsg::UpdateVotingModule {
+            module: dao_interface::ModuleInstantiateInfo {
+                code_id: 1,
+                msg: to_binary("").unwrap(),
+                admin: None,
+                label: "voting".to_string(),
+            },
+        };
+        let update_voting_module = execute("dao", &update_voting_module_msg);
+        let matcher = MsgMatcher::WasmExecute {
+            contract_addr: Some("dao".to_string()),
+            method: Some("update_voting_module".to_string()),
+        };
+        assert!(matcher.matches(&[update_voting_module.clone()]));
+        assert!(!matcher.matches(&[execute("other", &update_voting_module_msg)]));
+        assert!(!matcher.matches(&[execute(
+            "dao",
+            &dao_core::msg::ExecuteMsg::Pause {
+                duration: Duration::Height(1)
+            }
+        )]));
+        // Non-object messages never match a method.
+        assert!(!matcher.matches(&[execute("dao", &"update_voting_module")]));
+
+        let matcher = MsgMatcher::WasmExecute {
+            contract_addr: None,
+            method: None,
+        };
+        assert!(matcher.matches(&[update_voting_module.clone()]));
+
+        let migrate: CosmosMsg = WasmMsg::Migrate {
+            contract_addr: "dao".to_string(),
+            new_code_id: 2,
+            msg: to_binary("").unwrap(),
+        }
+        .into();
+        assert!(!matcher.matches(&[migrate.clone()]));
+        assert!(MsgMatcher::WasmMigrate {
+            contract_addr: None
+        }
+        .matches(&[migrate.clone()]));
+        assert!(!MsgMatcher::WasmMigrate {
+            contract_addr: Some("other".to_string())
+        }
+        .matches(&[migrate.clone()]));
+    }
+
+    #[test]
+    fn test_find_threshold_rule() {
+        let rule = |amount| ThresholdRule {
+            matcher: MsgMatcher::BankSend {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(amount),
+            },
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(amount as u64)),
+            },
+            max_voting_period: Duration::Height(amount as u64),
+        };
+        let rules = vec![rule(50), rule(10)];
+        let send = |amount| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: coins(amount, "ujuno"),
+            }
+            .into()
+        };
+
+        assert_eq!(find_threshold_rule(&rules, &[]), None);
+        assert_eq!(find_threshold_rule(&rules, &[send(5)]), None);
+        assert_eq!(
+            find_threshold_rule(&rules, &[send(20)]),
+            Some((1, &rules[1]))
+        );
+        // The first matching rule is selected, and amounts are
+        // summed across messages.
+        assert_eq!(
+            find_threshold_rule(&rules, &[send(20), send(60)]),
+            Some((0, &rules[0]))
+        );
+        assert_eq!(
+            find_threshold_rule(&rules, &[send(30), send(30)]),
+            Some((0, &rules[0]))
+        );
+    }
+
+    #[test]
+    fn test_validate_threshold_rules() {
+        let deps = mock_dependencies();
+        let rule = |matcher| ThresholdRule {
+            matcher,
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(10),
+        };
+        let send = |amount| {
+            rule(MsgMatcher::BankSend {
+                denom: "ujuno".to_string(),
+                amount: Uint128::new(amount),
+            })
+        };
+        let execute = |contract_addr: Option<&str>, method: Option<&str>| {
+            rule(MsgMatcher::WasmExecute {
+                contract_addr: contract_addr.map(|a| a.to_string()),
+                method: method.map(|m| m.to_string()),
+            })
+        };
+        let max = Duration::Height(10);
+
+        validate_threshold_rules(&deps.api, &[send(50), send(10)], &max).unwrap();
+        validate_threshold_rules(
+            &deps.api,
+            &[execute(Some("dao"), Some("pause")), execute(None, None)],
+            &max,
+        )
+        .unwrap();
+
+        // A larger send is matched by every proposal matching a
+        // smaller one, so it may not follow it.
+        assert!(matches!(
+            validate_threshold_rules(&deps.api, &[send(10), send(50)], &max).unwrap_err(),
+            ContractError::ShadowedThresholdRule { rule: 1, by: 0 }
+        ));
+        assert!(matches!(
+            validate_threshold_rules(
+                &deps.api,
+                &[
+                    send(10),
+                    execute(None, Some("pause")),
+                    execute(Some("dao"), Some("pause"))
+                ],
+                &max
+            )
+            .unwrap_err(),
+            ContractError::ShadowedThresholdRule { rule: 2, by: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        let deps = mock_dependencies();
+        let rule = ThresholdRule {
+            matcher: MsgMatcher::WasmMigrate {
+                contract_addr: Some("dao".to_string()),
+            },
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(10),
+        };
+        rule.validate(&deps.api, &Duration::Height(10)).unwrap();
+        assert!(matches!(
+            rule.validate(&deps.api, &Duration::Height(11)).unwrap_err(),
+            ContractError::InvalidThresholdRuleVotingPeriod {}
+        ));
+        assert!(matches!(
+            rule.validate(&deps.api, &Duration::Time(1)).unwrap_err(),
+            ContractError::InvalidThresholdRuleVotingPeriod {}
+        ));
+
+        let rule = ThresholdRule {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::zero()),
+            },
+            ..rule
+        };
+        assert!(matches!(
+            rule.validate(&deps.api, &Duration::Height(10)).unwrap_err(),
+            ContractError::ThresholdError(_)
+        ));
+    }
+}
@@ -40,6 +40,15 @@ pub fn cw20_stake_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn delegation_registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_delegation_registry::contract::execute,
+        dao_delegation_registry::contract::instantiate,
+        dao_delegation_registry::contract::query,
+    );
+    Box::new(contract)
+}
+
 pub fn proposal_condorcet_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_proposal_condorcet::contract::execute,
@@ -50,6 +59,26 @@ pub fn proposal_condorcet_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn proposal_conviction_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_conviction::contract::execute,
+        dao_proposal_conviction::contract::instantiate,
+        dao_proposal_conviction::contract::query,
+    )
+    .with_reply(dao_proposal_conviction::contract::reply);
+    Box::new(contract)
+}
+
+pub fn proposal_optimistic_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        dao_proposal_optimistic::contract::execute,
+        dao_proposal_optimistic::contract::instantiate,
+        dao_proposal_optimistic::contract::query,
+    )
+    .with_reply(dao_proposal_optimistic::contract::reply);
+    Box::new(contract)
+}
+
 pub fn proposal_single_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         dao_proposal_single::contract::execute,
@@ -10,6 +10,9 @@ pub enum VoteHookMsg {
     NewVote {
         proposal_id: u64,
         voter: String,
+        /// The voter's position. Proposal modules using secret
+        /// ballots send `committed` when a vote is committed to and
+        /// the voter's position once it is revealed.
         vote: String,
     },
 }
@@ -0,0 +1,139 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::BlockInfo;
+use cw_utils::{Duration, Expiration};
+
+use crate::error::VotingError;
+
+/// Configuration for extending a proposal's voting period when a
+/// late vote changes its outcome. Without this, a large voter may
+/// wait until the last block of a proposal to flip its result,
+/// leaving the rest of the DAO no time to respond.
+#[cw_serde]
+#[derive(Copy)]
+pub struct AntiSnipingConfig {
+    /// The window before a proposal's expiration during which a vote
+    /// that changes the proposal's outcome causes the proposal's
+    /// expiration to be extended.
+    pub window: Duration,
+    /// The amount of time a proposal's expiration is extended by
+    /// each time an outcome changing vote is cast in the window.
+    pub extension: Duration,
+    /// The maximum number of times a proposal's expiration may be
+    /// extended.
+    pub max_extensions: u64,
+}
+
+impl AntiSnipingConfig {
+    /// Validates that the window and extension are non-zero and have
+    /// the same units (height or time) as the max voting period they
+    /// will be applied to.
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), VotingError> {
+        for duration in [self.window, self.extension] {
+            match (duration, max_voting_period) {
+                (Duration::Height(0), _) | (Duration::Time(0), _) => {
+                    return Err(VotingError::ZeroAntiSnipingDuration {})
+                }
+                (Duration::Height(_), Duration::Height(_))
+                | (Duration::Time(_), Duration::Time(_)) => (),
+                _ => return Err(VotingError::AntiSnipingUnitsConflict {}),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if BLOCK is inside the extension window of a
+    /// proposal expiring at EXPIRATION.
+    pub fn is_in_window(&self, block: &BlockInfo, expiration: &Expiration) -> bool {
+        !expiration.is_expired(block) && self.window.after(block) >= *expiration
+    }
+}
+
+/// Returns a block that is identical to BLOCK except that it is at
+/// EXPIRATION. This is used to determine what a proposal's outcome
+/// would be if voting closed with the current tally.
+pub fn block_at_expiration(block: &BlockInfo, expiration: &Expiration) -> BlockInfo {
+    match expiration {
+        Expiration::AtHeight(height) => BlockInfo {
+            height: *height,
+            ..block.clone()
+        },
+        Expiration::AtTime(time) => BlockInfo {
+            time: *time,
+            ..block.clone()
+        },
+        Expiration::Never {} => block.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let config = AntiSnipingConfig {
+            window: Duration::Height(10),
+            extension: Duration::Height(5),
+            max_extensions: 2,
+        };
+        config.validate(&Duration::Height(100)).unwrap();
+        assert_eq!(
+            config.validate(&Duration::Time(100)).unwrap_err(),
+            VotingError::AntiSnipingUnitsConflict {}
+        );
+
+        let config = AntiSnipingConfig {
+            window: Duration::Time(10),
+            extension: Duration::Height(5),
+            max_extensions: 2,
+        };
+        assert_eq!(
+            config.validate(&Duration::Time(100)).unwrap_err(),
+            VotingError::AntiSnipingUnitsConflict {}
+        );
+
+        let config = AntiSnipingConfig {
+            window: Duration::Time(10),
+            extension: Duration::Time(0),
+            max_extensions: 2,
+        };
+        assert_eq!(
+            config.validate(&Duration::Time(100)).unwrap_err(),
+            VotingError::ZeroAntiSnipingDuration {}
+        );
+    }
+
+    #[test]
+    fn test_is_in_window() {
+        let block = mock_env().block;
+        let config = AntiSnipingConfig {
+            window: Duration::Height(10),
+            extension: Duration::Height(5),
+            max_extensions: 1,
+        };
+
+        assert!(config.is_in_window(&block, &Expiration::AtHeight(block.height + 10)));
+        assert!(config.is_in_window(&block, &Expiration::AtHeight(block.height + 1)));
+        assert!(!config.is_in_window(&block, &Expiration::AtHeight(block.height + 11)));
+        // Expired proposals are never in the window.
+        assert!(!config.is_in_window(&block, &Expiration::AtHeight(block.height)));
+        // Mismatched units can not be compared.
+        assert!(!config.is_in_window(&block, &Expiration::AtTime(block.time)));
+        assert!(!config.is_in_window(&block, &Expiration::Never {}));
+    }
+
+    #[test]
+    fn test_block_at_expiration() {
+        let block = mock_env().block;
+
+        let at = block_at_expiration(&block, &Expiration::AtHeight(block.height + 5));
+        assert_eq!(at.height, block.height + 5);
+        assert_eq!(at.time, block.time);
+
+        let at = block_at_expiration(&block, &Expiration::AtTime(block.time.plus_seconds(5)));
+        assert_eq!(at.height, block.height);
+        assert_eq!(at.time, block.time.plus_seconds(5));
+    }
+}
@@ -11,4 +11,28 @@ pub enum VotingError {
 
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
+
+    #[error("Anti-sniping window and extension must have the same units (height or time) as the max voting period")]
+    AntiSnipingUnitsConflict {},
+
+    #[error("Anti-sniping window and extension must be non-zero")]
+    ZeroAntiSnipingDuration {},
+
+    #[error("Power transform cap must be greater than 0% and no greater than 100%")]
+    InvalidPowerTransformCap {},
+
+    #[error("Recurring proposals must have a non-zero interval and more than one instalment")]
+    InvalidRecurrence {},
+
+    #[error("The shortest choosable voting period must be non-zero and between the min and max voting periods")]
+    InvalidVotingPeriodChoice {},
+
+    #[error("Quorum schedule steps must have valid quorums and apply to choosable voting periods")]
+    InvalidQuorumSchedule {},
+
+    #[error("This module does not allow proposers to choose a voting period")]
+    VotingPeriodNotChoosable {},
+
+    #[error("Voting period must be between the shortest choosable voting period and the max voting period")]
+    InvalidVotingPeriod {},
 }
@@ -1,11 +1,16 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod anti_sniping;
 pub mod deposit;
 pub mod error;
 pub mod multiple_choice;
+pub mod power_transform;
 pub mod pre_propose;
+pub mod prerequisite;
 pub mod proposal;
+pub mod recurrence;
 pub mod reply;
 pub mod status;
 pub mod threshold;
 pub mod voting;
+pub mod voting_period;
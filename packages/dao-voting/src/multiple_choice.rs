@@ -1,44 +1,249 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, StdError, StdResult, Uint128};
 
-use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
+use crate::threshold::{
+    stricter_quorum, validate_percentage, validate_quorum, PercentageThreshold, ThresholdError,
+};
 
 /// Maximum number of choices for multiple choice votes. Chosen
-/// in order to impose a bound on state / queries.
-pub const MAX_NUM_CHOICES: u32 = 20;
+/// in order to impose a bound on the cost of creating a proposal.
+pub const MAX_NUM_CHOICES: u32 = 500;
+/// Maximum number of options an instant runoff ballot may rank.
+/// Bounds the cost of finding a ballot's highest ranked remaining
+/// option in each round of a runoff.
+pub const MAX_RANKING_LENGTH: u32 = 20;
+/// Maximum number of options an instant runoff proposal may list.
+/// Bounds the size of each round's tally, as a runoff may run for
+/// as many rounds as it has options.
+pub const MAX_RUNOFF_CHOICES: u32 = 50;
+/// Maximum `max_selections` of approval voting, and the maximum
+/// number of options an allocation vote may split its power across.
+/// Bounds the number of options whose tallies a single vote updates.
+pub const MAX_SELECTIONS: u32 = 20;
 const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 
 /// Determines how many choices may be selected.
 #[cw_serde]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Voters approve of any set of up to `max_selections` options,
+    /// and each approved option receives the voter's full power. The
+    /// option approved by the most power wins. `max_selections` may
+    /// be at most `MAX_SELECTIONS`.
+    Approval {
+        quorum: PercentageThreshold,
+        max_selections: u32,
+    },
+    /// Voters split their power across up to `MAX_SELECTIONS` options
+    /// by weights summing to 100%, and each option receives its share
+    /// of the voter's power. `winners` determines which options win.
+    Allocation {
+        quorum: PercentageThreshold,
+        winners: AllocationWinners,
+    },
+    /// Voters rank options in order of preference. Once voting
+    /// closes, options that can no longer win are eliminated in
+    /// rounds, with each ballot counting towards its highest ranked
+    /// remaining option, until one option has a majority of the
+    /// ballots still counted. Ballots may rank at most
+    /// `MAX_RANKING_LENGTH` options, and proposals may list at most
+    /// `MAX_RUNOFF_CHOICES`.
+    InstantRunoff {
+        quorum: PercentageThreshold,
+    },
+}
+
+/// Determines the winners of a proposal under allocation voting.
+#[cw_serde]
+#[derive(Copy)]
+pub enum AllocationWinners {
+    /// The option allocated the most power wins.
+    Highest {},
+    /// Every option, other than "None of the above", allocated at
+    /// least `threshold` of the power cast wins, and the messages of
+    /// each are executed. As options may fall below the threshold
+    /// while voting is open, proposals only pass once they expire.
+    AboveThreshold { threshold: PercentageThreshold },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::Approval {
+                quorum,
+                max_selections,
+            } => {
+                if *max_selections == 0 {
+                    return Err(ThresholdError::ZeroMaxSelections {});
+                }
+                if *max_selections > MAX_SELECTIONS {
+                    return Err(ThresholdError::TooManyMaxSelections {
+                        max: MAX_SELECTIONS,
+                    });
+                }
+                validate_quorum(quorum)
+            }
+            VotingStrategy::Allocation { quorum, winners } => {
+                if let AllocationWinners::AboveThreshold { threshold } = winners {
+                    validate_percentage(threshold)?;
+                }
+                validate_quorum(quorum)
+            }
+            VotingStrategy::InstantRunoff { quorum } => validate_quorum(quorum),
         }
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
-            VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoice { quorum }
+            | VotingStrategy::Approval { quorum, .. }
+            | VotingStrategy::Allocation { quorum, .. }
+            | VotingStrategy::InstantRunoff { quorum } => *quorum,
+        }
+    }
+
+    /// Returns this strategy with its quorum raised to at least QUORUM.
+    pub fn raise_quorum(&self, quorum: PercentageThreshold) -> VotingStrategy {
+        let mut strategy = self.clone();
+        match &mut strategy {
+            VotingStrategy::SingleChoice { quorum: q }
+            | VotingStrategy::Approval { quorum: q, .. }
+            | VotingStrategy::Allocation { quorum: q, .. }
+            | VotingStrategy::InstantRunoff { quorum: q } => *q = stricter_quorum(*q, quorum),
+        }
+        strategy
+    }
+
+    /// Returns true if VOTE is a valid vote under this strategy on a
+    /// proposal with NUM_CHOICES options.
+    pub fn is_valid_vote(&self, vote: &MultipleChoiceVote, num_choices: usize) -> bool {
+        let option_ids: Vec<u32> = vote.option_ids().collect();
+        if option_ids.iter().any(|id| *id as usize >= num_choices)
+            || !option_ids
+                .iter()
+                .enumerate()
+                .all(|(i, id)| !option_ids[..i].contains(id))
+        {
+            return false;
+        }
+        match self {
+            VotingStrategy::SingleChoice { .. } => option_ids.len() == 1 && vote.weights.is_empty(),
+            VotingStrategy::Approval { max_selections, .. } => {
+                // Approving of "None of the above" along with other
+                // options is contradictory. The none option is always
+                // the last.
+                option_ids.len() <= *max_selections as usize
+                    && vote.weights.is_empty()
+                    && !(option_ids.len() > 1
+                        && option_ids.iter().any(|id| *id as usize + 1 == num_choices))
+            }
+            VotingStrategy::Allocation { .. } => {
+                option_ids.len() <= MAX_SELECTIONS as usize
+                    && vote.weights.len() == option_ids.len()
+                    && vote.weights.iter().all(|weight| !weight.is_zero())
+                    && vote.weights.iter().sum::<Decimal>() == Decimal::one()
+            }
+            VotingStrategy::InstantRunoff { .. } => {
+                vote.weights.is_empty() && option_ids.len() <= MAX_RANKING_LENGTH as usize
+            }
+        }
+    }
+
+    /// The part of VOTE counted in a proposal's tally. Under instant
+    /// runoff only first preferences are tallied, and lower
+    /// preferences are read from ballots once voting closes.
+    pub fn tallied_vote(&self, vote: &MultipleChoiceVote) -> MultipleChoiceVote {
+        match self {
+            VotingStrategy::InstantRunoff { .. } => MultipleChoiceVote::single(vote.option_id),
+            _ => vote.clone(),
         }
     }
 }
 
-/// A multiple choice vote, picking the desired option
+/// Selects up to `winners` winning options on a proposal instead of
+/// a single one. Winning options are ranked by votes, with ties broken
+/// in favour of the option listed first, and their messages are
+/// executed in rank order. "None of the above" never wins. As a
+/// ranking may change while voting is open, proposals selecting
+/// more than one winner only pass once they expire.
 #[cw_serde]
 #[derive(Copy)]
+pub struct TopN {
+    /// The maximum number of winning options.
+    pub winners: u32,
+    /// The share of the power cast that each winning option must
+    /// receive.
+    pub min_votes: PercentageThreshold,
+}
+
+impl TopN {
+    pub fn validate(&self) -> Result<(), ThresholdError> {
+        if self.winners == 0 {
+            return Err(ThresholdError::ZeroWinners {});
+        }
+        validate_quorum(&self.min_votes)
+    }
+}
+
+/// A multiple choice vote, picking the desired option
+#[cw_serde]
 pub struct MultipleChoiceVote {
     // A vote indicates which option the user has selected.
     pub option_id: u32,
+    /// Further options selected by this vote. Only voting strategies
+    /// which allow more than one selection accept these. Under
+    /// instant runoff these are the voter's lower preferences, in
+    /// order.
+    #[serde(default)]
+    pub additional_option_ids: Vec<u32>,
+    /// Under allocation voting, the share of the voter's power given
+    /// to each selected option, in the order of `option_ids`.
+    #[serde(default)]
+    pub weights: Vec<Decimal>,
+}
+
+impl MultipleChoiceVote {
+    /// A vote for a single option.
+    pub fn single(option_id: u32) -> Self {
+        Self {
+            option_id,
+            additional_option_ids: vec![],
+            weights: vec![],
+        }
+    }
+
+    /// The power given to each selected option by a vote with POWER.
+    pub fn option_powers(&self, power: Uint128) -> impl Iterator<Item = (u32, Uint128)> + '_ {
+        self.option_ids().enumerate().map(move |(i, option_id)| {
+            let power = match self.weights.get(i) {
+                Some(weight) => power * *weight,
+                None => power,
+            };
+            (option_id, power)
+        })
+    }
+
+    /// All of the options selected by this vote.
+    pub fn option_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::once(self.option_id).chain(self.additional_option_ids.iter().copied())
+    }
 }
 
 impl std::fmt::Display for MultipleChoiceVote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.option_id)
+        for (i, option_id) in self.option_ids().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{option_id}")?;
+            if let Some(weight) = self.weights.get(i) {
+                write!(f, ":{weight}")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -48,27 +253,48 @@ pub struct MultipleChoiceVotes {
     // Vote counts is a vector of integers indicating the vote weight for each option
     // (the index corresponds to the option).
     pub vote_weights: Vec<Uint128>,
+    /// The total power of the votes cast. Votes selecting more than
+    /// one option add their power to each, so this may be less than
+    /// the sum of `vote_weights`. None for tallies stored before this
+    /// was tracked, in which case the two are equal.
+    #[serde(default)]
+    pub power_cast: Option<Uint128>,
 }
 
 impl MultipleChoiceVotes {
-    /// Sum of all vote weights
+    /// Total power of the votes cast
     pub fn total(&self) -> Uint128 {
-        self.vote_weights.iter().sum()
+        self.power_cast
+            .unwrap_or_else(|| self.vote_weights.iter().sum())
     }
 
     // Add a vote to the tally
-    pub fn add_vote(&mut self, vote: MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
-        self.vote_weights[vote.option_id as usize] = self.vote_weights[vote.option_id as usize]
-            .checked_add(weight)
-            .map_err(StdError::overflow)?;
+    pub fn add_vote(&mut self, vote: &MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
+        self.power_cast = Some(
+            self.total()
+                .checked_add(weight)
+                .map_err(StdError::overflow)?,
+        );
+        for (option_id, power) in vote.option_powers(weight) {
+            self.vote_weights[option_id as usize] = self.vote_weights[option_id as usize]
+                .checked_add(power)
+                .map_err(StdError::overflow)?;
+        }
         Ok(())
     }
 
     // Remove a vote from the tally
-    pub fn remove_vote(&mut self, vote: MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
-        self.vote_weights[vote.option_id as usize] = self.vote_weights[vote.option_id as usize]
-            .checked_sub(weight)
-            .map_err(StdError::overflow)?;
+    pub fn remove_vote(&mut self, vote: &MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
+        self.power_cast = Some(
+            self.total()
+                .checked_sub(weight)
+                .map_err(StdError::overflow)?,
+        );
+        for (option_id, power) in vote.option_powers(weight) {
+            self.vote_weights[option_id as usize] = self.vote_weights[option_id as usize]
+                .checked_sub(power)
+                .map_err(StdError::overflow)?;
+        }
         Ok(())
     }
 
@@ -76,6 +302,7 @@ impl MultipleChoiceVotes {
     pub fn zero(num_choices: usize) -> Self {
         Self {
             vote_weights: vec![Uint128::zero(); num_choices],
+            power_cast: Some(Uint128::zero()),
         }
     }
 }
@@ -114,13 +341,13 @@ pub struct CheckedMultipleChoiceOptions {
 /// A verified option that has all fields needed for voting.
 #[cw_serde]
 pub struct CheckedMultipleChoiceOption {
-    // This is the index of the option in both the vote_weights and proposal.choices vectors.
-    // Workaround due to not being able to use HashMaps in Cosmwasm.
+    /// The index of the option in its proposal.
     pub index: u32,
     pub option_type: MultipleChoiceOptionType,
     pub title: String,
     pub description: String,
     pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The voting power tallied for this option.
     pub vote_count: Uint128,
 }
 
@@ -178,34 +405,244 @@ mod test {
 
     #[test]
     fn test_display_multiple_choice_vote() {
-        let vote = MultipleChoiceVote { option_id: 0 };
-        assert_eq!("0", vote.to_string())
+        let vote = MultipleChoiceVote::single(0);
+        assert_eq!("0", vote.to_string());
+        let vote = MultipleChoiceVote {
+            option_id: 2,
+            additional_option_ids: vec![0, 3],
+            weights: vec![],
+        };
+        assert_eq!("2,0,3", vote.to_string())
     }
 
     #[test]
     fn test_multiple_choice_votes() {
         let mut votes = MultipleChoiceVotes {
             vote_weights: vec![Uint128::new(10), Uint128::new(100)],
+            power_cast: None,
         };
         let total = votes.total();
         assert_eq!(total, Uint128::new(110));
 
         votes
-            .add_vote(MultipleChoiceVote { option_id: 0 }, Uint128::new(10))
+            .add_vote(&MultipleChoiceVote::single(0), Uint128::new(10))
             .unwrap();
         let total = votes.total();
         assert_eq!(total, Uint128::new(120));
 
         votes
-            .remove_vote(MultipleChoiceVote { option_id: 0 }, Uint128::new(20))
+            .remove_vote(&MultipleChoiceVote::single(0), Uint128::new(20))
             .unwrap();
         votes
-            .remove_vote(MultipleChoiceVote { option_id: 1 }, Uint128::new(100))
+            .remove_vote(&MultipleChoiceVote::single(1), Uint128::new(100))
             .unwrap();
 
         assert_eq!(votes, MultipleChoiceVotes::zero(2))
     }
 
+    #[test]
+    fn test_approval_votes() {
+        let mut votes = MultipleChoiceVotes::zero(3);
+        let vote = MultipleChoiceVote {
+            option_id: 0,
+            additional_option_ids: vec![1],
+            weights: vec![],
+        };
+        votes.add_vote(&vote, Uint128::new(10)).unwrap();
+        votes
+            .add_vote(&MultipleChoiceVote::single(1), Uint128::new(5))
+            .unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(10), Uint128::new(15), Uint128::zero()]
+        );
+        // Each voter's power is only counted once towards turnout.
+        assert_eq!(votes.total(), Uint128::new(15));
+
+        votes.remove_vote(&vote, Uint128::new(10)).unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::zero(), Uint128::new(5), Uint128::zero()]
+        );
+        assert_eq!(votes.total(), Uint128::new(5));
+    }
+
+    #[test]
+    fn test_is_valid_vote() {
+        let quorum = PercentageThreshold::Majority {};
+        let approval = VotingStrategy::Approval {
+            quorum,
+            max_selections: 2,
+        };
+        let vote = |option_id, additional_option_ids| MultipleChoiceVote {
+            option_id,
+            additional_option_ids,
+            weights: vec![],
+        };
+
+        assert!(approval.is_valid_vote(&vote(0, vec![2]), 4));
+        assert!(approval.is_valid_vote(&vote(3, vec![]), 4));
+        // Too many selections.
+        assert!(!approval.is_valid_vote(&vote(0, vec![1, 2]), 4));
+        // Out of range.
+        assert!(!approval.is_valid_vote(&vote(0, vec![4]), 4));
+        // Duplicate selection.
+        assert!(!approval.is_valid_vote(&vote(1, vec![1]), 4));
+        // "None of the above" along with another option.
+        assert!(!approval.is_valid_vote(&vote(0, vec![3]), 4));
+
+        let single = VotingStrategy::SingleChoice { quorum };
+        assert!(single.is_valid_vote(&vote(1, vec![]), 4));
+        assert!(!single.is_valid_vote(&vote(0, vec![1]), 4));
+
+        assert_eq!(
+            VotingStrategy::Approval {
+                quorum,
+                max_selections: 0
+            }
+            .validate(),
+            Err(ThresholdError::ZeroMaxSelections {})
+        );
+        assert_eq!(
+            VotingStrategy::Approval {
+                quorum,
+                max_selections: MAX_SELECTIONS
+            }
+            .validate(),
+            Ok(())
+        );
+        assert_eq!(
+            VotingStrategy::Approval {
+                quorum,
+                max_selections: MAX_SELECTIONS + 1
+            }
+            .validate(),
+            Err(ThresholdError::TooManyMaxSelections {
+                max: MAX_SELECTIONS
+            })
+        );
+    }
+
+    #[test]
+    fn test_allocation_votes() {
+        let allocation = VotingStrategy::Allocation {
+            quorum: PercentageThreshold::Majority {},
+            winners: AllocationWinners::Highest {},
+        };
+        let vote = |option_id, additional_option_ids, weights| MultipleChoiceVote {
+            option_id,
+            additional_option_ids,
+            weights,
+        };
+        let split = vote(0, vec![2], vec![Decimal::percent(75), Decimal::percent(25)]);
+        assert_eq!(split.to_string(), "0:0.75,2:0.25");
+
+        assert!(allocation.is_valid_vote(&split, 3));
+        assert!(allocation.is_valid_vote(&vote(1, vec![], vec![Decimal::one()]), 3));
+        // Weights must sum to 100%.
+        assert!(!allocation.is_valid_vote(
+            &vote(0, vec![1], vec![Decimal::percent(50), Decimal::percent(40)]),
+            3
+        ));
+        // Every selected option must have a non-zero weight.
+        assert!(
+            !allocation.is_valid_vote(&vote(0, vec![1], vec![Decimal::one(), Decimal::zero()]), 3)
+        );
+        assert!(!allocation.is_valid_vote(&vote(0, vec![1], vec![Decimal::one()]), 3));
+        // At most `MAX_SELECTIONS` options may be weighted.
+        let spread = |n: u32| {
+            let mut weights = vec![Decimal::percent(1); n as usize - 1];
+            weights.push(Decimal::percent(101 - n as u64));
+            vote(0, (1..n).collect(), weights)
+        };
+        assert!(allocation.is_valid_vote(&spread(MAX_SELECTIONS), 30));
+        assert!(!allocation.is_valid_vote(&spread(MAX_SELECTIONS + 1), 30));
+        // Weights are only accepted under allocation voting.
+        assert!(!VotingStrategy::SingleChoice {
+            quorum: PercentageThreshold::Majority {}
+        }
+        .is_valid_vote(&vote(1, vec![], vec![Decimal::one()]), 3));
+
+        let mut votes = MultipleChoiceVotes::zero(3);
+        votes.add_vote(&split, Uint128::new(100)).unwrap();
+        votes
+            .add_vote(&MultipleChoiceVote::single(1), Uint128::new(10))
+            .unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::new(75), Uint128::new(10), Uint128::new(25)]
+        );
+        assert_eq!(votes.total(), Uint128::new(110));
+        votes.remove_vote(&split, Uint128::new(100)).unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![Uint128::zero(), Uint128::new(10), Uint128::zero()]
+        );
+
+        assert_eq!(
+            VotingStrategy::Allocation {
+                quorum: PercentageThreshold::Majority {},
+                winners: AllocationWinners::AboveThreshold {
+                    threshold: PercentageThreshold::Percent(Decimal::zero())
+                },
+            }
+            .validate(),
+            Err(ThresholdError::ZeroThreshold {})
+        );
+    }
+
+    #[test]
+    fn test_instant_runoff_votes() {
+        let runoff = VotingStrategy::InstantRunoff {
+            quorum: PercentageThreshold::Majority {},
+        };
+        let ranking = MultipleChoiceVote {
+            option_id: 2,
+            additional_option_ids: vec![0, 1],
+            weights: vec![],
+        };
+        assert!(runoff.is_valid_vote(&ranking, 4));
+        assert!(runoff.is_valid_vote(&MultipleChoiceVote::single(3), 4));
+        assert!(!runoff.is_valid_vote(
+            &MultipleChoiceVote {
+                option_id: 2,
+                additional_option_ids: vec![2],
+                weights: vec![],
+            },
+            4
+        ));
+        let longest = MultipleChoiceVote {
+            option_id: 0,
+            additional_option_ids: (1..MAX_RANKING_LENGTH).collect(),
+            weights: vec![],
+        };
+        assert!(runoff.is_valid_vote(&longest, 30));
+        assert!(!runoff.is_valid_vote(
+            &MultipleChoiceVote {
+                option_id: 0,
+                additional_option_ids: (1..=MAX_RANKING_LENGTH).collect(),
+                weights: vec![],
+            },
+            30
+        ));
+
+        // Only first preferences are tallied.
+        let mut votes = MultipleChoiceVotes::zero(4);
+        votes
+            .add_vote(&runoff.tallied_vote(&ranking), Uint128::new(10))
+            .unwrap();
+        assert_eq!(
+            votes.vote_weights,
+            vec![
+                Uint128::zero(),
+                Uint128::zero(),
+                Uint128::new(10),
+                Uint128::zero()
+            ]
+        );
+        assert_eq!(votes.total(), Uint128::new(10));
+    }
+
     #[test]
     fn test_into_checked() {
         let options = vec![
@@ -0,0 +1,174 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Isqrt, Uint128};
+
+use crate::error::VotingError;
+
+/// A transformation applied to voters' voting power before it is
+/// tallied. This may be used to dampen the influence of large
+/// holders without changing the voting module.
+#[cw_serde]
+#[derive(Copy)]
+pub enum PowerTransform {
+    /// Voters' power is replaced by its integer square root. The
+    /// total power of a proposal is transformed the same way, so
+    /// thresholds are relative to the square root of the DAO's
+    /// total power.
+    SquareRoot {},
+    /// Voters' power is capped at `percent` of the DAO's total
+    /// power. The total power of a proposal is unchanged.
+    Cap { percent: Decimal },
+}
+
+impl PowerTransform {
+    /// Validates that a cap is a non-zero percentage no greater than
+    /// 100%.
+    pub fn validate(&self) -> Result<(), VotingError> {
+        match self {
+            PowerTransform::SquareRoot {} => Ok(()),
+            PowerTransform::Cap { percent } => {
+                if percent.is_zero() || *percent > Decimal::one() {
+                    Err(VotingError::InvalidPowerTransformCap {})
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Transforms the total power of a proposal when it is created.
+    pub fn total_power(&self, total_power: Uint128) -> Uint128 {
+        match self {
+            PowerTransform::SquareRoot {} => total_power.isqrt(),
+            PowerTransform::Cap { .. } => total_power,
+        }
+    }
+
+    /// Returns true if the transformed total power of a proposal is
+    /// at least the sum of its voters' transformed power. This does
+    /// not hold for square roots, as the sum of square roots may be
+    /// larger than the square root of the sum, so votes still to be
+    /// cast can not be bounded by the power that has not voted.
+    pub fn bounds_votes(&self) -> bool {
+        match self {
+            PowerTransform::SquareRoot {} => false,
+            PowerTransform::Cap { .. } => true,
+        }
+    }
+
+    /// Transforms a voter's POWER on a proposal whose transformed
+    /// total power is TOTAL_POWER.
+    pub fn voting_power(&self, power: Uint128, total_power: Uint128) -> Uint128 {
+        match self {
+            PowerTransform::SquareRoot {} => power.isqrt(),
+            PowerTransform::Cap { percent } => power.min(total_power * *percent),
+        }
+    }
+}
+
+/// Applies TRANSFORM, if any, to a voter's POWER on a proposal whose
+/// transformed total power is TOTAL_POWER.
+pub fn transform_voting_power(
+    transform: Option<PowerTransform>,
+    power: Uint128,
+    total_power: Uint128,
+) -> Uint128 {
+    match transform {
+        Some(transform) => transform.voting_power(power, total_power),
+        None => power,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        PowerTransform::SquareRoot {}.validate().unwrap();
+        PowerTransform::Cap {
+            percent: Decimal::one(),
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(
+            PowerTransform::Cap {
+                percent: Decimal::zero()
+            }
+            .validate(),
+            Err(VotingError::InvalidPowerTransformCap {})
+        );
+        assert_eq!(
+            PowerTransform::Cap {
+                percent: Decimal::percent(101)
+            }
+            .validate(),
+            Err(VotingError::InvalidPowerTransformCap {})
+        );
+    }
+
+    #[test]
+    fn test_square_root() {
+        let transform = PowerTransform::SquareRoot {};
+        let total = transform.total_power(Uint128::new(10_000));
+        assert_eq!(total, Uint128::new(100));
+        assert_eq!(
+            transform.voting_power(Uint128::new(9_000), total),
+            Uint128::new(94)
+        );
+        assert_eq!(
+            transform.voting_power(Uint128::new(1_000), total),
+            Uint128::new(31)
+        );
+        assert_eq!(
+            transform.voting_power(Uint128::zero(), total),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_bounds_votes() {
+        let transform = PowerTransform::SquareRoot {};
+        let total = transform.total_power(Uint128::new(100));
+        let votes = transform.voting_power(Uint128::new(50), total) * Uint128::new(2);
+        assert!(votes > total);
+        assert!(!transform.bounds_votes());
+
+        assert!(PowerTransform::Cap {
+            percent: Decimal::percent(10)
+        }
+        .bounds_votes());
+    }
+
+    #[test]
+    fn test_cap() {
+        let transform = PowerTransform::Cap {
+            percent: Decimal::percent(10),
+        };
+        let total = transform.total_power(Uint128::new(1_000));
+        assert_eq!(total, Uint128::new(1_000));
+        assert_eq!(
+            transform.voting_power(Uint128::new(900), total),
+            Uint128::new(100)
+        );
+        assert_eq!(
+            transform.voting_power(Uint128::new(50), total),
+            Uint128::new(50)
+        );
+    }
+
+    #[test]
+    fn test_no_transform() {
+        assert_eq!(
+            transform_voting_power(None, Uint128::new(9), Uint128::new(10)),
+            Uint128::new(9)
+        );
+        assert_eq!(
+            transform_voting_power(
+                Some(PowerTransform::SquareRoot {}),
+                Uint128::new(9),
+                Uint128::new(10)
+            ),
+            Uint128::new(3)
+        );
+    }
+}
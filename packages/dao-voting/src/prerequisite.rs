@@ -0,0 +1,79 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, QuerierWrapper, StdResult};
+
+use crate::status::Status;
+
+/// A proposal that must be executed before the proposal that depends
+/// on it may be executed.
+#[cw_serde]
+pub struct Prerequisite {
+    /// The address of the proposal module the prerequisite proposal
+    /// was created in. This may be the module the dependent proposal
+    /// is being created in.
+    pub proposal_module: String,
+    /// The ID of the prerequisite proposal in that module.
+    pub proposal_id: u64,
+}
+
+/// A prerequisite whose proposal module address has been validated.
+#[cw_serde]
+pub struct CheckedPrerequisite {
+    pub proposal_module: Addr,
+    pub proposal_id: u64,
+}
+
+impl Prerequisite {
+    pub fn into_checked(self, api: &dyn Api) -> StdResult<CheckedPrerequisite> {
+        Ok(CheckedPrerequisite {
+            proposal_module: api.addr_validate(&self.proposal_module)?,
+            proposal_id: self.proposal_id,
+        })
+    }
+}
+
+/// The subset of the `proposal { proposal_id }` query common to
+/// proposal modules needed to check a prerequisite.
+#[cw_serde]
+enum ProposalQuery {
+    Proposal { proposal_id: u64 },
+}
+
+// Proposal responses contain more fields than these, so these types
+// must not deny unknown fields like `cw_serde` types do.
+#[derive(cosmwasm_schema::serde::Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct ProposalResponse {
+    proposal: ProposalStatus,
+}
+
+#[derive(cosmwasm_schema::serde::Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+struct ProposalStatus {
+    status: Status,
+}
+
+impl CheckedPrerequisite {
+    /// Queries the current status of the prerequisite proposal. The
+    /// proposal module must implement a `proposal { proposal_id }`
+    /// query whose response contains `proposal.status`, as
+    /// dao-proposal-single and dao-proposal-multiple do. Errors if
+    /// the proposal does not exist.
+    pub fn query_status(&self, querier: &QuerierWrapper) -> StdResult<Status> {
+        let response: ProposalResponse = querier.query_wasm_smart(
+            &self.proposal_module,
+            &ProposalQuery::Proposal {
+                proposal_id: self.proposal_id,
+            },
+        )?;
+        Ok(response.proposal.status)
+    }
+}
+
+/// Returns true if a proposal with STATUS will never be executed,
+/// in which case proposals depending on it may be closed.
+pub fn is_failed(status: Status) -> bool {
+    matches!(
+        status,
+        Status::Rejected | Status::Closed | Status::ExecutionFailed
+    )
+}
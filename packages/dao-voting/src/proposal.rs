@@ -1,5 +1,8 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{CosmosMsg, Empty};
+use cw_utils::{Duration, Expiration};
+
+use crate::{prerequisite::Prerequisite, recurrence::Recurrence};
 
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
@@ -31,4 +34,23 @@ pub struct SingleChoiceProposeMsg {
     /// pre-propose module is attached, this must be Some and will
     /// set the proposer of the proposal it creates.
     pub proposer: Option<String>,
+    /// Proposals that must be executed before this proposal may be
+    /// executed. If any of them is rejected or closed, this proposal
+    /// may be closed.
+    #[serde(default)]
+    pub prerequisites: Vec<Prerequisite>,
+    /// If set, the proposal may not be executed before this time
+    /// once passed. Once this time is reached, any address may
+    /// execute the proposal.
+    #[serde(default)]
+    pub execute_at: Option<Expiration>,
+    /// If set, the proposal's messages are executed repeatedly on
+    /// this schedule once it is executed.
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    /// The voting period of the proposal, if the module allows
+    /// proposers to choose one. If None, the module's max voting
+    /// period is used.
+    #[serde(default)]
+    pub voting_period: Option<Duration>,
 }
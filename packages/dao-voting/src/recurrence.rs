@@ -0,0 +1,61 @@
+use cosmwasm_schema::cw_serde;
+use cw_utils::Duration;
+
+use crate::error::VotingError;
+
+/// A schedule on which a passed proposal's messages are executed
+/// repeatedly. The first instalment is executed when the proposal is
+/// executed, and each following instalment may be executed by anyone
+/// once `interval` has passed since the previous one was due.
+#[cw_serde]
+#[derive(Copy)]
+pub struct Recurrence {
+    /// The amount of time between instalments.
+    pub interval: Duration,
+    /// The total number of instalments, including the one executed
+    /// along with the proposal.
+    pub count: u64,
+}
+
+impl Recurrence {
+    /// Validates that the interval is non-zero and that there is more
+    /// than one instalment.
+    pub fn validate(&self) -> Result<(), VotingError> {
+        if matches!(self.interval, Duration::Height(0) | Duration::Time(0)) || self.count < 2 {
+            Err(VotingError::InvalidRecurrence {})
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        Recurrence {
+            interval: Duration::Time(2592000),
+            count: 12,
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(
+            Recurrence {
+                interval: Duration::Height(0),
+                count: 12,
+            }
+            .validate(),
+            Err(VotingError::InvalidRecurrence {})
+        );
+        assert_eq!(
+            Recurrence {
+                interval: Duration::Height(10),
+                count: 1,
+            }
+            .validate(),
+            Err(VotingError::InvalidRecurrence {})
+        );
+    }
+}
@@ -9,10 +9,18 @@ const FAILED_VOTE_HOOK_MASK: u64 = 0b010;
 /// handler is handling.)
 const PRE_PROPOSE_MODULE_INSTANTIATION_ID: u64 = 0b011;
 const FAILED_PRE_PROPOSE_MODULE_HOOK_ID: u64 = 0b100;
+const FAILED_PROPOSAL_MESSAGE_MASK: u64 = 0b101;
+const FAILED_INSTALMENT_MASK: u64 = 0b110;
 
 const BITS_RESERVED_FOR_REPLY_TYPE: u8 = 3;
 const REPLY_TYPE_MASK: u64 = (1 << BITS_RESERVED_FOR_REPLY_TYPE) - 1;
 
+/// Failed proposal message and instalment replies carry both a
+/// proposal ID and the index of a message in that proposal. The index
+/// is stored in the lowest bits after the reply type.
+const BITS_RESERVED_FOR_MESSAGE_INDEX: u8 = 16;
+const MESSAGE_INDEX_MASK: u64 = (1 << BITS_RESERVED_FOR_MESSAGE_INDEX) - 1;
+
 /// Since we can only pass `id`, and we need to perform different actions in reply,
 /// we decided to take few bits to identify "Reply Type".
 /// See <https://github.com/DA0-DA0/dao-contracts/pull/385#discussion_r916324843>
@@ -32,6 +40,14 @@ pub enum TaggedReplyId {
     FailedPreProposeModuleHook,
     /// Fired when a pre-propose module is successfully instantiated.
     PreProposeModuleInstantiation,
+    /// Fired when a message of a proposal executed message by
+    /// message fails. Contains the proposal ID and the index of the
+    /// message.
+    FailedProposalMessage(u64, u64),
+    /// Fired when an instalment of a recurring proposal fails.
+    /// Contains the proposal ID and, if the proposal's messages are
+    /// executed message by message, the index of the failed message.
+    FailedInstalment(u64, u64),
 }
 
 impl TaggedReplyId {
@@ -50,6 +66,14 @@ impl TaggedReplyId {
             FAILED_VOTE_HOOK_MASK => Ok(TaggedReplyId::FailedVoteHook(id_after_shift)),
             PRE_PROPOSE_MODULE_INSTANTIATION_ID => Ok(TaggedReplyId::PreProposeModuleInstantiation),
             FAILED_PRE_PROPOSE_MODULE_HOOK_ID => Ok(TaggedReplyId::FailedPreProposeModuleHook),
+            FAILED_PROPOSAL_MESSAGE_MASK => Ok(TaggedReplyId::FailedProposalMessage(
+                id_after_shift >> BITS_RESERVED_FOR_MESSAGE_INDEX,
+                id_after_shift & MESSAGE_INDEX_MASK,
+            )),
+            FAILED_INSTALMENT_MASK => Ok(TaggedReplyId::FailedInstalment(
+                id_after_shift >> BITS_RESERVED_FOR_MESSAGE_INDEX,
+                id_after_shift & MESSAGE_INDEX_MASK,
+            )),
             _ => Err(error::TagError::UnknownReplyId { id }),
         }
     }
@@ -68,6 +92,24 @@ pub const fn mask_vote_hook_index(index: u64) -> u64 {
     FAILED_VOTE_HOOK_MASK | (index << BITS_RESERVED_FOR_REPLY_TYPE)
 }
 
+/// This function can drop bits, if you have more than
+/// `u(64-[`BITS_RESERVED_FOR_REPLY_TYPE`]-[`BITS_RESERVED_FOR_MESSAGE_INDEX`])`
+/// proposals or a proposal with more than `u16` messages.
+pub const fn mask_proposal_message_index(proposal_id: u64, index: u64) -> u64 {
+    FAILED_PROPOSAL_MESSAGE_MASK
+        | (((proposal_id << BITS_RESERVED_FOR_MESSAGE_INDEX) | (index & MESSAGE_INDEX_MASK))
+            << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
+/// This function can drop bits, if you have more than
+/// `u(64-[`BITS_RESERVED_FOR_REPLY_TYPE`]-[`BITS_RESERVED_FOR_MESSAGE_INDEX`])`
+/// proposals or a proposal with more than `u16` messages.
+pub const fn mask_instalment_message_index(proposal_id: u64, index: u64) -> u64 {
+    FAILED_INSTALMENT_MASK
+        | (((proposal_id << BITS_RESERVED_FOR_MESSAGE_INDEX) | (index & MESSAGE_INDEX_MASK))
+            << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
 pub const fn pre_propose_module_instantiation_id() -> u64 {
     PRE_PROPOSE_MODULE_INSTANTIATION_ID
 }
@@ -114,8 +156,16 @@ mod test {
             TaggedReplyId::FailedVoteHook(vote_hook_idx)
         );
         assert_eq!(
-            TaggedReplyId::new(0b110).unwrap_err(),
-            error::TagError::UnknownReplyId { id: 0b110 }
+            TaggedReplyId::new(mask_proposal_message_index(2_u64.pow(45) - 1, 1234)).unwrap(),
+            TaggedReplyId::FailedProposalMessage(2_u64.pow(45) - 1, 1234)
+        );
+        assert_eq!(
+            TaggedReplyId::new(mask_instalment_message_index(2_u64.pow(45) - 1, 4321)).unwrap(),
+            TaggedReplyId::FailedInstalment(2_u64.pow(45) - 1, 4321)
+        );
+        assert_eq!(
+            TaggedReplyId::new(0b111).unwrap_err(),
+            error::TagError::UnknownReplyId { id: 0b111 }
         );
     }
 }
@@ -10,8 +10,25 @@ pub enum ThresholdError {
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Minimum quorum must be less than or equal to maximum quorum")]
+    InvalidQuorumBounds {},
+
+    #[error("Approval voting must allow at least one selection")]
+    ZeroMaxSelections {},
+
+    #[error("Approval voting may allow at most ({max}) selections")]
+    TooManyMaxSelections { max: u32 },
+
+    #[error("Proposals must have at least one winner")]
+    ZeroWinners {},
 }
 
+/// The weight (20%) given to the turnout of each newly completed
+/// proposal in the moving average of turnout used by adaptive
+/// quorums.
+pub const TURNOUT_SMOOTHING: Decimal = Decimal::raw(200_000_000_000_000_000);
+
 /// A percentage of voting power that must vote yes for a proposal to
 /// pass. An example of why this is needed:
 ///
@@ -66,10 +83,22 @@ pub enum Threshold {
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// Like `ThresholdQuorum`, except that the quorum of each proposal
+    /// is the exponential moving average of turnout on past completed
+    /// proposals, bounded by `min_quorum` and `max_quorum`. Until a
+    /// proposal has completed, `max_quorum` is used. The quorum is
+    /// fixed when a proposal is created, at which point the proposal
+    /// stores an equivalent `ThresholdQuorum` threshold.
+    AdaptiveQuorum {
+        threshold: PercentageThreshold,
+        min_quorum: Decimal,
+        max_quorum: Decimal,
+    },
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
-fn validate_percentage(percent: &PercentageThreshold) -> Result<(), ThresholdError> {
+pub(crate) fn validate_percentage(percent: &PercentageThreshold) -> Result<(), ThresholdError> {
     if let PercentageThreshold::Percent(percent) = percent {
         if percent.is_zero() {
             Err(ThresholdError::ZeroThreshold {})
@@ -119,8 +148,104 @@ impl Threshold {
                     Ok(())
                 }
             }
+            Threshold::AdaptiveQuorum {
+                threshold,
+                min_quorum,
+                max_quorum,
+            } => {
+                validate_percentage(threshold)?;
+                validate_quorum(&PercentageThreshold::Percent(*max_quorum))?;
+                if min_quorum > max_quorum {
+                    Err(ThresholdError::InvalidQuorumBounds {})
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Returns the threshold a proposal created when the moving
+    /// average of turnout on past proposals is TURNOUT should
+    /// use. Adaptive quorums are converted into a `ThresholdQuorum`
+    /// with a fixed quorum. Other thresholds are returned unchanged.
+    pub fn fix_quorum(&self, turnout: Option<Decimal>) -> Threshold {
+        match self {
+            Threshold::AdaptiveQuorum {
+                threshold,
+                min_quorum,
+                max_quorum,
+            } => Threshold::ThresholdQuorum {
+                threshold: *threshold,
+                quorum: PercentageThreshold::Percent(
+                    turnout
+                        .unwrap_or(*max_quorum)
+                        .clamp(*min_quorum, *max_quorum),
+                ),
+            },
+            _ => self.clone(),
         }
     }
+
+    /// Returns this threshold with its quorum raised to at least
+    /// QUORUM. The bounds of an adaptive quorum are raised instead, so
+    /// that it keeps adapting; as the bounds are percentages, a
+    /// majority is taken as 50% there. Thresholds without a quorum are
+    /// returned unchanged.
+    pub fn raise_quorum(&self, quorum: PercentageThreshold) -> Threshold {
+        match self {
+            Threshold::ThresholdQuorum {
+                threshold,
+                quorum: current,
+            } => Threshold::ThresholdQuorum {
+                threshold: *threshold,
+                quorum: stricter_quorum(*current, quorum),
+            },
+            Threshold::AdaptiveQuorum {
+                threshold,
+                min_quorum,
+                max_quorum,
+            } => {
+                let floor = match quorum {
+                    PercentageThreshold::Majority {} => Decimal::percent(50),
+                    PercentageThreshold::Percent(percent) => percent,
+                };
+                Threshold::AdaptiveQuorum {
+                    threshold: *threshold,
+                    min_quorum: (*min_quorum).max(floor),
+                    max_quorum: (*max_quorum).max(floor),
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Returns the stricter of the quorums A and B. A majority is
+/// stricter than 50%, but not than any higher percentage.
+pub fn stricter_quorum(a: PercentageThreshold, b: PercentageThreshold) -> PercentageThreshold {
+    match (a, b) {
+        (PercentageThreshold::Percent(a), PercentageThreshold::Percent(b)) => {
+            PercentageThreshold::Percent(a.max(b))
+        }
+        (PercentageThreshold::Percent(p), PercentageThreshold::Majority {})
+        | (PercentageThreshold::Majority {}, PercentageThreshold::Percent(p))
+            if p > Decimal::percent(50) =>
+        {
+            PercentageThreshold::Percent(p)
+        }
+        _ => PercentageThreshold::Majority {},
+    }
+}
+
+/// Updates AVERAGE, the moving average of turnout on completed
+/// proposals, with the TURNOUT of a newly completed proposal.
+pub fn update_turnout(average: Option<Decimal>, turnout: Decimal) -> Decimal {
+    match average {
+        Some(average) => {
+            average * (Decimal::one() - TURNOUT_SMOOTHING) + turnout * TURNOUT_SMOOTHING
+        }
+        None => turnout,
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +305,117 @@ mod tests {
             ThresholdError::UnreachableThreshold {}
         );
     }
+
+    #[test]
+    fn test_adaptive_quorum() {
+        let t = Threshold::AdaptiveQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(40),
+        };
+        t.validate().unwrap();
+
+        let quorum = |turnout| match t.fix_quorum(turnout) {
+            Threshold::ThresholdQuorum { threshold, quorum } => {
+                assert_eq!(threshold, PercentageThreshold::Majority {});
+                quorum
+            }
+            t => panic!("expected a fixed quorum, got {t:?}"),
+        };
+        assert_eq!(quorum(None), p!(40));
+        assert_eq!(quorum(Some(Decimal::percent(25))), p!(25));
+        assert_eq!(quorum(Some(Decimal::percent(5))), p!(10));
+        assert_eq!(quorum(Some(Decimal::percent(90))), p!(40));
+
+        let t = Threshold::AbsoluteCount {
+            threshold: Uint128::new(1),
+        };
+        assert_eq!(t.fix_quorum(Some(Decimal::percent(10))), t);
+
+        let t = Threshold::AdaptiveQuorum {
+            threshold: p!(50),
+            min_quorum: Decimal::percent(40),
+            max_quorum: Decimal::percent(10),
+        };
+        assert_eq!(
+            t.validate().unwrap_err(),
+            ThresholdError::InvalidQuorumBounds {}
+        );
+
+        let t = Threshold::AdaptiveQuorum {
+            threshold: p!(50),
+            min_quorum: Decimal::percent(40),
+            max_quorum: Decimal::percent(101),
+        };
+        assert_eq!(
+            t.validate().unwrap_err(),
+            ThresholdError::UnreachableThreshold {}
+        );
+    }
+
+    #[test]
+    fn test_update_turnout() {
+        assert_eq!(
+            update_turnout(None, Decimal::percent(30)),
+            Decimal::percent(30)
+        );
+        // 0.8 * 0.3 + 0.2 * 0.8
+        assert_eq!(
+            update_turnout(Some(Decimal::percent(30)), Decimal::percent(80)),
+            Decimal::percent(40)
+        );
+    }
+
+    #[test]
+    fn test_raise_quorum() {
+        let t = Threshold::ThresholdQuorum {
+            threshold: p!(50),
+            quorum: p!(20),
+        };
+        assert_eq!(
+            t.raise_quorum(p!(60)),
+            Threshold::ThresholdQuorum {
+                threshold: p!(50),
+                quorum: p!(60),
+            }
+        );
+        assert_eq!(t.raise_quorum(p!(10)), t);
+
+        // Adaptive quorums keep adapting within raised bounds.
+        let t = Threshold::AdaptiveQuorum {
+            threshold: p!(50),
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(40),
+        };
+        assert_eq!(
+            t.raise_quorum(p!(30)),
+            Threshold::AdaptiveQuorum {
+                threshold: p!(50),
+                min_quorum: Decimal::percent(30),
+                max_quorum: Decimal::percent(40),
+            }
+        );
+        assert_eq!(
+            t.raise_quorum(PercentageThreshold::Majority {}),
+            Threshold::AdaptiveQuorum {
+                threshold: p!(50),
+                min_quorum: Decimal::percent(50),
+                max_quorum: Decimal::percent(50),
+            }
+        );
+        assert_eq!(t.raise_quorum(p!(5)), t);
+
+        let t = Threshold::AbsolutePercentage { percentage: p!(50) };
+        assert_eq!(t.raise_quorum(p!(60)), t);
+    }
+
+    #[test]
+    fn test_stricter_quorum() {
+        let majority = PercentageThreshold::Majority {};
+        assert_eq!(stricter_quorum(p!(20), p!(30)), p!(30));
+        assert_eq!(stricter_quorum(p!(50), majority), majority);
+        assert_eq!(stricter_quorum(majority, p!(40)), majority);
+        assert_eq!(stricter_quorum(p!(51), majority), p!(51));
+        assert_eq!(stricter_quorum(majority, majority), majority);
+    }
 }
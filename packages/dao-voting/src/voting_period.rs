@@ -0,0 +1,265 @@
+use cosmwasm_schema::cw_serde;
+use cw_utils::Duration;
+
+use crate::{
+    error::VotingError,
+    threshold::{stricter_quorum, validate_quorum, PercentageThreshold},
+};
+
+/// Configuration allowing proposers to choose the voting period of
+/// their proposals. This lets urgent proposals, like security fixes,
+/// be voted on quickly while routine ones stay open for longer.
+#[cw_serde]
+pub struct VotingPeriodChoice {
+    /// The shortest voting period a proposer may choose. The longest
+    /// is the module's max voting period, which is also used if a
+    /// proposer does not choose one.
+    pub min: Duration,
+    /// Quorums required of proposals with short voting periods. A
+    /// proposal uses the quorum of the step with the shortest
+    /// `max_voting_period` that is at least as long as its voting
+    /// period, or the module's quorum if no step applies. Steps for
+    /// shorter periods may not require lower quorums, and a step
+    /// never lowers a proposal's quorum below the module's.
+    #[serde(default)]
+    pub quorum_schedule: Vec<QuorumStep>,
+}
+
+/// A quorum required of proposals whose voting period is no longer
+/// than `max_voting_period`.
+#[cw_serde]
+pub struct QuorumStep {
+    /// The longest voting period this step applies to.
+    pub max_voting_period: Duration,
+    /// The quorum required of proposals this step applies to.
+    pub quorum: PercentageThreshold,
+}
+
+/// Returns the length of DURATION in its units.
+fn length(duration: &Duration) -> u64 {
+    match duration {
+        Duration::Height(height) => *height,
+        Duration::Time(time) => *time,
+    }
+}
+
+/// Returns an error if A and B do not have the same units (height or
+/// time).
+fn check_units(a: &Duration, b: &Duration) -> Result<(), VotingError> {
+    match (a, b) {
+        (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_)) => {
+            Ok(())
+        }
+        _ => Err(VotingError::DurationUnitsConflict {}),
+    }
+}
+
+impl VotingPeriodChoice {
+    /// Validates that the shortest choosable voting period is
+    /// non-zero and between the module's min and max voting periods,
+    /// and that every step of the quorum schedule applies to a
+    /// choosable voting period and has a valid quorum no lower than
+    /// the quorums of steps for longer periods.
+    pub fn validate(
+        &self,
+        min_voting_period: Option<&Duration>,
+        max_voting_period: &Duration,
+    ) -> Result<(), VotingError> {
+        check_units(&self.min, max_voting_period)?;
+        let floor = match min_voting_period {
+            Some(min_voting_period) => {
+                check_units(min_voting_period, max_voting_period)?;
+                length(min_voting_period).max(1)
+            }
+            None => 1,
+        };
+        if length(&self.min) < floor || length(&self.min) > length(max_voting_period) {
+            return Err(VotingError::InvalidVotingPeriodChoice {});
+        }
+        for step in &self.quorum_schedule {
+            check_units(&step.max_voting_period, max_voting_period)?;
+            let period = length(&step.max_voting_period);
+            if period < length(&self.min)
+                || period > length(max_voting_period)
+                || validate_quorum(&step.quorum).is_err()
+            {
+                return Err(VotingError::InvalidQuorumSchedule {});
+            }
+        }
+        for shorter in &self.quorum_schedule {
+            for longer in &self.quorum_schedule {
+                if length(&shorter.max_voting_period) < length(&longer.max_voting_period)
+                    && stricter_quorum(shorter.quorum, longer.quorum) != shorter.quorum
+                {
+                    return Err(VotingError::InvalidQuorumSchedule {});
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the quorum the schedule requires of proposals with
+    /// VOTING_PERIOD, if any.
+    pub fn quorum(&self, voting_period: &Duration) -> Option<PercentageThreshold> {
+        self.quorum_schedule
+            .iter()
+            .filter(|step| {
+                check_units(&step.max_voting_period, voting_period).is_ok()
+                    && length(voting_period) <= length(&step.max_voting_period)
+            })
+            .min_by_key(|step| length(&step.max_voting_period))
+            .map(|step| step.quorum)
+    }
+}
+
+/// Returns the voting period of a new proposal whose proposer chose
+/// CHOSEN, or the module's MAX_VOTING_PERIOD if they did not
+/// choose. Errors if a period was chosen and CHOICE does not allow
+/// it.
+pub fn proposal_voting_period(
+    choice: Option<&VotingPeriodChoice>,
+    chosen: Option<Duration>,
+    max_voting_period: Duration,
+) -> Result<Duration, VotingError> {
+    match (chosen, choice) {
+        (None, _) => Ok(max_voting_period),
+        (Some(_), None) => Err(VotingError::VotingPeriodNotChoosable {}),
+        (Some(chosen), Some(choice)) => {
+            check_units(&chosen, &max_voting_period)?;
+            if length(&chosen) < length(&choice.min) || length(&chosen) > length(&max_voting_period)
+            {
+                Err(VotingError::InvalidVotingPeriod {})
+            } else {
+                Ok(chosen)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Decimal;
+
+    use super::*;
+
+    fn choice() -> VotingPeriodChoice {
+        VotingPeriodChoice {
+            min: Duration::Time(100),
+            quorum_schedule: vec![
+                QuorumStep {
+                    max_voting_period: Duration::Time(500),
+                    quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+                },
+                QuorumStep {
+                    max_voting_period: Duration::Time(200),
+                    quorum: PercentageThreshold::Majority {},
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let max = Duration::Time(1000);
+        choice().validate(None, &max).unwrap();
+        choice().validate(Some(&Duration::Time(100)), &max).unwrap();
+
+        assert_eq!(
+            choice()
+                .validate(Some(&Duration::Time(101)), &max)
+                .unwrap_err(),
+            VotingError::InvalidVotingPeriodChoice {}
+        );
+        assert_eq!(
+            choice().validate(None, &Duration::Time(99)).unwrap_err(),
+            VotingError::InvalidVotingPeriodChoice {}
+        );
+        assert_eq!(
+            VotingPeriodChoice {
+                min: Duration::Time(0),
+                quorum_schedule: vec![],
+            }
+            .validate(None, &max)
+            .unwrap_err(),
+            VotingError::InvalidVotingPeriodChoice {}
+        );
+        assert_eq!(
+            choice()
+                .validate(None, &Duration::Height(1000))
+                .unwrap_err(),
+            VotingError::DurationUnitsConflict {}
+        );
+
+        // Steps must apply to choosable voting periods.
+        assert_eq!(
+            choice().validate(None, &Duration::Time(400)).unwrap_err(),
+            VotingError::InvalidQuorumSchedule {}
+        );
+        let mut invalid = choice();
+        invalid.quorum_schedule[0].quorum = PercentageThreshold::Percent(Decimal::percent(101));
+        assert_eq!(
+            invalid.validate(None, &max).unwrap_err(),
+            VotingError::InvalidQuorumSchedule {}
+        );
+
+        // Shorter periods may not require lower quorums.
+        let mut decreasing = choice();
+        decreasing.quorum_schedule[1].quorum = PercentageThreshold::Percent(Decimal::percent(20));
+        assert_eq!(
+            decreasing.validate(None, &max).unwrap_err(),
+            VotingError::InvalidQuorumSchedule {}
+        );
+        decreasing.quorum_schedule[1].quorum = PercentageThreshold::Percent(Decimal::percent(30));
+        decreasing.validate(None, &max).unwrap();
+    }
+
+    #[test]
+    fn test_quorum() {
+        let choice = choice();
+        assert_eq!(
+            choice.quorum(&Duration::Time(100)),
+            Some(PercentageThreshold::Majority {})
+        );
+        assert_eq!(
+            choice.quorum(&Duration::Time(200)),
+            Some(PercentageThreshold::Majority {})
+        );
+        assert_eq!(
+            choice.quorum(&Duration::Time(201)),
+            Some(PercentageThreshold::Percent(Decimal::percent(30)))
+        );
+        assert_eq!(choice.quorum(&Duration::Time(501)), None);
+    }
+
+    #[test]
+    fn test_proposal_voting_period() {
+        let max = Duration::Time(1000);
+        let choice = choice();
+
+        assert_eq!(proposal_voting_period(None, None, max).unwrap(), max);
+        assert_eq!(
+            proposal_voting_period(Some(&choice), None, max).unwrap(),
+            max
+        );
+        assert_eq!(
+            proposal_voting_period(None, Some(Duration::Time(100)), max).unwrap_err(),
+            VotingError::VotingPeriodNotChoosable {}
+        );
+        assert_eq!(
+            proposal_voting_period(Some(&choice), Some(Duration::Time(100)), max).unwrap(),
+            Duration::Time(100)
+        );
+        assert_eq!(
+            proposal_voting_period(Some(&choice), Some(Duration::Time(99)), max).unwrap_err(),
+            VotingError::InvalidVotingPeriod {}
+        );
+        assert_eq!(
+            proposal_voting_period(Some(&choice), Some(Duration::Time(1001)), max).unwrap_err(),
+            VotingError::InvalidVotingPeriod {}
+        );
+        assert_eq!(
+            proposal_voting_period(Some(&choice), Some(Duration::Height(100)), max).unwrap_err(),
+            VotingError::DurationUnitsConflict {}
+        );
+    }
+}
@@ -149,6 +149,14 @@ fn test_counters() {
         allow_revoting: false,
         pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
         close_proposal_on_execution_failure: true,
+        anti_sniping: None,
+        threshold_rules: vec![],
+        commit_reveal: None,
+        delegation_registry: None,
+        partial_execution: false,
+        power_transform: None,
+        review_period: None,
+        voting_period_choice: None,
     };
 
     let governance_addr =
@@ -253,6 +261,10 @@ fn test_counters() {
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
             proposer: None,
+            prerequisites: vec![],
+            execute_at: None,
+            recurrence: None,
+            voting_period: None,
         }),
         &[],
     )
@@ -353,6 +365,10 @@ fn test_counters() {
             description: "This is a simple text proposal 2nd".to_string(),
             msgs: vec![],
             proposer: None,
+            prerequisites: vec![],
+            execute_at: None,
+            recurrence: None,
+            voting_period: None,
         }),
         &[],
     )